@@ -6,12 +6,18 @@ use std::process::ExitCode;
 use serde::Deserialize;
 
 use crate::core::{
-    Anchor, ChangeKind, ChangedFile, Comment, CommentContext, CommentStatus, CommentStore,
-    DiffResult, DiffSource, FileCommentStore, RelPath, RepoError, RepoRoot, Selector, TextBuffer,
+    Anchor, AnchorConfidence, AnchorMatch, AnchorStatus, ChangeKind, ChangedFile, Comment,
+    CommentBackend, CommentContext, CommentKind, CommentReply, CommentStatus, ConfigOverrides,
+    DiffResult, DiffSide, DiffSource, FileCommentStore, FileViewedStore, MemoryViewedStore,
+    NewComment, PathFilter, PullOutcome, RelPath, RepoError, RepoRoot, Selector, TextBuffer,
+    ViewedStore, apply_suggestion_to_file, comment_author, context_from_hunk,
     format_anchor_summary, list_changed_files, list_changed_files_between,
     list_changed_files_from_base_with_merge_base, list_commit_files, load_diff_contents,
-    resolve_revision, selector_from_hunk,
+    load_preferences, open_comment_store, parse_cargo_diagnostics, parse_sarif, pull_comments,
+    push_comments, relocate_anchor, resolve_revision, selector_from_context, selector_from_hunk,
+    selector_from_line_range, split_suggestion, suggestion_original,
 };
+use crate::report::{ReportFormat, ReportInput, build_review_report};
 
 /// Run a comments subcommand.
 /// Returns ExitCode for the process.
@@ -20,16 +26,26 @@ pub fn run_comments_command(repo: &RepoRoot, args: &[String]) -> ExitCode {
         eprintln!("Usage: quickdiff comments <command>");
         eprintln!("Commands:");
         eprintln!(
-            "  list [--all] [--json] [--path <path>] [--worktree|--base <ref>|--commit <rev>|--range <from>..<to>]"
+            "  list [--all] [--json] [--path <path>] [--author <name>] [-f <pathspec>]... [--worktree|--base <ref>|--commit <rev>|--range <from>..<to>]"
         );
         eprintln!(
-            "  add  [--worktree|--base <ref>|--commit <rev>|--range <from>..<to>] --path <path> (--hunk <n>|--old-line <n>|--new-line <n>) --message <text>"
+            "  add  [--worktree|--base <ref>|--commit <rev>|--range <from>..<to>] --path <path> (--hunk <n>|--old-line <n>[-<m>]|--new-line <n>[-<m>]) --message <text> [--kind bug|warning|suggestion|good] [--suggestion <text>] [--author <name>]"
         );
         eprintln!(
-            "  import --json <file> [--worktree|--base <ref>|--commit <rev>|--range <from>..<to>]"
+            "  import (--json|--sarif|--cargo-json) <file> [--author <name>] [--worktree|--base <ref>|--commit <rev>|--range <from>..<to>]"
         );
         eprintln!("  next [--json] [--worktree|--base <ref>|--commit <rev>|--range <from>..<to>]");
+        eprintln!("  reply <id> --message <text> [--author <name>]");
+        eprintln!("  edit <id> --message <text>");
         eprintln!("  resolve <id>");
+        eprintln!("  reopen <id>");
+        eprintln!("  apply <id>");
+        eprintln!("  delete <id>");
+        eprintln!("  push [<remote>]");
+        eprintln!("  pull [<remote>]");
+        eprintln!(
+            "  export [--format markdown|text] [--output <file>] [--worktree|--base <ref>|--commit <rev>|--range <from>..<to>]"
+        );
         return ExitCode::from(1);
     }
 
@@ -41,7 +57,15 @@ pub fn run_comments_command(repo: &RepoRoot, args: &[String]) -> ExitCode {
         "add" => cmd_add(repo, cmd_args),
         "import" => cmd_import(repo, cmd_args),
         "next" => cmd_next(repo, cmd_args),
+        "reply" => cmd_reply(repo, cmd_args),
+        "edit" => cmd_edit(repo, cmd_args),
         "resolve" => cmd_resolve(repo, cmd_args),
+        "reopen" => cmd_reopen(repo, cmd_args),
+        "apply" => cmd_apply(repo, cmd_args),
+        "delete" => cmd_delete(repo, cmd_args),
+        "push" => cmd_push(repo, cmd_args),
+        "pull" => cmd_pull(repo, cmd_args),
+        "export" => cmd_export(repo, cmd_args),
         _ => {
             eprintln!("Unknown command: {}", cmd);
             ExitCode::from(1)
@@ -59,7 +83,17 @@ const FLAGS_WITH_VALUES: &[&str] = &[
     "--old-line",
     "--new-line",
     "--message",
+    "--kind",
+    "--suggestion",
+    "--author",
     "--json",
+    "--sarif",
+    "--cargo-json",
+    "--file",
+    "--format",
+    "--output",
+    "-o",
+    "-f",
     "-m",
 ];
 
@@ -69,6 +103,28 @@ fn takes_value(arg: &str) -> bool {
     FLAGS_WITH_VALUES.contains(&flag_name)
 }
 
+/// Author for new comments and replies: `--author`, else [`comment_author`].
+fn parse_author(repo: &RepoRoot, args: &[String]) -> Result<Option<String>, String> {
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--author" => {
+                let Some(author) = args.get(i + 1).map(|a| a.trim()) else {
+                    return Err("--author requires a value".to_string());
+                };
+                if author.is_empty() {
+                    return Err("--author must not be empty".to_string());
+                }
+                return Ok(Some(author.to_string()));
+            }
+            other if takes_value(other) => i += 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    Ok(comment_author(repo))
+}
+
 fn parse_context(
     repo: &RepoRoot,
     args: &[String],
@@ -124,55 +180,73 @@ fn parse_context(
         return Err("Only one of --worktree/--base/--commit/--range may be specified".to_string());
     }
 
-    if worktree {
-        return Ok(Some((CommentContext::Worktree, DiffSource::WorkingTree)));
-    }
+    let context = if let Some(base) = base {
+        CommentContext::Base { base }
+    } else if let Some(commit) = commit {
+        CommentContext::Commit { commit }
+    } else if let Some(range) = range {
+        let (from, to) = parse_range(&range)?;
+        CommentContext::Range { from, to }
+    } else {
+        CommentContext::Worktree
+    };
+    resolve_context(repo, context).map(Some)
+}
 
-    if let Some(base) = base {
-        return Ok(Some((
+/// Resolve the revisions in a context to commit SHAs and pair it with its diff source.
+///
+/// Empty range ends default to the working copy's parent. PR contexts have no local source.
+pub(super) fn resolve_context(
+    repo: &RepoRoot,
+    context: CommentContext,
+) -> Result<(CommentContext, DiffSource), String> {
+    match context {
+        CommentContext::Unscoped | CommentContext::Worktree => {
+            Ok((CommentContext::Worktree, DiffSource::WorkingTree))
+        }
+        CommentContext::Base { base } => Ok((
             CommentContext::Base { base: base.clone() },
             DiffSource::Base(base),
-        )));
-    }
-
-    if let Some(commit) = commit {
-        let sha = resolve_revision(repo, &commit)
-            .map_err(|e| format!("Failed to resolve commit {}: {}", commit, e))?;
-        return Ok(Some((
-            CommentContext::Commit {
-                commit: sha.clone(),
-            },
-            DiffSource::Commit(sha),
-        )));
-    }
+        )),
+        CommentContext::Commit { commit } => {
+            let sha = resolve_revision(repo, &commit)
+                .map_err(|e| format!("Failed to resolve commit {}: {}", commit, e))?;
+            Ok((
+                CommentContext::Commit {
+                    commit: sha.clone(),
+                },
+                DiffSource::Commit(sha),
+            ))
+        }
+        CommentContext::Range { mut from, mut to } => {
+            let default_ref = repo.working_copy_parent_ref();
+            if from.is_empty() {
+                from = default_ref.to_string();
+            }
+            if to.is_empty() {
+                to = default_ref.to_string();
+            }
 
-    if let Some(range) = range {
-        let (mut from, mut to) = parse_range(&range)?;
-        let default_ref = repo.working_copy_parent_ref();
-        if from.is_empty() {
-            from = default_ref.to_string();
-        }
-        if to.is_empty() {
-            to = default_ref.to_string();
-        }
-
-        let from_sha = resolve_revision(repo, &from)
-            .map_err(|e| format!("Failed to resolve revision {}: {}", from, e))?;
-        let to_sha = resolve_revision(repo, &to)
-            .map_err(|e| format!("Failed to resolve revision {}: {}", to, e))?;
-        return Ok(Some((
-            CommentContext::Range {
-                from: from_sha.clone(),
-                to: to_sha.clone(),
-            },
-            DiffSource::Range {
-                from: from_sha,
-                to: to_sha,
-            },
-        )));
+            let from_sha = resolve_revision(repo, &from)
+                .map_err(|e| format!("Failed to resolve revision {}: {}", from, e))?;
+            let to_sha = resolve_revision(repo, &to)
+                .map_err(|e| format!("Failed to resolve revision {}: {}", to, e))?;
+            Ok((
+                CommentContext::Range {
+                    from: from_sha.clone(),
+                    to: to_sha.clone(),
+                },
+                DiffSource::Range {
+                    from: from_sha,
+                    to: to_sha,
+                },
+            ))
+        }
+        CommentContext::PullRequest { number } => Err(format!(
+            "Pull request #{} diffs are not available locally",
+            number
+        )),
     }
-
-    Ok(None)
 }
 
 fn parse_range(s: &str) -> Result<(String, String), String> {
@@ -188,16 +262,20 @@ fn parse_range(s: &str) -> Result<(String, String), String> {
     Ok((from.to_string(), to.to_string()))
 }
 
-fn list_files_for_source(
+/// Changed files of a local diff source, with the merge base for `--base`.
+pub(super) fn list_files_for_source(
     repo: &RepoRoot,
     source: &DiffSource,
 ) -> Result<(Vec<ChangedFile>, Option<String>), RepoError> {
+    let renames = load_preferences(repo.path(), &ConfigOverrides::default()).renames;
     match source {
         DiffSource::WorkingTree => Ok((list_changed_files(repo)?, None)),
-        DiffSource::Commit(commit) => Ok((list_commit_files(repo, commit)?, None)),
-        DiffSource::Range { from, to } => Ok((list_changed_files_between(repo, from, to)?, None)),
+        DiffSource::Commit(commit) => Ok((list_commit_files(repo, commit, &renames)?, None)),
+        DiffSource::Range { from, to } | DiffSource::Evolog { from, to, .. } => {
+            Ok((list_changed_files_between(repo, from, to, &renames)?, None))
+        }
         DiffSource::Base(base) => {
-            let result = list_changed_files_from_base_with_merge_base(repo, base)?;
+            let result = list_changed_files_from_base_with_merge_base(repo, base, &renames)?;
             Ok((result.files, Some(result.merge_base)))
         }
         DiffSource::FilePair { .. } | DiffSource::DiffTool { .. } => Ok((Vec::new(), None)),
@@ -222,6 +300,71 @@ fn context_summary(ctx: &CommentContext) -> String {
             let short_to: String = to.chars().take(7).collect();
             format!("range:{}..{}", short_from, short_to)
         }
+        CommentContext::PullRequest { number } => format!("pr:#{}", number),
+    }
+}
+
+/// Local diff source a comment context refers to.
+///
+/// PR diffs are fetched from GitHub, so their comments cannot be checked locally.
+pub(super) fn source_for_context(ctx: &CommentContext) -> Option<DiffSource> {
+    match ctx {
+        CommentContext::Unscoped | CommentContext::Worktree => Some(DiffSource::WorkingTree),
+        CommentContext::Base { base } => Some(DiffSource::Base(base.clone())),
+        CommentContext::Commit { commit } if commit.starts_with("pr-") => None,
+        CommentContext::Commit { commit } => Some(DiffSource::Commit(commit.clone())),
+        CommentContext::Range { from, to } => Some(DiffSource::Range {
+            from: from.clone(),
+            to: to.clone(),
+        }),
+        CommentContext::PullRequest { .. } => None,
+    }
+}
+
+pub(super) type LoadedDiff = (TextBuffer, TextBuffer, DiffResult);
+type SourceFiles = (Vec<ChangedFile>, Option<String>);
+
+/// Places comments on the diff of their own context, loading each file at most once.
+#[derive(Default)]
+struct CommentLocator {
+    files: HashMap<CommentContext, Option<SourceFiles>>,
+    diffs: HashMap<(CommentContext, String), Option<LoadedDiff>>,
+}
+
+impl CommentLocator {
+    fn locate(&mut self, repo: &RepoRoot, comment: &Comment) -> Option<AnchorMatch> {
+        let source = source_for_context(&comment.context)?;
+        let files = self
+            .files
+            .entry(comment.context.clone())
+            .or_insert_with(|| list_files_for_source(repo, &source).ok());
+        let key = (comment.context.clone(), comment.path.as_str().to_string());
+        let loaded = self.diffs.entry(key).or_insert_with(|| {
+            let (files, merge_base) = files.as_ref()?;
+            let file = files.iter().find(|f| f.path == comment.path)?;
+            let (old_bytes, new_bytes) =
+                load_diff_contents(repo, &source, file, merge_base.as_deref()).ok()?;
+            let old = TextBuffer::new(&old_bytes);
+            let new = TextBuffer::new(&new_bytes);
+            let diff = DiffResult::compute(&old, &new);
+            Some((old, new, diff))
+        });
+        let (old, new, diff) = loaded.as_ref()?;
+        relocate_anchor(&comment.anchor, diff, old, new)
+    }
+}
+
+/// Short status suffix for text output, empty when the comment is in place.
+fn anchor_status_suffix(found: Option<&AnchorMatch>) -> String {
+    match (AnchorStatus::of(found), found) {
+        (AnchorStatus::Relocated, Some(m)) => format!(
+            " (relocated to {} L{}, {} confidence)",
+            m.side.as_str(),
+            m.start_line + 1,
+            m.confidence.as_str()
+        ),
+        (AnchorStatus::Stale, _) => " (stale)".to_string(),
+        _ => String::new(),
     }
 }
 
@@ -230,12 +373,22 @@ fn cmd_list(repo: &RepoRoot, args: &[String]) -> ExitCode {
     let mut include_resolved = false;
     let mut json_output = false;
     let mut filter_path: Option<String> = None;
+    let mut filter_author: Option<String> = None;
+    let mut pathspecs: Vec<String> = Vec::new();
 
     let mut i = 0;
     while i < args.len() {
         let arg = args[i].as_str();
         match arg {
             "--all" => include_resolved = true,
+            "--author" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("--author requires a value");
+                    return ExitCode::from(1);
+                }
+                filter_author = Some(args[i].to_lowercase());
+            }
             "--json" => json_output = true,
             "--path" => {
                 i += 1;
@@ -245,6 +398,14 @@ fn cmd_list(repo: &RepoRoot, args: &[String]) -> ExitCode {
                 }
                 filter_path = Some(args[i].clone());
             }
+            "--file" | "-f" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("{} requires a value", arg);
+                    return ExitCode::from(1);
+                }
+                pathspecs.push(args[i].clone());
+            }
             // Skip values for other known flags
             other if takes_value(other) => {
                 i += 1;
@@ -262,7 +423,7 @@ fn cmd_list(repo: &RepoRoot, args: &[String]) -> ExitCode {
         }
     };
 
-    let store = match FileCommentStore::open(repo) {
+    let store = match open_comment_store(repo) {
         Ok(s) => s,
         Err(e) => {
             eprintln!("Failed to open comment store: {}", e);
@@ -286,22 +447,48 @@ fn cmd_list(repo: &RepoRoot, args: &[String]) -> ExitCode {
     if let Some(ctx) = context_filter {
         comments.retain(|c| c.context.matches(&ctx));
     }
+    if let Some(author) = filter_author {
+        // Substring match, so a name or an email address both work.
+        comments.retain(|c| {
+            c.author
+                .as_deref()
+                .is_some_and(|a| a.to_lowercase().contains(&author))
+        });
+    }
+
+    let loaded = load_preferences(repo.path(), &ConfigOverrides::default());
+    let file_filter = PathFilter::new(&pathspecs).with_excludes(&loaded.excludes);
+    comments.retain(|c| file_filter.matches(c.path.as_str()));
+
+    let mut locator = CommentLocator::default();
 
     if json_output {
         let json_comments: Vec<_> = comments
             .iter()
             .map(|c| {
+                let found = locator.locate(repo, c);
+                let anchor_status =
+                    source_for_context(&c.context).map(|_| AnchorStatus::of(found.as_ref()));
                 serde_json::json!({
                     "id": c.id,
                     "path": c.path.as_str(),
                     "context": &c.context,
                     "context_summary": context_summary(&c.context),
                     "status": c.status,
+                    "author": &c.author,
                     "message": &c.message,
+                    "kind": c.kind,
+                    "suggestion": &c.suggestion,
                     "anchor": &c.anchor,
                     "anchor_summary": format_anchor_summary(&c.anchor),
+                    "replies": &c.replies,
+                    "edits": &c.edits,
+                    "anchor_status": anchor_status,
+                    "confidence": found.as_ref().map(|m| m.confidence),
+                    "location": &found,
                     "created_at_ms": c.created_at_ms,
                     "resolved_at_ms": c.resolved_at_ms,
+                    "edited_at_ms": c.edited_at_ms(),
                 })
             })
             .collect();
@@ -322,15 +509,35 @@ fn cmd_list(repo: &RepoRoot, args: &[String]) -> ExitCode {
             } else {
                 "RESOLVED"
             };
+            let edited = if c.edits.is_empty() { "" } else { " (edited)" };
+            let kind = c
+                .kind
+                .map(|k| format!(" [{}]", k.as_str()))
+                .unwrap_or_default();
+            let author = c
+                .author
+                .as_deref()
+                .map(|a| format!("{}: ", a))
+                .unwrap_or_default();
             println!(
-                "[{}] {} ({}, {}) - {}",
+                "[{}] {} ({}, {}){} - {}{}{}",
                 c.id,
                 c.path.as_str(),
                 status,
                 context_summary(&c.context),
-                c.message
+                kind,
+                author,
+                c.message,
+                edited
             );
-            println!("    {}", format_anchor_summary(&c.anchor));
+            let found = locator.locate(repo, c);
+            let suffix = match source_for_context(&c.context) {
+                Some(_) => anchor_status_suffix(found.as_ref()),
+                None => String::new(),
+            };
+            println!("    {}{}", format_anchor_summary(&c.anchor), suffix);
+            print_suggestion(c);
+            print_replies(&c.replies);
         }
     }
 
@@ -344,9 +551,19 @@ struct NextComment<'a> {
     context: &'a CommentContext,
     context_summary: String,
     status: CommentStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<&'a str>,
     message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<CommentKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggestion: Option<&'a str>,
     anchor: &'a Anchor,
     anchor_summary: String,
+    replies: &'a [CommentReply],
+    anchor_status: AnchorStatus,
+    confidence: AnchorConfidence,
+    location: &'a AnchorMatch,
     hunk_digest: &'a str,
     hunk_index: usize,
     hunk: NextHunkContext,
@@ -375,6 +592,35 @@ struct ImportComment {
     path: String,
     hunk_digest: String,
     message: String,
+    #[serde(default)]
+    kind: Option<CommentKind>,
+}
+
+/// Input formats accepted by `comments import`.
+#[derive(Debug, Clone, Copy)]
+enum ImportFormat {
+    Json,
+    Sarif,
+    CargoJson,
+}
+
+/// Where an imported comment should be anchored.
+#[derive(Debug)]
+enum ImportTarget {
+    Hunk(String),
+    /// New-side lines (0-indexed, inclusive).
+    Lines(usize, usize),
+}
+
+/// A comment to import, normalized from any input format.
+#[derive(Debug)]
+struct PendingImport {
+    path: Option<RelPath>,
+    target: ImportTarget,
+    message: String,
+    kind: Option<CommentKind>,
+    /// Analyser findings outside the diff are counted rather than rejected.
+    finding: bool,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -387,16 +633,7 @@ struct ImportRejected {
 struct ImportReport {
     accepted: usize,
     rejected: Vec<ImportRejected>,
-}
-
-fn comment_hunk_digest(comment: &Comment) -> Option<&str> {
-    comment
-        .anchor
-        .selectors
-        .first()
-        .map(|selector| match selector {
-            Selector::DiffHunkV1(hunk) => hunk.digest_hex.as_str(),
-        })
+    outside_diff: usize,
 }
 
 fn build_hunk_context(diff: &DiffResult, hunk_index: usize) -> Option<NextHunkContext> {
@@ -444,11 +681,100 @@ fn build_hunk_context(diff: &DiffResult, hunk_index: usize) -> Option<NextHunkCo
     })
 }
 
+/// What a new comment is anchored to.
 #[derive(Debug, Clone, Copy)]
-enum HunkSelectorArg {
+pub(super) enum HunkSelectorArg {
+    /// A hunk by index.
     Index(usize),
-    OldLine(usize),
-    NewLine(usize),
+    /// Lines on one side (0-indexed, inclusive).
+    Lines(DiffSide, usize, usize),
+}
+
+/// Parse a 1-based `N` or `N-M` line span into a 0-based inclusive range.
+pub(super) fn parse_line_span(value: &str) -> Option<(usize, usize)> {
+    let (start, end) = match value.split_once('-') {
+        Some((start, end)) => (start.parse::<usize>().ok()?, end.parse::<usize>().ok()?),
+        None => {
+            let n = value.parse::<usize>().ok()?;
+            (n, n)
+        }
+    };
+    (start >= 1 && end >= start).then(|| (start - 1, end - 1))
+}
+
+/// Build the anchor for a hunk or line selection in a file's diff.
+pub(super) fn anchor_for_selector(
+    diff: &DiffResult,
+    old_buffer: &TextBuffer,
+    new_buffer: &TextBuffer,
+    selector_arg: HunkSelectorArg,
+) -> Result<Anchor, String> {
+    let mut selectors = Vec::new();
+    let hunk_idx = match selector_arg {
+        HunkSelectorArg::Index(idx) => {
+            if idx >= diff.hunks().len() {
+                return Err(format!(
+                    "Hunk {} does not exist (file has {} hunks)",
+                    idx + 1,
+                    diff.hunks().len()
+                ));
+            }
+            Some(idx)
+        }
+        HunkSelectorArg::Lines(side, start, end) => {
+            let buffer = match side {
+                DiffSide::Old => old_buffer,
+                DiffSide::New => new_buffer,
+            };
+            let side_range = |h: &crate::core::Hunk| match side {
+                DiffSide::Old => h.old_range,
+                DiffSide::New => h.new_range,
+            };
+            // A range that touches no hunk is most likely a typo.
+            if !diff.hunks().iter().any(|h| {
+                let (first, count) = side_range(h);
+                start < first + count && end >= first
+            }) {
+                let span = if start == end {
+                    (start + 1).to_string()
+                } else {
+                    format!("{}-{}", start + 1, end + 1)
+                };
+                return Err(format!("No hunk matches {} line {}", side.as_str(), span));
+            }
+            let Some(range) = selector_from_line_range(buffer, side, start, end) else {
+                return Err(format!(
+                    "Line {} is past the end of the {} side ({} lines)",
+                    end + 1,
+                    side.as_str(),
+                    buffer.line_count()
+                ));
+            };
+            selectors.push(Selector::LineRangeV1(range));
+            if let Some(context) = selector_from_context(buffer, side, start, end) {
+                selectors.push(Selector::ContextV1(context));
+            }
+            // Also anchor to the enclosing hunk, if the range sits inside one.
+            diff.hunks().iter().position(|h| {
+                let (first, count) = side_range(h);
+                start >= first && end < first + count
+            })
+        }
+    };
+
+    if let Some(idx) = hunk_idx {
+        let Some(hunk_selector) = selector_from_hunk(diff, idx) else {
+            return Err("Failed to create selector for hunk".to_string());
+        };
+        selectors.push(Selector::DiffHunkV1(hunk_selector));
+        if selectors.len() == 1
+            && let Some(context) = context_from_hunk(diff, idx, old_buffer, new_buffer)
+        {
+            selectors.push(Selector::ContextV1(context));
+        }
+    }
+
+    Ok(Anchor { selectors })
 }
 
 /// Add a comment.
@@ -456,6 +782,8 @@ fn cmd_add(repo: &RepoRoot, args: &[String]) -> ExitCode {
     let mut path: Option<String> = None;
     let mut selector: Option<HunkSelectorArg> = None;
     let mut message: Option<String> = None;
+    let mut kind: Option<CommentKind> = None;
+    let mut suggestion: Option<String> = None;
 
     let (context, source) = match parse_context(repo, args) {
         Ok(Some(v)) => v,
@@ -499,14 +827,11 @@ fn cmd_add(repo: &RepoRoot, args: &[String]) -> ExitCode {
                     eprintln!("--old-line requires a value");
                     return ExitCode::from(1);
                 }
-                let n = match args[i].parse::<usize>() {
-                    Ok(n) if n >= 1 => n,
-                    _ => {
-                        eprintln!("--old-line must be a positive integer");
-                        return ExitCode::from(1);
-                    }
+                let Some((start, end)) = parse_line_span(&args[i]) else {
+                    eprintln!("--old-line must be a line number or an N-M range");
+                    return ExitCode::from(1);
                 };
-                selector = Some(HunkSelectorArg::OldLine(n - 1));
+                selector = Some(HunkSelectorArg::Lines(DiffSide::Old, start, end));
             }
             "--new-line" => {
                 i += 1;
@@ -514,14 +839,11 @@ fn cmd_add(repo: &RepoRoot, args: &[String]) -> ExitCode {
                     eprintln!("--new-line requires a value");
                     return ExitCode::from(1);
                 }
-                let n = match args[i].parse::<usize>() {
-                    Ok(n) if n >= 1 => n,
-                    _ => {
-                        eprintln!("--new-line must be a positive integer");
-                        return ExitCode::from(1);
-                    }
+                let Some((start, end)) = parse_line_span(&args[i]) else {
+                    eprintln!("--new-line must be a line number or an N-M range");
+                    return ExitCode::from(1);
                 };
-                selector = Some(HunkSelectorArg::NewLine(n - 1));
+                selector = Some(HunkSelectorArg::Lines(DiffSide::New, start, end));
             }
             "--message" | "-m" => {
                 i += 1;
@@ -531,6 +853,26 @@ fn cmd_add(repo: &RepoRoot, args: &[String]) -> ExitCode {
                 }
                 message = Some(args[i].clone());
             }
+            "--kind" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("--kind requires a value");
+                    return ExitCode::from(1);
+                }
+                let Some(parsed) = CommentKind::parse(&args[i]) else {
+                    eprintln!("--kind must be one of: bug, warning, suggestion, good");
+                    return ExitCode::from(1);
+                };
+                kind = Some(parsed);
+            }
+            "--suggestion" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("--suggestion requires a value");
+                    return ExitCode::from(1);
+                }
+                suggestion = Some(args[i].clone());
+            }
             // Skip values for context flags (handled by parse_context)
             other if takes_value(other) => {
                 i += 1;
@@ -550,9 +892,14 @@ fn cmd_add(repo: &RepoRoot, args: &[String]) -> ExitCode {
         return ExitCode::from(1);
     };
 
-    let Some(message) = message else {
-        eprintln!("--message is required");
-        return ExitCode::from(1);
+    let (message, suggestion) = match (message, suggestion) {
+        (Some(message), Some(suggestion)) => (message, Some(suggestion)),
+        (Some(message), None) => split_suggestion(&message),
+        (None, Some(suggestion)) => ("Suggested change".to_string(), Some(suggestion)),
+        (None, None) => {
+            eprintln!("--message is required");
+            return ExitCode::from(1);
+        }
     };
 
     let rel_path = match RelPath::try_new(&path) {
@@ -598,52 +945,29 @@ fn cmd_add(repo: &RepoRoot, args: &[String]) -> ExitCode {
         return ExitCode::from(1);
     }
 
-    let hunk_idx = match selector_arg {
-        HunkSelectorArg::Index(idx) => {
-            if idx >= diff.hunks().len() {
-                eprintln!(
-                    "Hunk {} does not exist (file has {} hunks)",
-                    idx + 1,
-                    diff.hunks().len()
-                );
-                return ExitCode::from(1);
-            }
-            idx
+    let anchor = match anchor_for_selector(&diff, &old_buffer, &new_buffer, selector_arg) {
+        Ok(anchor) => anchor,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(1);
         }
-        HunkSelectorArg::OldLine(old_line) => match diff
-            .hunks()
-            .iter()
-            .position(|h| old_line >= h.old_range.0 && old_line < h.old_range.0 + h.old_range.1)
-        {
-            Some(idx) => idx,
-            None => {
-                eprintln!("No hunk matches --old-line {}", old_line + 1);
-                return ExitCode::from(1);
-            }
-        },
-        HunkSelectorArg::NewLine(new_line) => match diff
-            .hunks()
-            .iter()
-            .position(|h| new_line >= h.new_range.0 && new_line < h.new_range.0 + h.new_range.1)
-        {
-            Some(idx) => idx,
-            None => {
-                eprintln!("No hunk matches --new-line {}", new_line + 1);
-                return ExitCode::from(1);
-            }
-        },
     };
+    let summary = format_anchor_summary(&anchor);
 
-    let Some(hunk_selector) = selector_from_hunk(&diff, hunk_idx) else {
-        eprintln!("Failed to create selector for hunk");
+    if suggestion.is_some() && suggestion_original(&anchor).is_none() {
+        eprintln!("A suggestion needs new-side lines: use --new-line or a hunk that adds lines");
         return ExitCode::from(1);
-    };
+    }
 
-    let anchor = Anchor {
-        selectors: vec![Selector::DiffHunkV1(hunk_selector)],
+    let author = match parse_author(repo, args) {
+        Ok(author) => author,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(1);
+        }
     };
 
-    let mut store = match FileCommentStore::open(repo) {
+    let mut store = match open_comment_store(repo) {
         Ok(s) => s,
         Err(e) => {
             eprintln!("Failed to open comment store: {}", e);
@@ -651,13 +975,17 @@ fn cmd_add(repo: &RepoRoot, args: &[String]) -> ExitCode {
         }
     };
 
-    match store.add(rel_path, context, message, anchor) {
+    match store.add(NewComment {
+        path: rel_path,
+        context,
+        author,
+        message,
+        kind,
+        suggestion,
+        anchor,
+    }) {
         Ok(id) => {
-            let digest_prefix = diff
-                .hunk_digest(hunk_idx)
-                .map(|d| d.get(..8).unwrap_or(d).to_string())
-                .unwrap_or_else(|| "????????".to_string());
-            println!("Created comment {} [{}]", id, digest_prefix);
+            println!("Created comment {} {}", id, summary);
             ExitCode::SUCCESS
         }
         Err(e) => {
@@ -676,42 +1004,91 @@ fn cmd_import(repo: &RepoRoot, args: &[String]) -> ExitCode {
             return ExitCode::from(1);
         }
     };
+    let author = match parse_author(repo, args) {
+        Ok(author) => author,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(1);
+        }
+    };
 
-    let mut json_path: Option<String> = None;
+    let mut input_arg: Option<(ImportFormat, String)> = None;
     let mut i = 0;
     while i < args.len() {
-        match args[i].as_str() {
-            "--json" => {
-                i += 1;
-                if i >= args.len() {
-                    eprintln!("--json requires a value");
-                    return ExitCode::from(1);
-                }
-                json_path = Some(args[i].clone());
+        let format = match args[i].as_str() {
+            "--json" => Some(ImportFormat::Json),
+            "--sarif" => Some(ImportFormat::Sarif),
+            "--cargo-json" => Some(ImportFormat::CargoJson),
+            _ => None,
+        };
+        if let Some(format) = format {
+            let flag = args[i].clone();
+            i += 1;
+            if i >= args.len() {
+                eprintln!("{} requires a value", flag);
+                return ExitCode::from(1);
             }
-            other if takes_value(other) => i += 1,
-            _ => {}
+            if input_arg.is_some() {
+                eprintln!("Use only one of --json, --sarif or --cargo-json");
+                return ExitCode::from(1);
+            }
+            input_arg = Some((format, args[i].clone()));
+        } else if takes_value(&args[i]) {
+            i += 1;
         }
         i += 1;
     }
 
-    let Some(json_path) = json_path else {
-        eprintln!("Usage: quickdiff comments import --json <file>");
+    let Some((format, input_path)) = input_arg else {
+        eprintln!("Usage: quickdiff comments import (--json|--sarif|--cargo-json) <file>");
         return ExitCode::from(1);
     };
 
-    let input = match std::fs::read_to_string(&json_path) {
+    let input = match std::fs::read_to_string(&input_path) {
         Ok(input) => input,
         Err(e) => {
-            eprintln!("Failed to read {}: {}", json_path, e);
+            eprintln!("Failed to read {}: {}", input_path, e);
             return ExitCode::from(1);
         }
     };
-    let imports: Vec<ImportComment> = match serde_json::from_str(&input) {
-        Ok(imports) => imports,
-        Err(e) => {
-            eprintln!("Failed to parse JSON: {}", e);
-            return ExitCode::from(1);
+    let imports: Vec<PendingImport> = match format {
+        ImportFormat::Json => match serde_json::from_str::<Vec<ImportComment>>(&input) {
+            Ok(imports) => imports
+                .into_iter()
+                .map(|item| PendingImport {
+                    path: RelPath::try_new(item.path).ok(),
+                    target: ImportTarget::Hunk(item.hunk_digest),
+                    message: item.message,
+                    kind: item.kind,
+                    finding: false,
+                })
+                .collect(),
+            Err(e) => {
+                eprintln!("Failed to parse JSON: {}", e);
+                return ExitCode::from(1);
+            }
+        },
+        ImportFormat::Sarif | ImportFormat::CargoJson => {
+            let findings = match format {
+                ImportFormat::Sarif => parse_sarif(&input),
+                _ => parse_cargo_diagnostics(&input),
+            };
+            match findings {
+                Ok(findings) => findings
+                    .into_iter()
+                    .map(|finding| PendingImport {
+                        path: finding.relative_path(repo.path()),
+                        target: ImportTarget::Lines(finding.start_line, finding.end_line),
+                        message: finding.comment_message(),
+                        kind: Some(finding.kind),
+                        finding: true,
+                    })
+                    .collect(),
+                Err(e) => {
+                    eprintln!("Failed to parse {}: {}", input_path, e);
+                    return ExitCode::from(1);
+                }
+            }
         }
     };
 
@@ -727,7 +1104,7 @@ fn cmd_import(repo: &RepoRoot, args: &[String]) -> ExitCode {
         .map(|file| (file.path.as_str().to_string(), file))
         .collect();
 
-    let mut store = match FileCommentStore::open(repo) {
+    let mut store = match open_comment_store(repo) {
         Ok(s) => s,
         Err(e) => {
             eprintln!("Failed to open comment store: {}", e);
@@ -744,22 +1121,41 @@ fn cmd_import(repo: &RepoRoot, args: &[String]) -> ExitCode {
                 .anchor
                 .selectors
                 .iter()
-                .map(|selector| match selector {
-                    Selector::DiffHunkV1(hunk) => (
+                .filter_map(|selector| match selector {
+                    Selector::DiffHunkV1(hunk) => Some((
                         comment.path.as_str().to_string(),
                         hunk.digest_hex.clone(),
                         comment.message.trim().to_string(),
-                    ),
+                    )),
+                    Selector::LineRangeV1(range) => Some((
+                        comment.path.as_str().to_string(),
+                        range.fingerprint_hex.clone(),
+                        comment.message.trim().to_string(),
+                    )),
+                    Selector::ContextV1(_) => None,
                 })
         })
         .collect();
     let mut report = ImportReport {
         accepted: 0,
         rejected: Vec::new(),
+        outside_diff: 0,
     };
-    let mut diff_cache: HashMap<String, DiffResult> = HashMap::new();
+    let mut diff_cache: HashMap<String, LoadedDiff> = HashMap::new();
 
     for (index, item) in imports.into_iter().enumerate() {
+        // Findings from analysers cover the whole tree; only those inside the diff are kept.
+        let mut skip = |reason: &str| {
+            if item.finding {
+                report.outside_diff += 1;
+            } else {
+                report.rejected.push(ImportRejected {
+                    index,
+                    reason: reason.to_string(),
+                });
+            }
+        };
+
         let message = item.message.trim();
         if message.is_empty() {
             report.rejected.push(ImportRejected {
@@ -769,34 +1165,15 @@ fn cmd_import(repo: &RepoRoot, args: &[String]) -> ExitCode {
             continue;
         }
 
-        let Ok(rel_path) = RelPath::try_new(item.path.clone()) else {
-            report.rejected.push(ImportRejected {
-                index,
-                reason: "path must be relative".to_string(),
-            });
+        let Some(rel_path) = item.path else {
+            skip("path must be relative");
             continue;
         };
         let Some(file) = files_by_path.get(rel_path.as_str()) else {
-            report.rejected.push(ImportRejected {
-                index,
-                reason: "path is not part of current changeset".to_string(),
-            });
+            skip("path is not part of current changeset");
             continue;
         };
 
-        let seen_key = (
-            rel_path.as_str().to_string(),
-            item.hunk_digest.clone(),
-            message.to_string(),
-        );
-        if seen.contains(&seen_key) {
-            report.rejected.push(ImportRejected {
-                index,
-                reason: "duplicate comment".to_string(),
-            });
-            continue;
-        }
-
         if !diff_cache.contains_key(rel_path.as_str()) {
             let (old_bytes, new_bytes) =
                 match load_diff_contents(repo, &source, file, merge_base.as_deref()) {
@@ -809,35 +1186,84 @@ fn cmd_import(repo: &RepoRoot, args: &[String]) -> ExitCode {
                         continue;
                     }
                 };
-            diff_cache.insert(
-                rel_path.as_str().to_string(),
-                DiffResult::compute(&TextBuffer::new(&old_bytes), &TextBuffer::new(&new_bytes)),
-            );
+            let old = TextBuffer::new(&old_bytes);
+            let new = TextBuffer::new(&new_bytes);
+            let diff = DiffResult::compute(&old, &new);
+            diff_cache.insert(rel_path.as_str().to_string(), (old, new, diff));
         }
-        let Some(diff) = diff_cache.get(rel_path.as_str()) else {
+        let Some((old, new, diff)) = diff_cache.get(rel_path.as_str()) else {
             continue;
         };
-        let Some((_, hunk)) = diff
-            .hunks()
-            .iter()
-            .enumerate()
-            .find(|(_, hunk)| hunk.digest_hex == item.hunk_digest)
-        else {
+
+        let (selectors, seen_digest) = match &item.target {
+            ImportTarget::Hunk(digest) => {
+                let Some((hunk_idx, hunk)) = diff
+                    .hunks()
+                    .iter()
+                    .enumerate()
+                    .find(|(_, hunk)| &hunk.digest_hex == digest)
+                else {
+                    skip("hunk digest not found");
+                    continue;
+                };
+                let mut selectors = vec![Selector::DiffHunkV1(crate::core::DiffHunkSelectorV1 {
+                    old_range: hunk.old_range,
+                    new_range: hunk.new_range,
+                    digest_hex: hunk.digest_hex.clone(),
+                })];
+                if let Some(context) = context_from_hunk(diff, hunk_idx, old, new) {
+                    selectors.push(Selector::ContextV1(context));
+                }
+                (selectors, digest.clone())
+            }
+            &ImportTarget::Lines(start, end) => {
+                let Some(hunk_idx) = diff.hunks().iter().position(|h| {
+                    let (first, count) = h.new_range;
+                    start >= first && end < first + count
+                }) else {
+                    skip("lines are outside the changed hunks");
+                    continue;
+                };
+                let (Some(range), Some(hunk_selector)) = (
+                    selector_from_line_range(new, DiffSide::New, start, end),
+                    selector_from_hunk(diff, hunk_idx),
+                ) else {
+                    skip("lines are past the end of the file");
+                    continue;
+                };
+                let digest = range.fingerprint_hex.clone();
+                let mut selectors = vec![Selector::LineRangeV1(range)];
+                if let Some(context) = selector_from_context(new, DiffSide::New, start, end) {
+                    selectors.push(Selector::ContextV1(context));
+                }
+                selectors.push(Selector::DiffHunkV1(hunk_selector));
+                (selectors, digest)
+            }
+        };
+
+        let seen_key = (
+            rel_path.as_str().to_string(),
+            seen_digest,
+            message.to_string(),
+        );
+        if seen.contains(&seen_key) {
             report.rejected.push(ImportRejected {
                 index,
-                reason: "hunk digest not found".to_string(),
+                reason: "duplicate comment".to_string(),
             });
             continue;
-        };
+        }
 
-        let anchor = Anchor {
-            selectors: vec![Selector::DiffHunkV1(crate::core::DiffHunkSelectorV1 {
-                old_range: hunk.old_range,
-                new_range: hunk.new_range,
-                digest_hex: hunk.digest_hex.clone(),
-            })],
-        };
-        match store.add(rel_path, context.clone(), message.to_string(), anchor) {
+        let anchor = Anchor { selectors };
+        match store.add(NewComment {
+            path: rel_path,
+            context: context.clone(),
+            author: author.clone(),
+            message: message.to_string(),
+            kind: item.kind,
+            suggestion: None,
+            anchor,
+        }) {
             Ok(_) => {
                 seen.insert(seen_key);
                 report.accepted += 1;
@@ -880,7 +1306,7 @@ fn cmd_next(repo: &RepoRoot, args: &[String]) -> ExitCode {
         }
     };
 
-    let store = match FileCommentStore::open(repo) {
+    let store = match open_comment_store(repo) {
         Ok(s) => s,
         Err(e) => {
             eprintln!("Failed to open comment store: {}", e);
@@ -902,11 +1328,6 @@ fn cmd_next(repo: &RepoRoot, args: &[String]) -> ExitCode {
         return ExitCode::from(2);
     };
 
-    let Some(hunk_digest) = comment_hunk_digest(comment) else {
-        eprintln!("Comment {} has no diff hunk selector", comment.id);
-        return ExitCode::from(1);
-    };
-
     let (files, merge_base) = match list_files_for_source(repo, &source) {
         Ok(v) => v,
         Err(e) => {
@@ -933,21 +1354,30 @@ fn cmd_next(repo: &RepoRoot, args: &[String]) -> ExitCode {
                 return ExitCode::from(1);
             }
         };
-    let diff = DiffResult::compute(&TextBuffer::new(&old_bytes), &TextBuffer::new(&new_bytes));
-    let Some((hunk_index, _)) = diff
-        .hunks()
-        .iter()
-        .enumerate()
-        .find(|(_, hunk)| hunk.digest_hex == hunk_digest)
-    else {
+    let old_buffer = TextBuffer::new(&old_bytes);
+    let new_buffer = TextBuffer::new(&new_bytes);
+    let diff = DiffResult::compute(&old_buffer, &new_buffer);
+    let Some(found) = relocate_anchor(&comment.anchor, &diff, &old_buffer, &new_buffer) else {
         eprintln!(
-            "Comment {} is stale: hunk digest {} was not found in {}",
+            "Comment {} is stale: its anchor was not found in {}",
             comment.id,
-            hunk_digest,
             comment.path.as_str()
         );
         return ExitCode::from(3);
     };
+    let Some(hunk_index) = found
+        .hunk
+        .or_else(|| found.row.and_then(|row| diff.hunk_at_row(row)))
+    else {
+        eprintln!(
+            "Comment {} is stale: {} L{} is no longer part of a changed hunk",
+            comment.id,
+            found.side.as_str(),
+            found.start_line + 1
+        );
+        return ExitCode::from(3);
+    };
+    let hunk_digest = diff.hunks()[hunk_index].digest_hex.as_str();
 
     let Some(hunk) = build_hunk_context(&diff, hunk_index) else {
         eprintln!("Failed to build hunk context for comment {}", comment.id);
@@ -961,9 +1391,16 @@ fn cmd_next(repo: &RepoRoot, args: &[String]) -> ExitCode {
             context: &comment.context,
             context_summary: context_summary(&comment.context),
             status: comment.status,
+            author: comment.author.as_deref(),
             message: &comment.message,
+            kind: comment.kind,
+            suggestion: comment.suggestion.as_deref(),
             anchor: &comment.anchor,
             anchor_summary: format_anchor_summary(&comment.anchor),
+            replies: &comment.replies,
+            anchor_status: AnchorStatus::of(Some(&found)),
+            confidence: found.confidence,
+            location: &found,
             hunk_digest,
             hunk_index,
             hunk,
@@ -977,17 +1414,202 @@ fn cmd_next(repo: &RepoRoot, args: &[String]) -> ExitCode {
         }
     } else {
         println!(
-            "[{}] {} - {}",
+            "[{}] {} - {}{}",
             comment.id,
             comment.path.as_str(),
+            comment
+                .author
+                .as_deref()
+                .map(|a| format!("{}: ", a))
+                .unwrap_or_default(),
             comment.message
         );
-        println!("    {}", format_anchor_summary(&comment.anchor));
+        println!(
+            "    {}{}",
+            format_anchor_summary(&comment.anchor),
+            anchor_status_suffix(Some(&found))
+        );
+        print_replies(&comment.replies);
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Export open comments as a markdown or plain-text review report.
+fn cmd_export(repo: &RepoRoot, args: &[String]) -> ExitCode {
+    let mut format = ReportFormat::Markdown;
+    let mut output: Option<&str> = None;
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--format" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("--format requires a value");
+                    return ExitCode::from(1);
+                };
+                let Some(parsed) = ReportFormat::parse(value) else {
+                    eprintln!("Unknown format: {} (expected markdown or text)", value);
+                    return ExitCode::from(1);
+                };
+                format = parsed;
+                i += 1;
+            }
+            "--output" | "-o" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("{} requires a value", args[i]);
+                    return ExitCode::from(1);
+                };
+                output = Some(value);
+                i += 1;
+            }
+            other if takes_value(other) => i += 1,
+            _ => {}
+        }
+        i += 1;
     }
 
+    let source = match parse_context(repo, args) {
+        Ok(Some((_, source))) => source,
+        Ok(None) => DiffSource::WorkingTree,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let (files, merge_base) = match list_files_for_source(repo, &source) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to list files for source: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let viewed: Box<dyn ViewedStore> = match FileViewedStore::new(repo.state_key()) {
+        Ok(store) => Box::new(store),
+        Err(_) => Box::new(MemoryViewedStore::new()),
+    };
+
+    let input = ReportInput {
+        source: &source,
+        files: &files,
+        merge_base: merge_base.as_deref(),
+        viewed: viewed.as_ref(),
+    };
+    let report = match build_review_report(repo, input) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Failed to build report: {:#}", e);
+            return ExitCode::from(1);
+        }
+    };
+    let text = report.render(format);
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, &text) {
+                eprintln!("Failed to write {}: {}", path, e);
+                return ExitCode::from(1);
+            }
+            eprintln!("Wrote {} comment(s) to {}", report.comment_count(), path);
+        }
+        None => print!("{}", text),
+    }
     ExitCode::SUCCESS
 }
 
+/// Print a comment's suggested change as a small diff.
+fn print_suggestion(comment: &Comment) {
+    let Some(replacement) = comment.suggestion.as_deref() else {
+        return;
+    };
+    for line in suggestion_original(&comment.anchor).unwrap_or_default() {
+        println!("    - {}", line);
+    }
+    if !replacement.is_empty() {
+        for line in replacement.lines() {
+            println!("    + {}", line);
+        }
+    }
+}
+
+/// Print a thread's replies under its comment.
+fn print_replies(replies: &[CommentReply]) {
+    for reply in replies {
+        println!(
+            "    ↳ {}: {}",
+            reply.author.as_deref().unwrap_or("anonymous"),
+            reply.message
+        );
+    }
+}
+
+/// Reply to a comment.
+fn cmd_reply(repo: &RepoRoot, args: &[String]) -> ExitCode {
+    let usage = "Usage: quickdiff comments reply <id> --message <text>";
+    let Some(id_arg) = args.first() else {
+        eprintln!("{}", usage);
+        return ExitCode::from(1);
+    };
+    let id: u64 = match id_arg.parse() {
+        Ok(n) => n,
+        Err(_) => {
+            eprintln!("Invalid comment ID: {}", id_arg);
+            return ExitCode::from(1);
+        }
+    };
+
+    let mut message: Option<String> = None;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--message" | "-m" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("--message requires a value");
+                    return ExitCode::from(1);
+                }
+                message = Some(args[i].clone());
+            }
+            other if takes_value(other) => i += 1,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let Some(message) = message.filter(|m| !m.trim().is_empty()) else {
+        eprintln!("{}", usage);
+        return ExitCode::from(1);
+    };
+    let author = match parse_author(repo, &args[1..]) {
+        Ok(author) => author,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let mut store = match open_comment_store(repo) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to open comment store: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    match store.reply(id, author, message) {
+        Ok(true) => {
+            println!("Replied to comment {}", id);
+            ExitCode::SUCCESS
+        }
+        Ok(false) => {
+            eprintln!("Comment {} not found", id);
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            eprintln!("Failed to reply: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
 /// Resolve a comment.
 fn cmd_resolve(repo: &RepoRoot, args: &[String]) -> ExitCode {
     if args.is_empty() {
@@ -1003,7 +1625,7 @@ fn cmd_resolve(repo: &RepoRoot, args: &[String]) -> ExitCode {
         }
     };
 
-    let mut store = match FileCommentStore::open(repo) {
+    let mut store = match open_comment_store(repo) {
         Ok(s) => s,
         Err(e) => {
             eprintln!("Failed to open comment store: {}", e);
@@ -1026,3 +1648,231 @@ fn cmd_resolve(repo: &RepoRoot, args: &[String]) -> ExitCode {
         }
     }
 }
+
+/// Parse the `<id>` argument shared by the single-comment commands.
+fn parse_comment_id(args: &[String], usage: &str) -> Option<u64> {
+    let Some(id_arg) = args.first() else {
+        eprintln!("{}", usage);
+        return None;
+    };
+    match id_arg.parse() {
+        Ok(n) => Some(n),
+        Err(_) => {
+            eprintln!("Invalid comment ID: {}", id_arg);
+            None
+        }
+    }
+}
+
+/// Edit a comment's message, keeping the previous text in its history.
+fn cmd_edit(repo: &RepoRoot, args: &[String]) -> ExitCode {
+    let usage = "Usage: quickdiff comments edit <id> --message <text>";
+    let Some(id) = parse_comment_id(args, usage) else {
+        return ExitCode::from(1);
+    };
+
+    let mut message: Option<String> = None;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--message" | "-m" => {
+                i += 1;
+                if i >= args.len() {
+                    eprintln!("--message requires a value");
+                    return ExitCode::from(1);
+                }
+                message = Some(args[i].clone());
+            }
+            other if takes_value(other) => i += 1,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let Some(message) = message.filter(|m| !m.trim().is_empty()) else {
+        eprintln!("{}", usage);
+        return ExitCode::from(1);
+    };
+
+    let mut store = match open_comment_store(repo) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to open comment store: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    match store.edit(id, message) {
+        Ok(true) => {
+            println!("Edited comment {}", id);
+            ExitCode::SUCCESS
+        }
+        Ok(false) => {
+            eprintln!("Comment {} not found", id);
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            eprintln!("Failed to edit comment: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Reopen a resolved comment.
+fn cmd_reopen(repo: &RepoRoot, args: &[String]) -> ExitCode {
+    let Some(id) = parse_comment_id(args, "Usage: quickdiff comments reopen <id>") else {
+        return ExitCode::from(1);
+    };
+
+    let mut store = match open_comment_store(repo) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to open comment store: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    match store.reopen(id) {
+        Ok(true) => {
+            println!("Reopened comment {}", id);
+            ExitCode::SUCCESS
+        }
+        Ok(false) => {
+            eprintln!("Comment {} not found", id);
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            eprintln!("Failed to reopen comment: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Delete a comment and its replies.
+fn cmd_delete(repo: &RepoRoot, args: &[String]) -> ExitCode {
+    let Some(id) = parse_comment_id(args, "Usage: quickdiff comments delete <id>") else {
+        return ExitCode::from(1);
+    };
+
+    let mut store = match open_comment_store(repo) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to open comment store: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    match store.delete(id) {
+        Ok(true) => {
+            println!("Deleted comment {}", id);
+            ExitCode::SUCCESS
+        }
+        Ok(false) => {
+            eprintln!("Comment {} not found", id);
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            eprintln!("Failed to delete comment: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Remote named by the first argument, `origin` by default. `None` after printing an
+/// error when comments are not stored in the git ref.
+fn sync_remote<'a>(repo: &RepoRoot, args: &'a [String]) -> Option<&'a str> {
+    if repo.comment_backend() != CommentBackend::GitRef {
+        eprintln!(
+            "Comments are stored in {}; set comments_backend = \"git-ref\" in .quickdiff/config.toml to share them",
+            FileCommentStore::path_for(repo).display()
+        );
+        return None;
+    }
+    Some(args.first().map(String::as_str).unwrap_or("origin"))
+}
+
+/// Push the comments ref to a remote.
+fn cmd_push(repo: &RepoRoot, args: &[String]) -> ExitCode {
+    let Some(remote) = sync_remote(repo, args) else {
+        return ExitCode::from(1);
+    };
+
+    match push_comments(repo, remote) {
+        Ok(()) => {
+            println!("Pushed comments to {}", remote);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to push comments: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Fetch the comments ref from a remote and merge it into the local one.
+fn cmd_pull(repo: &RepoRoot, args: &[String]) -> ExitCode {
+    let Some(remote) = sync_remote(repo, args) else {
+        return ExitCode::from(1);
+    };
+
+    match pull_comments(repo, remote) {
+        Ok(outcome) => {
+            match outcome {
+                PullOutcome::NoRemoteComments => println!("{} has no comments yet", remote),
+                PullOutcome::UpToDate => println!("Comments already up to date"),
+                PullOutcome::FastForward => println!("Pulled comments from {}", remote),
+                PullOutcome::Merged => println!("Merged comments from {}", remote),
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to pull comments: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Apply a comment's suggested change to the working file and resolve the comment.
+fn cmd_apply(repo: &RepoRoot, args: &[String]) -> ExitCode {
+    let Some(id) = parse_comment_id(args, "Usage: quickdiff comments apply <id>") else {
+        return ExitCode::from(1);
+    };
+
+    let mut store = match open_comment_store(repo) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to open comment store: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let Some(comment) = store.get(id).cloned() else {
+        eprintln!("Comment {} not found", id);
+        return ExitCode::from(1);
+    };
+    let Some(suggestion) = comment.suggestion.as_deref() else {
+        eprintln!("Comment {} has no suggestion", id);
+        return ExitCode::from(1);
+    };
+
+    let path = repo.path().join(comment.path.as_str());
+    if let Err(e) = apply_suggestion_to_file(&path, &comment.anchor, suggestion) {
+        eprintln!("Cannot apply suggestion from comment {}: {}", id, e);
+        return ExitCode::from(1);
+    }
+
+    match store.resolve(id) {
+        Ok(_) => {
+            println!(
+                "Applied suggestion from comment {} to {}",
+                id,
+                comment.path.as_str()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Applied suggestion but failed to resolve comment: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
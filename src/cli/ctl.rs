@@ -0,0 +1,158 @@
+//! `quickdiff ctl`: drive a running TUI over its control socket.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use crate::core::{
+    CONTROL_SOCKET_ENV, ControlCommand, RepoRoot, default_control_socket, send_control_command,
+    subscribe_control_events,
+};
+
+/// How long to wait for the TUI to apply a command.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn usage() -> ExitCode {
+    eprintln!("Usage: quickdiff ctl [--socket <path>] <command>");
+    eprintln!("Commands:");
+    eprintln!("  open <path>[:<line>]   Select a file and scroll to a new-side line");
+    eprintln!("  reload                 Refresh the file list and diff");
+    eprintln!("  worktree               Show the working tree diff");
+    eprintln!("  base <ref>             Diff against the merge base with <ref>");
+    eprintln!("  commit <rev>           Show a commit");
+    eprintln!("  range <from>..<to>     Show a range");
+    eprintln!("  quit                   Quit the TUI");
+    eprintln!("  events                 Print events (file selected, comment added) as JSON lines");
+    ExitCode::from(1)
+}
+
+/// Run a ctl subcommand against the TUI listening for `repo` (or `--socket`).
+pub fn run_ctl_command(repo: Option<&RepoRoot>, args: &[String]) -> ExitCode {
+    let mut socket: Option<PathBuf> = std::env::var_os(CONTROL_SOCKET_ENV).map(PathBuf::from);
+    let mut rest = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--socket" {
+            i += 1;
+            let Some(path) = args.get(i) else {
+                eprintln!("--socket requires a value");
+                return ExitCode::from(1);
+            };
+            socket = Some(PathBuf::from(path));
+        } else {
+            rest.push(args[i].as_str());
+        }
+        i += 1;
+    }
+
+    let Some(socket) = socket.or_else(|| repo.map(default_control_socket)) else {
+        eprintln!(
+            "Not inside a repository; pass --socket or set {}",
+            CONTROL_SOCKET_ENV
+        );
+        return ExitCode::from(1);
+    };
+
+    let command = match rest.as_slice() {
+        ["events"] => return print_events(&socket),
+        ["open", target] => {
+            // `path:line`, unless the suffix is not a number (a path containing ':').
+            match target.rsplit_once(':') {
+                Some((path, line)) if line.parse::<usize>().is_ok() => ControlCommand::Open {
+                    path: path.to_string(),
+                    line: line.parse().ok(),
+                },
+                _ => ControlCommand::Open {
+                    path: target.to_string(),
+                    line: None,
+                },
+            }
+        }
+        ["reload"] => ControlCommand::Reload,
+        ["worktree"] => ControlCommand::Worktree,
+        ["base", base] => ControlCommand::Base {
+            base: base.to_string(),
+        },
+        ["commit", commit] => ControlCommand::Commit {
+            commit: commit.to_string(),
+        },
+        ["range", range] => {
+            if range.contains("...") {
+                eprintln!(
+                    "three-dot ranges are not supported; use `ctl base <ref>` to diff against a merge base"
+                );
+                return ExitCode::from(1);
+            }
+            let Some((from, to)) = range.split_once("..") else {
+                eprintln!("range must contain '..' (e.g. a..b)");
+                return ExitCode::from(1);
+            };
+            ControlCommand::Range {
+                from: from.to_string(),
+                to: to.to_string(),
+            }
+        }
+        ["quit"] => ControlCommand::Quit,
+        _ => return usage(),
+    };
+
+    match send_control_command(&socket, &command, REPLY_TIMEOUT) {
+        Ok(reply) if reply.ok => ExitCode::SUCCESS,
+        Ok(reply) => {
+            eprintln!(
+                "{}",
+                reply.error.unwrap_or_else(|| "command failed".to_string())
+            );
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            eprintln!(
+                "Failed to reach quickdiff at {} ({}). Is it running with --listen?",
+                socket.display(),
+                e
+            );
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn print_events(socket: &std::path::Path) -> ExitCode {
+    let events = match subscribe_control_events(socket) {
+        Ok(events) => events,
+        Err(e) => {
+            eprintln!(
+                "Failed to reach quickdiff at {} ({}). Is it running with --listen?",
+                socket.display(),
+                e
+            );
+            return ExitCode::from(1);
+        }
+    };
+    for line in events {
+        match line {
+            Ok(line) => println!("{}", line),
+            Err(_) => break,
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ControlServer;
+
+    #[test]
+    fn rejects_three_dot_ranges_without_sending() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("control.sock");
+        let mut server = ControlServer::bind(&path).unwrap();
+        let args: Vec<String> = ["--socket", path.to_str().unwrap(), "range", "main...topic"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(run_ctl_command(None, &args), ExitCode::from(1));
+        assert!(server.poll().is_empty());
+    }
+}
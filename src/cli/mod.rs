@@ -1,5 +1,11 @@
 //! CLI commands for quickdiff.
 
 mod comments;
+#[cfg(unix)]
+mod ctl;
+mod serve;
 
 pub use comments::run_comments_command;
+#[cfg(unix)]
+pub use ctl::run_ctl_command;
+pub use serve::{run_serve, serve};
@@ -0,0 +1,705 @@
+//! JSON-RPC review server (`quickdiff serve --stdio`).
+//!
+//! Messages use LSP-style framing: a `Content-Length` header, a blank line, then the
+//! JSON body. Editors and agents call methods to read diffs and manage comments, and
+//! receive `quickdiff/didChangeWorktree` / `quickdiff/didChangeComments` notifications
+//! when files or the comment store change on disk.
+
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use super::comments::{
+    HunkSelectorArg, anchor_for_selector, list_files_for_source, parse_line_span, resolve_context,
+};
+use crate::core::{
+    ChangeKind, ChangedFile, CommentContext, CommentId, CommentKind, CommentsWatcher, DiffResult,
+    DiffSide, DiffSource, FileChangeKind, FileViewedStore, NewComment, RelPath, RepoRoot,
+    RepoWatcher, TextBuffer, ViewedStore, comment_author, load_diff_contents, open_comment_store,
+    split_suggestion, suggestion_original,
+};
+
+/// Methods answered by the server, reported by `initialize`.
+const METHODS: &[&str] = &[
+    "initialize",
+    "shutdown",
+    "files/list",
+    "diff/get",
+    "comments/list",
+    "comments/add",
+    "comments/resolve",
+    "viewed/set",
+];
+
+/// How often watchers are polled while waiting for input.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Serve JSON-RPC on stdin/stdout until the client sends `exit` or closes stdin.
+pub fn run_serve(repo: &RepoRoot) -> ExitCode {
+    match serve(repo, io::BufReader::new(io::stdin()), io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("serve: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Serve JSON-RPC requests read from `input`, writing responses and notifications to `output`.
+///
+/// Returns when the client sends `exit` or `input` reaches end of file.
+pub fn serve<R, W>(repo: &RepoRoot, input: R, mut output: W) -> io::Result<()>
+where
+    R: BufRead + Send + 'static,
+    W: Write,
+{
+    // Read on a separate thread so watcher events can be forwarded while idle.
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut input = input;
+        loop {
+            let message = read_message(&mut input);
+            let done = !matches!(message, Ok(Some(_)));
+            if tx.send(message).is_err() || done {
+                break;
+            }
+        }
+    });
+
+    let repo_watcher = RepoWatcher::new(repo).ok();
+    let mut comments_watcher = CommentsWatcher::new(repo).ok();
+    let mut server = Server::new(repo.clone());
+
+    loop {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(Some(message))) => {
+                if let Some(response) = server.handle(&message) {
+                    write_message(&mut output, &response)?;
+                }
+                if server.exited {
+                    return Ok(());
+                }
+            }
+            Ok(Ok(None)) | Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            Ok(Err(e)) => return Err(e),
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+
+        if repo_watcher.as_ref().and_then(RepoWatcher::poll).is_some() {
+            write_message(&mut output, &notification("quickdiff/didChangeWorktree"))?;
+        }
+        if comments_watcher
+            .as_mut()
+            .and_then(CommentsWatcher::poll)
+            .is_some()
+        {
+            write_message(&mut output, &notification("quickdiff/didChangeComments"))?;
+        }
+    }
+}
+
+/// Largest message body the server accepts.
+const MAX_MESSAGE_SIZE: usize = 8 * 1024 * 1024;
+
+/// Read one framed message. Returns `None` at end of input.
+///
+/// A header block without a valid `Content-Length`, or one announcing more
+/// than [`MAX_MESSAGE_SIZE`] bytes, is rejected before any body is read.
+fn read_message(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    let mut content_length: Option<usize> = None;
+    let mut saw_header = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if saw_header {
+                break;
+            }
+            continue;
+        }
+        saw_header = true;
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            let length = value
+                .trim()
+                .parse()
+                .map_err(|_| invalid(format!("invalid Content-Length: {}", value.trim())))?;
+            content_length = Some(length);
+        }
+    }
+    let length = content_length.ok_or_else(|| invalid("missing Content-Length".to_string()))?;
+    if length > MAX_MESSAGE_SIZE {
+        return Err(invalid(format!(
+            "message of {} bytes exceeds the {} byte limit",
+            length, MAX_MESSAGE_SIZE
+        )));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    String::from_utf8(body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Write one framed message and flush.
+fn write_message(writer: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = serde_json::to_string(message)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
+
+fn notification(method: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": {} })
+}
+
+/// A JSON-RPC error object.
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    const PARSE_ERROR: i64 = -32700;
+    const INVALID_REQUEST: i64 = -32600;
+    const METHOD_NOT_FOUND: i64 = -32601;
+    const INVALID_PARAMS: i64 = -32602;
+    const INTERNAL_ERROR: i64 = -32603;
+
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_PARAMS, message)
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        Self::new(Self::INTERNAL_ERROR, message)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Option<Value>,
+    #[serde(default)]
+    method: Option<String>,
+    #[serde(default)]
+    params: Option<Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SourceParams {
+    source: Option<CommentContext>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiffParams {
+    #[serde(default)]
+    source: Option<CommentContext>,
+    path: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ListCommentsParams {
+    source: Option<CommentContext>,
+    path: Option<String>,
+    all: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AddCommentParams {
+    #[serde(default)]
+    source: Option<CommentContext>,
+    path: String,
+    #[serde(default)]
+    hunk: Option<usize>,
+    #[serde(default)]
+    old_line: Option<String>,
+    #[serde(default)]
+    new_line: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    kind: Option<CommentKind>,
+    #[serde(default)]
+    suggestion: Option<String>,
+    #[serde(default)]
+    author: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResolveParams {
+    id: CommentId,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewedParams {
+    path: String,
+    #[serde(default = "default_viewed")]
+    viewed: bool,
+}
+
+fn default_viewed() -> bool {
+    true
+}
+
+/// Request handling state for one client.
+struct Server {
+    repo: RepoRoot,
+    /// Viewed-state file; `None` uses the default under the config dir.
+    viewed_path: Option<PathBuf>,
+    exited: bool,
+}
+
+impl Server {
+    fn new(repo: RepoRoot) -> Self {
+        Self {
+            repo,
+            viewed_path: None,
+            exited: false,
+        }
+    }
+
+    /// Handle one message, returning the response for requests (not notifications).
+    fn handle(&mut self, message: &str) -> Option<Value> {
+        let request: Request = match serde_json::from_str(message) {
+            Ok(request) => request,
+            Err(e) => {
+                return Some(error_response(
+                    Value::Null,
+                    RpcError::new(RpcError::PARSE_ERROR, e.to_string()),
+                ));
+            }
+        };
+        let Some(method) = request.method else {
+            return Some(error_response(
+                request.id.unwrap_or(Value::Null),
+                RpcError::new(RpcError::INVALID_REQUEST, "missing method"),
+            ));
+        };
+
+        let result = self.dispatch(&method, request.params.unwrap_or(Value::Null));
+        let id = request.id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_response(id, e),
+        })
+    }
+
+    fn dispatch(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => Ok(json!({
+                "serverInfo": { "name": "quickdiff", "version": env!("CARGO_PKG_VERSION") },
+                "methods": METHODS,
+            })),
+            "shutdown" => Ok(Value::Null),
+            "exit" => {
+                self.exited = true;
+                Ok(Value::Null)
+            }
+            "files/list" => self.list_files(parse_params(params)?),
+            "diff/get" => self.get_diff(parse_params(params)?),
+            "comments/list" => self.list_comments(parse_params(params)?),
+            "comments/add" => self.add_comment(parse_params(params)?),
+            "comments/resolve" => self.resolve_comment(parse_params(params)?),
+            "viewed/set" => self.set_viewed(parse_params(params)?),
+            _ => Err(RpcError::new(
+                RpcError::METHOD_NOT_FOUND,
+                format!("unknown method: {}", method),
+            )),
+        }
+    }
+
+    fn resolve_source(
+        &self,
+        source: Option<CommentContext>,
+    ) -> Result<(CommentContext, DiffSource), RpcError> {
+        resolve_context(&self.repo, source.unwrap_or(CommentContext::Worktree))
+            .map_err(RpcError::invalid_params)
+    }
+
+    fn viewed_store(&self) -> Result<FileViewedStore, RpcError> {
+        let key = self.repo.state_key();
+        match &self.viewed_path {
+            Some(path) => FileViewedStore::with_path(key, path.clone()),
+            None => FileViewedStore::new(key),
+        }
+        .map_err(|e| RpcError::internal(format!("failed to open viewed state: {}", e)))
+    }
+
+    fn list_files(&self, params: SourceParams) -> Result<Value, RpcError> {
+        let (_, source) = self.resolve_source(params.source)?;
+        let (files, _) = list_files_for_source(&self.repo, &source)
+            .map_err(|e| RpcError::internal(e.to_string()))?;
+        let viewed = self.viewed_store()?;
+        Ok(files
+            .iter()
+            .map(|file| {
+                json!({
+                    "path": file.path.as_str(),
+                    "old_path": file.old_path.as_ref().map(RelPath::as_str),
+                    "status": change_kind_name(file.kind),
+                    "similarity": file.similarity,
+                    "viewed": viewed.is_viewed(&file.path),
+                })
+            })
+            .collect())
+    }
+
+    /// Load a file's diff for a source.
+    fn load_diff(
+        &self,
+        source: &DiffSource,
+        path: &str,
+    ) -> Result<(ChangedFile, TextBuffer, TextBuffer, DiffResult), RpcError> {
+        let rel_path = RelPath::try_new(path)
+            .map_err(|_| RpcError::invalid_params(format!("path must be relative: {}", path)))?;
+        let (files, merge_base) = list_files_for_source(&self.repo, source)
+            .map_err(|e| RpcError::internal(e.to_string()))?;
+        let Some(file) = files.into_iter().find(|f| f.path == rel_path) else {
+            return Err(RpcError::invalid_params(format!(
+                "{} is not part of the changeset",
+                path
+            )));
+        };
+        let (old_bytes, new_bytes) =
+            load_diff_contents(&self.repo, source, &file, merge_base.as_deref())
+                .map_err(|e| RpcError::internal(format!("failed to load diff: {}", e)))?;
+        let old = TextBuffer::new(&old_bytes);
+        let new = TextBuffer::new(&new_bytes);
+        let diff = DiffResult::compute(&old, &new);
+        Ok((file, old, new, diff))
+    }
+
+    fn get_diff(&self, params: DiffParams) -> Result<Value, RpcError> {
+        let (_, source) = self.resolve_source(params.source)?;
+        let (file, _, _, diff) = self.load_diff(&source, &params.path)?;
+        let hunks: Vec<Value> = diff
+            .hunks()
+            .iter()
+            .map(|hunk| {
+                json!({
+                    "start_row": hunk.start_row,
+                    "row_count": hunk.row_count,
+                    "old_start": hunk.old_range.0 + 1,
+                    "old_count": hunk.old_range.1,
+                    "new_start": hunk.new_range.0 + 1,
+                    "new_count": hunk.new_range.1,
+                    "digest": hunk.digest_hex,
+                })
+            })
+            .collect();
+        let rows: Vec<Value> = diff
+            .rows()
+            .iter()
+            .map(|row| {
+                json!({
+                    "kind": match row.kind {
+                        ChangeKind::Equal => "context",
+                        ChangeKind::Delete => "delete",
+                        ChangeKind::Insert => "insert",
+                        ChangeKind::Replace => "replace",
+                    },
+                    "old_line": row.old.as_ref().map(|line| line.line_num + 1),
+                    "new_line": row.new.as_ref().map(|line| line.line_num + 1),
+                    "old_text": row.old.as_ref().map(|line| line.content.as_str()),
+                    "new_text": row.new.as_ref().map(|line| line.content.as_str()),
+                })
+            })
+            .collect();
+        Ok(json!({
+            "path": file.path.as_str(),
+            "old_path": file.old_path.as_ref().map(RelPath::as_str),
+            "status": change_kind_name(file.kind),
+            "hunks": hunks,
+            "rows": rows,
+        }))
+    }
+
+    fn list_comments(&self, params: ListCommentsParams) -> Result<Value, RpcError> {
+        let context = match params.source {
+            Some(source) => Some(self.resolve_source(Some(source))?.0),
+            None => None,
+        };
+        let store = open_comment_store(&self.repo)
+            .map_err(|e| RpcError::internal(format!("failed to open comment store: {}", e)))?;
+        let comments: Vec<_> = store
+            .list(params.all)
+            .into_iter()
+            .filter(|c| context.as_ref().is_none_or(|ctx| c.context.matches(ctx)))
+            .filter(|c| params.path.as_deref().is_none_or(|p| c.path.as_str() == p))
+            .collect();
+        serde_json::to_value(comments).map_err(|e| RpcError::internal(e.to_string()))
+    }
+
+    fn add_comment(&self, params: AddCommentParams) -> Result<Value, RpcError> {
+        let side = if params.new_line.is_some() {
+            DiffSide::New
+        } else {
+            DiffSide::Old
+        };
+        let selector = match (params.hunk, params.old_line, params.new_line) {
+            (Some(n), None, None) if n >= 1 => HunkSelectorArg::Index(n - 1),
+            (None, Some(span), None) | (None, None, Some(span)) => {
+                let (start, end) = parse_line_span(&span).ok_or_else(|| {
+                    RpcError::invalid_params("lines must be a line number or an N-M range")
+                })?;
+                HunkSelectorArg::Lines(side, start, end)
+            }
+            _ => {
+                return Err(RpcError::invalid_params(
+                    "exactly one of hunk (1-based), old_line or new_line is required",
+                ));
+            }
+        };
+        let (message, suggestion) = match (params.message, params.suggestion) {
+            (Some(message), Some(suggestion)) => (message, Some(suggestion)),
+            (Some(message), None) => split_suggestion(&message),
+            (None, Some(suggestion)) => ("Suggested change".to_string(), Some(suggestion)),
+            (None, None) => return Err(RpcError::invalid_params("message is required")),
+        };
+
+        let (context, source) = self.resolve_source(params.source)?;
+        let (file, old, new, diff) = self.load_diff(&source, &params.path)?;
+        let anchor =
+            anchor_for_selector(&diff, &old, &new, selector).map_err(RpcError::invalid_params)?;
+        if suggestion.is_some() && suggestion_original(&anchor).is_none() {
+            return Err(RpcError::invalid_params(
+                "a suggestion needs new-side lines",
+            ));
+        }
+
+        let author = params
+            .author
+            .filter(|a| !a.trim().is_empty())
+            .or_else(|| comment_author(&self.repo));
+        let mut store = open_comment_store(&self.repo)
+            .map_err(|e| RpcError::internal(format!("failed to open comment store: {}", e)))?;
+        let id = store
+            .add(NewComment {
+                path: file.path,
+                context,
+                author,
+                message,
+                kind: params.kind,
+                suggestion,
+                anchor,
+            })
+            .map_err(|e| RpcError::internal(format!("failed to create comment: {}", e)))?;
+        Ok(json!({ "id": id }))
+    }
+
+    fn resolve_comment(&self, params: ResolveParams) -> Result<Value, RpcError> {
+        let mut store = open_comment_store(&self.repo)
+            .map_err(|e| RpcError::internal(format!("failed to open comment store: {}", e)))?;
+        match store.resolve(params.id) {
+            Ok(true) => Ok(Value::Null),
+            Ok(false) => Err(RpcError::invalid_params(format!(
+                "comment {} not found",
+                params.id
+            ))),
+            Err(e) => Err(RpcError::internal(format!(
+                "failed to resolve comment: {}",
+                e
+            ))),
+        }
+    }
+
+    fn set_viewed(&self, params: ViewedParams) -> Result<Value, RpcError> {
+        let path = RelPath::try_new(&params.path).map_err(|_| {
+            RpcError::invalid_params(format!("path must be relative: {}", params.path))
+        })?;
+        // Re-read on every call so marks made in a running TUI are kept.
+        let mut viewed = self.viewed_store()?;
+        if params.viewed {
+            viewed.mark_viewed(path);
+        } else {
+            viewed.unmark_viewed(&path);
+        }
+        viewed
+            .save()
+            .map_err(|e| RpcError::internal(format!("failed to save viewed state: {}", e)))?;
+        Ok(Value::Null)
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| RpcError::invalid_params(e.to_string()))
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+fn change_kind_name(kind: FileChangeKind) -> &'static str {
+    match kind {
+        FileChangeKind::Added => "added",
+        FileChangeKind::Modified => "modified",
+        FileChangeKind::Deleted => "deleted",
+        FileChangeKind::Untracked => "untracked",
+        FileChangeKind::Renamed => "renamed",
+        FileChangeKind::Copied => "copied",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::VcsPreference;
+    use std::io::Cursor;
+    use tempfile::TempDir;
+
+    fn repo_with_change() -> (TempDir, RepoRoot) {
+        let dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "one\ntwo\nthree\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("lib.rs")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@test.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+            .unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "one\nTWO\nthree\n").unwrap();
+        let root = RepoRoot::discover(dir.path(), VcsPreference::Git).unwrap();
+        (dir, root)
+    }
+
+    fn call(server: &mut Server, method: &str, params: Value) -> Value {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        server.handle(&request.to_string()).unwrap()
+    }
+
+    #[test]
+    fn framing_roundtrip() {
+        let mut out = Vec::new();
+        write_message(&mut out, &json!({ "a": 1 })).unwrap();
+        write_message(&mut out, &json!({ "b": "ü" })).unwrap();
+        let mut reader = Cursor::new(out);
+        assert_eq!(
+            read_message(&mut reader).unwrap().as_deref(),
+            Some(r#"{"a":1}"#)
+        );
+        assert_eq!(
+            read_message(&mut reader).unwrap().as_deref(),
+            Some(r#"{"b":"ü"}"#)
+        );
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_oversized_and_unframed_messages() {
+        let mut reader = Cursor::new(format!("Content-Length: {}\r\n\r\n{{}}", usize::MAX));
+        let err = read_message(&mut reader).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut reader = Cursor::new("Content-Length: 99999999999\r\n\r\n");
+        let err = read_message(&mut reader).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut reader = Cursor::new("Content-Type: application/json\r\n\r\n{}");
+        let err = read_message(&mut reader).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn lists_files_and_diff_rows() {
+        let (dir, repo) = repo_with_change();
+        let mut server = Server::new(repo);
+        server.viewed_path = Some(dir.path().join("state.json"));
+
+        let files = call(&mut server, "files/list", Value::Null);
+        assert_eq!(files["result"][0]["path"], "lib.rs");
+        assert_eq!(files["result"][0]["status"], "modified");
+        assert_eq!(files["result"][0]["viewed"], false);
+
+        let diff = call(&mut server, "diff/get", json!({ "path": "lib.rs" }));
+        let diff = &diff["result"];
+        assert_eq!(diff["hunks"].as_array().unwrap().len(), 1);
+        let changed: Vec<_> = diff["rows"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|row| row["kind"] != "context")
+            .collect();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0]["new_text"], "TWO");
+        assert_eq!(changed[0]["new_line"], 2);
+
+        call(&mut server, "viewed/set", json!({ "path": "lib.rs" }));
+        let files = call(&mut server, "files/list", Value::Null);
+        assert_eq!(files["result"][0]["viewed"], true);
+    }
+
+    #[test]
+    fn adds_lists_and_resolves_comments() {
+        let (_dir, repo) = repo_with_change();
+        let mut server = Server::new(repo);
+
+        let added = call(
+            &mut server,
+            "comments/add",
+            json!({ "path": "lib.rs", "new_line": "2", "message": "shout less", "kind": "suggestion" }),
+        );
+        let id = added["result"]["id"].as_u64().unwrap();
+
+        let listed = call(
+            &mut server,
+            "comments/list",
+            json!({ "source": { "type": "worktree" } }),
+        );
+        assert_eq!(listed["result"][0]["message"], "shout less");
+        assert_eq!(listed["result"][0]["kind"], "suggestion");
+
+        call(&mut server, "comments/resolve", json!({ "id": id }));
+        let listed = call(&mut server, "comments/list", Value::Null);
+        assert_eq!(listed["result"].as_array().unwrap().len(), 0);
+
+        let missing = call(&mut server, "comments/resolve", json!({ "id": 99 }));
+        assert_eq!(missing["error"]["code"], RpcError::INVALID_PARAMS);
+    }
+
+    #[test]
+    fn reports_protocol_errors() {
+        let (_dir, repo) = repo_with_change();
+        let mut server = Server::new(repo);
+
+        let unknown = call(&mut server, "nope", Value::Null);
+        assert_eq!(unknown["error"]["code"], RpcError::METHOD_NOT_FOUND);
+        let bad = server.handle("{not json").unwrap();
+        assert_eq!(bad["error"]["code"], RpcError::PARSE_ERROR);
+        let missing = call(&mut server, "diff/get", json!({}));
+        assert_eq!(missing["error"]["code"], RpcError::INVALID_PARAMS);
+
+        // Notifications get no response.
+        assert!(
+            server
+                .handle(r#"{"jsonrpc":"2.0","method":"exit"}"#)
+                .is_none()
+        );
+        assert!(server.exited);
+    }
+}
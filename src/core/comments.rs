@@ -2,13 +2,13 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::core::{DiffResult, Hunk, RelPath};
+use crate::core::{DiffResult, DiffSource, Fnv1a, Hunk, RelPath, TextBuffer};
 
 /// Comment identifier.
 pub type CommentId = u64;
 
 /// Context in which a comment was created (which diff it refers to).
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[derive(Default)]
 pub enum CommentContext {
@@ -34,14 +34,47 @@ pub enum CommentContext {
         /// Ending commit.
         to: String,
     },
+    /// GitHub pull request diff.
+    PullRequest {
+        /// PR number.
+        number: u32,
+    },
 }
 
 impl CommentContext {
+    /// Context for comments made while viewing `source`.
+    pub fn for_source(source: &DiffSource) -> Self {
+        match source {
+            DiffSource::WorkingTree => CommentContext::Worktree,
+            DiffSource::Base(base) => CommentContext::Base { base: base.clone() },
+            DiffSource::Commit(commit) => CommentContext::Commit {
+                commit: commit.clone(),
+            },
+            DiffSource::Range { from, to } | DiffSource::Evolog { from, to, .. } => {
+                CommentContext::Range {
+                    from: from.clone(),
+                    to: to.clone(),
+                }
+            }
+            DiffSource::FilePair { .. } | DiffSource::DiffTool { .. } => CommentContext::Unscoped,
+            DiffSource::PullRequest { number, .. } => {
+                CommentContext::PullRequest { number: *number }
+            }
+        }
+    }
+
     /// Whether this stored context should be considered relevant for the current view.
     ///
-    /// `Unscoped` is treated as matching all contexts for backward compatibility.
+    /// `Unscoped` is treated as matching all contexts for backward compatibility, and
+    /// PR comments stored under the old `pr-<n>` pseudo-commit match their PR.
     pub fn matches(&self, current: &CommentContext) -> bool {
-        matches!(self, CommentContext::Unscoped) || self == current
+        match (self, current) {
+            (CommentContext::Unscoped, _) => true,
+            (CommentContext::Commit { commit }, CommentContext::PullRequest { number }) => {
+                commit.strip_prefix("pr-") == Some(number.to_string().as_str())
+            }
+            _ => self == current,
+        }
     }
 
     fn is_unscoped(ctx: &CommentContext) -> bool {
@@ -59,8 +92,60 @@ pub enum CommentStatus {
     Resolved,
 }
 
+/// Severity category of a comment. Ordered most severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentKind {
+    /// Something is broken.
+    Bug,
+    /// Likely a problem, worth a second look.
+    Warning,
+    /// Optional improvement.
+    Suggestion,
+    /// Positive note.
+    Good,
+}
+
+impl CommentKind {
+    /// All kinds, most severe first.
+    pub const ALL: [CommentKind; 4] = [
+        CommentKind::Bug,
+        CommentKind::Warning,
+        CommentKind::Suggestion,
+        CommentKind::Good,
+    ];
+
+    /// Lowercase name, as used on the command line and in JSON.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CommentKind::Bug => "bug",
+            CommentKind::Warning => "warning",
+            CommentKind::Suggestion => "suggestion",
+            CommentKind::Good => "good",
+        }
+    }
+
+    /// Parse a kind name (case-insensitive).
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+    }
+
+    /// Cycle `None` → bug → warning → suggestion → good → `None`.
+    pub fn cycle(kind: Option<Self>) -> Option<Self> {
+        match kind {
+            None => Some(CommentKind::Bug),
+            Some(CommentKind::Bug) => Some(CommentKind::Warning),
+            Some(CommentKind::Warning) => Some(CommentKind::Suggestion),
+            Some(CommentKind::Suggestion) => Some(CommentKind::Good),
+            Some(CommentKind::Good) => None,
+        }
+    }
+}
+
 /// A hunk-level comment.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Comment {
     /// Unique identifier.
     pub id: CommentId,
@@ -69,8 +154,17 @@ pub struct Comment {
     /// Context in which the comment was created.
     #[serde(default, skip_serializing_if = "CommentContext::is_unscoped")]
     pub context: CommentContext,
+    /// Who wrote the comment (`Name <email>`), if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
     /// Comment text.
     pub message: String,
+    /// Replacement text for the anchored new-side lines (see [`crate::core::apply_suggestion`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    /// Severity category, if the author picked one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<CommentKind>,
     /// Current status.
     pub status: CommentStatus,
     /// Location anchor.
@@ -81,25 +175,115 @@ pub struct Comment {
     /// Resolution timestamp (milliseconds since epoch).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub resolved_at_ms: Option<u64>,
+    /// Replies, oldest first. Resolving the comment closes the whole thread.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replies: Vec<CommentReply>,
+    /// Earlier versions of the message, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edits: Vec<CommentEdit>,
+}
+
+impl Comment {
+    /// When the message was last edited, if ever.
+    pub fn edited_at_ms(&self) -> Option<u64> {
+        self.edits.last().and_then(|e| e.edited_at_ms)
+    }
+}
+
+/// A superseded version of a comment's message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentEdit {
+    /// Message text before the edit.
+    pub message: String,
+    /// When it was replaced (milliseconds since epoch).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited_at_ms: Option<u64>,
+}
+
+/// A reply in a comment thread.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentReply {
+    /// Who wrote the reply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Reply text.
+    pub message: String,
+    /// Creation timestamp (milliseconds since epoch).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at_ms: Option<u64>,
 }
 
 /// Anchor describing where a comment is attached.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Anchor {
     /// Selectors for locating the comment (tried in order).
     pub selectors: Vec<Selector>,
 }
 
+impl Anchor {
+    /// Lines the anchor pointed at when it was created, as `(side, start, end)`
+    /// (0-indexed, inclusive). Prefers an exact line range, then the hunk's
+    /// new-side range (old side for pure deletions), then the context snapshot.
+    pub fn recorded_span(&self) -> Option<(DiffSide, usize, usize)> {
+        let line_range = self.selectors.iter().find_map(|s| match s {
+            Selector::LineRangeV1(sel) => Some((sel.side, sel.start_line, sel.end_line)),
+            _ => None,
+        });
+        let hunk = || {
+            self.selectors.iter().find_map(|s| match s {
+                Selector::DiffHunkV1(sel) => Some(ranges_line_span(sel.old_range, sel.new_range)),
+                _ => None,
+            })
+        };
+        let context = || {
+            self.selectors.iter().find_map(|s| match s {
+                Selector::ContextV1(sel) if !sel.lines.is_empty() => Some((
+                    sel.side,
+                    sel.start_line,
+                    sel.start_line + sel.lines.len() - 1,
+                )),
+                _ => None,
+            })
+        };
+        line_range.or_else(hunk).or_else(context)
+    }
+}
+
 /// Selector type for locating a comment target.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Selector {
     /// V1 hunk-based selector using line ranges and content digest.
     DiffHunkV1(DiffHunkSelectorV1),
+    /// V1 selector for an exact line span on one side of the diff.
+    LineRangeV1(LineRangeSelectorV1),
+    /// V1 text snapshot of the target lines and their surroundings, used to
+    /// relocate the comment when the exact selectors no longer match.
+    ContextV1(ContextSelectorV1),
+}
+
+/// Side of a diff that a line-based selector points into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffSide {
+    /// Old (left) file.
+    Old,
+    /// New (right) file.
+    New,
+}
+
+impl DiffSide {
+    /// Lowercase name (`old` / `new`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiffSide::Old => "old",
+            DiffSide::New => "new",
+        }
+    }
 }
 
 /// V1 selector: line ranges + content digest.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DiffHunkSelectorV1 {
     /// Old file line range (start, count).
     pub old_range: (usize, usize),
@@ -109,6 +293,118 @@ pub struct DiffHunkSelectorV1 {
     pub digest_hex: String,
 }
 
+/// V1 line-range selector: inclusive line span + content fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineRangeSelectorV1 {
+    /// Which file the lines belong to.
+    pub side: DiffSide,
+    /// First line (0-indexed, inclusive).
+    pub start_line: usize,
+    /// Last line (0-indexed, inclusive).
+    pub end_line: usize,
+    /// FNV-1a 64-bit hash of the selected lines, lowercase hex.
+    pub fingerprint_hex: String,
+}
+
+impl LineRangeSelectorV1 {
+    /// Whether the recorded lines are still at the recorded position in `buffer`.
+    pub fn matches(&self, buffer: &TextBuffer) -> bool {
+        fingerprint_lines(buffer, self.start_line, self.end_line).as_deref()
+            == Some(self.fingerprint_hex.as_str())
+    }
+
+    /// Whether `line` on `side` falls inside this range.
+    pub fn contains(&self, side: DiffSide, line: usize) -> bool {
+        self.side == side && (self.start_line..=self.end_line).contains(&line)
+    }
+}
+
+/// Number of surrounding lines captured on each side by [`ContextSelectorV1`].
+pub const ANCHOR_CONTEXT_LINES: usize = 3;
+
+/// V1 context selector: the commented lines plus a few lines around them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextSelectorV1 {
+    /// Which file the lines were taken from.
+    pub side: DiffSide,
+    /// Line where `lines` started when the comment was made (0-indexed).
+    pub start_line: usize,
+    /// Lines just before the target.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub before: Vec<String>,
+    /// The commented lines.
+    pub lines: Vec<String>,
+    /// Lines just after the target.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
+}
+
+/// Snapshot lines `start..=end` of `buffer` together with their surroundings.
+///
+/// Returns `None` when the range is reversed or runs past the end of the file.
+pub fn selector_from_context(
+    buffer: &TextBuffer,
+    side: DiffSide,
+    start_line: usize,
+    end_line: usize,
+) -> Option<ContextSelectorV1> {
+    if start_line > end_line || end_line >= buffer.line_count() {
+        return None;
+    }
+    let text = |range: std::ops::Range<usize>| -> Vec<String> {
+        range.filter_map(|i| buffer.line_str(i)).collect()
+    };
+    let after_end = (end_line + 1 + ANCHOR_CONTEXT_LINES).min(buffer.line_count());
+    Some(ContextSelectorV1 {
+        side,
+        start_line,
+        before: text(start_line.saturating_sub(ANCHOR_CONTEXT_LINES)..start_line),
+        lines: text(start_line..end_line + 1),
+        after: text(end_line + 1..after_end),
+    })
+}
+
+/// Build a line-range selector for `start..=end` (0-indexed) in `buffer`.
+///
+/// Returns `None` when the range is reversed or runs past the end of the file.
+pub fn selector_from_line_range(
+    buffer: &TextBuffer,
+    side: DiffSide,
+    start_line: usize,
+    end_line: usize,
+) -> Option<LineRangeSelectorV1> {
+    Some(LineRangeSelectorV1 {
+        side,
+        start_line,
+        end_line,
+        fingerprint_hex: fingerprint_lines(buffer, start_line, end_line)?,
+    })
+}
+
+/// FNV-1a 64-bit digest of lines `start..=end`, each fed with a trailing `\n`.
+fn fingerprint_lines(buffer: &TextBuffer, start: usize, end: usize) -> Option<String> {
+    if start > end || end >= buffer.line_count() {
+        return None;
+    }
+    let mut hash = Fnv1a::new();
+    for line in start..=end {
+        hash.write(buffer.line(line)?);
+        hash.write(b"\n");
+    }
+    Some(hash.finish_hex())
+}
+
+/// Find the render row showing `line` on `side`.
+pub fn row_for_line(diff: &DiffResult, side: DiffSide, line: usize) -> Option<usize> {
+    diff.rows().iter().position(|row| {
+        let line_ref = match side {
+            DiffSide::Old => row.old.as_ref(),
+            DiffSide::New => row.new.as_ref(),
+        };
+        line_ref.is_some_and(|l| l.line_num == line)
+    })
+}
+
 /// Build a selector from a hunk in a diff.
 pub fn selector_from_hunk(diff: &DiffResult, hunk_idx: usize) -> Option<DiffHunkSelectorV1> {
     let hunk = diff.hunks().get(hunk_idx)?;
@@ -119,6 +415,39 @@ pub fn selector_from_hunk(diff: &DiffResult, hunk_idx: usize) -> Option<DiffHunk
     })
 }
 
+/// Side and inclusive line span a hunk covers, preferring the new side.
+pub fn hunk_line_span(hunk: &Hunk) -> (DiffSide, usize, usize) {
+    ranges_line_span(hunk.old_range, hunk.new_range)
+}
+
+/// [`hunk_line_span`] of the `(start, count)` ranges a hunk or hunk selector records.
+fn ranges_line_span(
+    old_range: (usize, usize),
+    new_range: (usize, usize),
+) -> (DiffSide, usize, usize) {
+    let (side, (start, count)) = if new_range.1 > 0 {
+        (DiffSide::New, new_range)
+    } else {
+        (DiffSide::Old, old_range)
+    };
+    (side, start, start + count.saturating_sub(1))
+}
+
+/// Snapshot the lines of a hunk (see [`hunk_line_span`]) for relocation.
+pub fn context_from_hunk(
+    diff: &DiffResult,
+    hunk_idx: usize,
+    old: &TextBuffer,
+    new: &TextBuffer,
+) -> Option<ContextSelectorV1> {
+    let (side, start, end) = hunk_line_span(diff.hunks().get(hunk_idx)?);
+    let buffer = match side {
+        DiffSide::Old => old,
+        DiffSide::New => new,
+    };
+    selector_from_context(buffer, side, start, end)
+}
+
 /// Compute FNV-1a 64-bit digest of changed rows in a hunk.
 /// Feeds `-<old_line>\n` for deletions/replaces (old side)
 /// and `+<new_line>\n` for insertions/replaces (new side).
@@ -132,19 +461,40 @@ pub fn format_anchor_summary(anchor: &Anchor) -> String {
     anchor
         .selectors
         .iter()
-        .map(|s| match s {
+        .filter_map(|s| match s {
             Selector::DiffHunkV1(h) => {
                 // Safe truncation: use get() to avoid panic on short/malformed digests
                 let digest_prefix = h.digest_hex.get(..8).unwrap_or(&h.digest_hex);
-                format!(
+                Some(format!(
                     "@@ -{},{} +{},{} @@ [{}]",
                     h.old_range.0 + 1,
                     h.old_range.1,
                     h.new_range.0 + 1,
                     h.new_range.1,
                     digest_prefix
-                )
+                ))
+            }
+            Selector::LineRangeV1(r) => {
+                let fingerprint = r.fingerprint_hex.get(..8).unwrap_or(&r.fingerprint_hex);
+                Some(if r.start_line == r.end_line {
+                    format!(
+                        "{} L{} [{}]",
+                        r.side.as_str(),
+                        r.start_line + 1,
+                        fingerprint
+                    )
+                } else {
+                    format!(
+                        "{} L{}-{} [{}]",
+                        r.side.as_str(),
+                        r.start_line + 1,
+                        r.end_line + 1,
+                        fingerprint
+                    )
+                })
             }
+            // Context snapshots only help relocation; they add nothing to the summary.
+            Selector::ContextV1(_) => None,
         })
         .collect::<Vec<_>>()
         .join("; ")
@@ -207,6 +557,26 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn context_pull_request_matches_legacy_pseudo_commit() {
+        let pr = CommentContext::for_source(&DiffSource::PullRequest {
+            number: 42,
+            head: "feature".to_string(),
+            base: "main".to_string(),
+        });
+        assert_eq!(pr, CommentContext::PullRequest { number: 42 });
+
+        let legacy = CommentContext::Commit {
+            commit: "pr-42".to_string(),
+        };
+        assert!(legacy.matches(&pr));
+        assert!(!legacy.matches(&CommentContext::PullRequest { number: 4 }));
+        assert!(!pr.matches(&legacy));
+
+        let json = serde_json::to_string(&pr).unwrap();
+        assert_eq!(json, r#"{"type":"pull_request","number":42}"#);
+    }
+
     #[test]
     fn format_anchor_summary_short_digest() {
         // Test with a short/malformed digest that would panic with direct slicing
@@ -234,6 +604,72 @@ mod tests {
         assert!(summary.contains("[]")); // Should handle gracefully
     }
 
+    #[test]
+    fn line_range_selector_fingerprints_lines() {
+        let buffer = TextBuffer::new(b"a\nb\nc\nd\n");
+        let sel = selector_from_line_range(&buffer, DiffSide::New, 1, 2).unwrap();
+        assert_eq!(sel.fingerprint_hex.len(), 16);
+        assert!(sel.matches(&buffer));
+        assert!(sel.contains(DiffSide::New, 2));
+        assert!(!sel.contains(DiffSide::Old, 2));
+        assert!(!sel.contains(DiffSide::New, 3));
+
+        let edited = TextBuffer::new(b"a\nB\nc\nd\n");
+        assert!(!sel.matches(&edited));
+        let shifted = TextBuffer::new(b"x\na\nb\nc\nd\n");
+        assert!(!sel.matches(&shifted));
+
+        assert!(selector_from_line_range(&buffer, DiffSide::New, 2, 1).is_none());
+        assert!(selector_from_line_range(&buffer, DiffSide::New, 3, 4).is_none());
+    }
+
+    #[test]
+    fn line_range_serializes_with_type_tag() {
+        let anchor = Anchor {
+            selectors: vec![Selector::LineRangeV1(LineRangeSelectorV1 {
+                side: DiffSide::Old,
+                start_line: 41,
+                end_line: 47,
+                fingerprint_hex: "0123456789abcdef".to_string(),
+            })],
+        };
+        let json = serde_json::to_value(&anchor).unwrap();
+        assert_eq!(json["selectors"][0]["type"], "line_range_v1");
+        assert_eq!(json["selectors"][0]["side"], "old");
+        assert_eq!(format_anchor_summary(&anchor), "old L42-48 [01234567]");
+    }
+
+    #[test]
+    fn context_selector_captures_surroundings() {
+        let buffer = TextBuffer::new(b"1\n2\n3\n4\n5\n6\n7\n8\n9\n");
+        let ctx = selector_from_context(&buffer, DiffSide::New, 1, 2).unwrap();
+        assert_eq!(ctx.before, vec!["1"]);
+        assert_eq!(ctx.lines, vec!["2", "3"]);
+        assert_eq!(ctx.after, vec!["4", "5", "6"]);
+
+        let tail = selector_from_context(&buffer, DiffSide::Old, 8, 8).unwrap();
+        assert_eq!(tail.before, vec!["6", "7", "8"]);
+        assert!(tail.after.is_empty());
+        assert!(selector_from_context(&buffer, DiffSide::Old, 9, 9).is_none());
+
+        let anchor = Anchor {
+            selectors: vec![Selector::ContextV1(ctx)],
+        };
+        assert_eq!(format_anchor_summary(&anchor), "");
+    }
+
+    #[test]
+    fn row_for_line_finds_each_side() {
+        let old = TextBuffer::new(b"a\nb\nc\n");
+        let new = TextBuffer::new(b"a\nc\nd\n");
+        let diff = DiffResult::compute(&old, &new);
+        let row = row_for_line(&diff, DiffSide::Old, 1).unwrap();
+        assert_eq!(diff.rows()[row].old.as_ref().unwrap().content, "b");
+        let row = row_for_line(&diff, DiffSide::New, 2).unwrap();
+        assert_eq!(diff.rows()[row].new.as_ref().unwrap().content, "d");
+        assert!(row_for_line(&diff, DiffSide::New, 9).is_none());
+    }
+
     #[test]
     fn format_anchor_summary_normal_digest() {
         let anchor = Anchor {
@@ -246,4 +682,42 @@ mod tests {
         let summary = format_anchor_summary(&anchor);
         assert!(summary.contains("[01234567]")); // Should truncate to 8 chars
     }
+
+    #[test]
+    fn recorded_span_prefers_line_range_over_hunk() {
+        let hunk = Selector::DiffHunkV1(DiffHunkSelectorV1 {
+            old_range: (4, 0),
+            new_range: (4, 3),
+            digest_hex: "00".to_string(),
+        });
+        let range = Selector::LineRangeV1(LineRangeSelectorV1 {
+            side: DiffSide::New,
+            start_line: 5,
+            end_line: 5,
+            fingerprint_hex: "00".to_string(),
+        });
+        let anchor = Anchor {
+            selectors: vec![hunk.clone(), range],
+        };
+        assert_eq!(anchor.recorded_span(), Some((DiffSide::New, 5, 5)));
+        let anchor = Anchor {
+            selectors: vec![hunk],
+        };
+        assert_eq!(anchor.recorded_span(), Some((DiffSide::New, 4, 6)));
+        assert_eq!(Anchor { selectors: vec![] }.recorded_span(), None);
+    }
+
+    #[test]
+    fn comment_kind_parses_and_cycles() {
+        assert_eq!(CommentKind::parse("BUG"), Some(CommentKind::Bug));
+        assert_eq!(CommentKind::parse("nit"), None);
+        let mut kind = None;
+        let mut seen = Vec::new();
+        for _ in 0..5 {
+            kind = CommentKind::cycle(kind);
+            seen.push(kind);
+        }
+        assert_eq!(seen.last(), Some(&None));
+        assert!(CommentKind::Bug < CommentKind::Good);
+    }
 }
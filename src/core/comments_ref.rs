@@ -0,0 +1,535 @@
+//! Comments stored in a git ref, shareable over plain git remotes.
+//!
+//! The ref `refs/quickdiff/comments` points at a commit whose tree holds a single
+//! `comments.json` (the same schema as the file store). Every change is a new commit
+//! on top of the previous one, so two copies of the ref can be merged against their
+//! common ancestor after a `comments pull`.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::path::Path;
+use std::process::Command;
+
+use git2::{Commit, ErrorCode, Oid, Repository, Signature};
+
+use crate::core::{
+    Comment, CommentId, CommentKind, CommentStatus, CommentStore, CommentStoreError, CommentsState,
+    Fnv1a, NewComment, RelPath, RepoRoot, lock_file, now_ms,
+};
+
+/// Ref holding the comments.
+pub const COMMENTS_REF: &str = "refs/quickdiff/comments";
+
+/// File name of the state blob inside the ref's tree.
+const STATE_FILE: &str = "comments.json";
+
+/// Comment store backed by [`COMMENTS_REF`].
+///
+/// Like [`crate::core::FileCommentStore`], every mutation takes an advisory lock and
+/// applies itself to the latest ref tip.
+pub struct GitRefCommentStore {
+    git: Repository,
+    state: CommentsState,
+}
+
+impl GitRefCommentStore {
+    /// Open the ref-backed store of the git repository containing `repo_root`.
+    #[must_use = "this returns a Result that should be checked"]
+    pub fn open(repo_root: &RepoRoot) -> Result<Self, CommentStoreError> {
+        let git = Repository::discover(repo_root.path())?;
+        let state = match tip(&git)? {
+            Some(oid) => state_at(&git, oid)?,
+            None => CommentsState::default(),
+        };
+        Ok(Self { git, state })
+    }
+
+    fn lock(&self) -> Result<File, CommentStoreError> {
+        lock_file(&self.git.commondir().join("quickdiff-comments.lock"))
+    }
+
+    /// Apply `f` to the state at the ref tip and commit the result if it changed.
+    fn update<T>(
+        &mut self,
+        f: impl FnOnce(&mut CommentsState) -> (T, bool),
+    ) -> Result<T, CommentStoreError> {
+        let _lock = self.lock()?;
+        let parent = tip(&self.git)?;
+        self.state = match parent {
+            Some(oid) => state_at(&self.git, oid)?,
+            None => CommentsState::default(),
+        };
+        let (result, changed) = f(&mut self.state);
+        if changed {
+            let parents = match parent {
+                Some(oid) => vec![self.git.find_commit(oid)?],
+                None => Vec::new(),
+            };
+            let oid = commit_state(&self.git, &self.state, &parents, "Update comments")?;
+            set_tip(&self.git, oid, parent)?;
+        }
+        Ok(result)
+    }
+}
+
+impl CommentStore for GitRefCommentStore {
+    fn list(&self, include_resolved: bool) -> Vec<&Comment> {
+        self.state.list(include_resolved)
+    }
+
+    fn list_for_path(&self, path: &RelPath, include_resolved: bool) -> Vec<&Comment> {
+        self.state.list_for_path(path, include_resolved)
+    }
+
+    fn add(&mut self, comment: NewComment) -> Result<CommentId, CommentStoreError> {
+        self.update(|state| state.add(comment, Some(now_ms())))
+    }
+
+    fn reply(
+        &mut self,
+        id: CommentId,
+        author: Option<String>,
+        message: String,
+    ) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.reply(id, author, message, Some(now_ms())))
+    }
+
+    fn resolve(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.set_status(id, CommentStatus::Resolved, Some(now_ms())))
+    }
+
+    fn reopen(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.set_status(id, CommentStatus::Open, None))
+    }
+
+    fn edit(&mut self, id: CommentId, message: String) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.edit(id, message, Some(now_ms())))
+    }
+
+    fn set_kind(
+        &mut self,
+        id: CommentId,
+        kind: Option<CommentKind>,
+    ) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.set_kind(id, kind))
+    }
+
+    fn delete(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.delete(id))
+    }
+
+    fn get(&self, id: CommentId) -> Option<&Comment> {
+        self.state.get(id)
+    }
+}
+
+/// What `comments pull` did to the local ref.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullOutcome {
+    /// The remote has no comments ref.
+    NoRemoteComments,
+    /// The local ref already contains the remote's comments.
+    UpToDate,
+    /// The local ref was moved to the remote's (no local changes).
+    FastForward,
+    /// Local and remote changes were merged into a new commit.
+    Merged,
+}
+
+/// Reject remote names git would parse as options (defense in depth; the
+/// remote is also passed after `--`).
+fn validate_remote(remote: &str) -> Result<(), CommentStoreError> {
+    if remote.is_empty() || remote.starts_with('-') {
+        return Err(CommentStoreError::Sync(format!(
+            "invalid remote name: {:?}",
+            remote
+        )));
+    }
+    Ok(())
+}
+
+/// Push the comments ref to `remote`.
+///
+/// Fails if the remote has comments this checkout has not pulled yet.
+pub fn push_comments(repo_root: &RepoRoot, remote: &str) -> Result<(), CommentStoreError> {
+    validate_remote(remote)?;
+    let git = Repository::discover(repo_root.path())?;
+    if tip(&git)?.is_none() {
+        return Err(CommentStoreError::Sync(format!(
+            "no comments in {} to push",
+            COMMENTS_REF
+        )));
+    }
+    let refspec = format!("{}:{}", COMMENTS_REF, COMMENTS_REF);
+    let output = run_git(
+        repo_root.path(),
+        &["push", "--quiet", "--", remote, &refspec],
+    )?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("rejected") || stderr.contains("fetch first") {
+        return Err(CommentStoreError::Sync(format!(
+            "{} has comments that are not here yet; run `quickdiff comments pull {}` first",
+            remote, remote
+        )));
+    }
+    Err(CommentStoreError::Sync(format!(
+        "git push {} failed: {}",
+        remote,
+        stderr.trim()
+    )))
+}
+
+/// Fetch the comments ref from `remote` and merge it into the local one.
+pub fn pull_comments(repo_root: &RepoRoot, remote: &str) -> Result<PullOutcome, CommentStoreError> {
+    validate_remote(remote)?;
+    let tracking = tracking_ref(&Repository::discover(repo_root.path())?, remote);
+    let refspec = format!("+{}:{}", COMMENTS_REF, tracking);
+    let output = run_git(
+        repo_root.path(),
+        &["fetch", "--quiet", "--", remote, &refspec],
+    )?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("couldn't find remote ref") {
+            return Ok(PullOutcome::NoRemoteComments);
+        }
+        return Err(CommentStoreError::Sync(format!(
+            "git fetch {} failed: {}",
+            remote,
+            stderr.trim()
+        )));
+    }
+
+    let store = GitRefCommentStore::open(repo_root)?;
+    let git = &store.git;
+    let theirs = git.refname_to_id(&tracking)?;
+    let _lock = store.lock()?;
+    let Some(ours) = tip(git)? else {
+        set_tip(git, theirs, None)?;
+        return Ok(PullOutcome::FastForward);
+    };
+    if ours == theirs || git.graph_descendant_of(ours, theirs)? {
+        return Ok(PullOutcome::UpToDate);
+    }
+    if git.graph_descendant_of(theirs, ours)? {
+        set_tip(git, theirs, Some(ours))?;
+        return Ok(PullOutcome::FastForward);
+    }
+
+    let base = match git.merge_base(ours, theirs) {
+        Ok(oid) => state_at(git, oid)?,
+        Err(e) if e.code() == ErrorCode::NotFound => CommentsState::default(),
+        Err(e) => return Err(e.into()),
+    };
+    let merged = merge_states(&base, &state_at(git, ours)?, &state_at(git, theirs)?);
+    let parents = [git.find_commit(ours)?, git.find_commit(theirs)?];
+    let message = format!("Merge comments from {}", remote);
+    let oid = commit_state(git, &merged, &parents, &message)?;
+    set_tip(git, oid, Some(ours))?;
+    Ok(PullOutcome::Merged)
+}
+
+/// Local ref the remote's comments are fetched into.
+///
+/// Configured remotes use their name; paths and URLs are not valid ref
+/// components, so they are keyed by a hash instead.
+fn tracking_ref(git: &Repository, remote: &str) -> String {
+    let key = if git.find_remote(remote).is_ok() {
+        remote.to_string()
+    } else {
+        let mut hash = Fnv1a::new();
+        hash.write(remote.as_bytes());
+        format!("url-{}", hash.finish_hex())
+    };
+    format!("refs/quickdiff/remotes/{}/comments", key)
+}
+
+/// Three-way merge of comment states.
+///
+/// Comments are matched by id. A side's change wins over the other side leaving the
+/// comment untouched; when both changed the same field, the remote (`theirs`) wins,
+/// except for the message, where the later edit wins and the other text is kept in
+/// the edit history. Replies and edits from both sides are kept. Comments added on
+/// both sides under the same id keep the remote id, and the local one is renumbered.
+pub fn merge_states(
+    base: &CommentsState,
+    ours: &CommentsState,
+    theirs: &CommentsState,
+) -> CommentsState {
+    fn by_id(state: &CommentsState) -> HashMap<CommentId, &Comment> {
+        state.comments.iter().map(|c| (c.id, c)).collect()
+    }
+    let (base_ids, our_ids, their_ids) = (by_id(base), by_id(ours), by_id(theirs));
+    let ids: BTreeSet<CommentId> = our_ids.keys().chain(their_ids.keys()).copied().collect();
+
+    let mut comments = Vec::new();
+    let mut renumber = Vec::new();
+    for id in ids {
+        let b = base_ids.get(&id).copied();
+        let o = our_ids.get(&id).copied();
+        let t = their_ids.get(&id).copied();
+        match (b, o, t) {
+            // Deleted on one side: keep it only if the other side changed it since.
+            (Some(b), Some(o), None) if o != b => comments.push(o.clone()),
+            (Some(b), None, Some(t)) if t != b => comments.push(t.clone()),
+            (Some(_), _, None) | (Some(_), None, _) => {}
+            (None, Some(o), None) => comments.push(o.clone()),
+            (None, None, Some(t)) => comments.push(t.clone()),
+            (None, Some(o), Some(t)) if o == t => comments.push(t.clone()),
+            (None, Some(o), Some(t)) => {
+                comments.push(t.clone());
+                renumber.push(o.clone());
+            }
+            (Some(b), Some(o), Some(t)) => comments.push(merge_comment(b, o, t)),
+            (None, None, None) => {}
+        }
+    }
+
+    let max_id = comments.iter().map(|c| c.id).max().unwrap_or(0);
+    let mut next_id = ours.next_id.max(theirs.next_id).max(max_id + 1);
+    for mut comment in renumber {
+        comment.id = next_id;
+        next_id += 1;
+        comments.push(comment);
+    }
+    comments.sort_by_key(|c| c.id);
+
+    CommentsState {
+        version: theirs.version.max(ours.version),
+        next_id,
+        comments,
+    }
+}
+
+fn merge_comment(base: &Comment, ours: &Comment, theirs: &Comment) -> Comment {
+    fn pick<T: PartialEq + Clone>(base: &T, ours: &T, theirs: &T) -> T {
+        if ours != base && theirs == base {
+            ours.clone()
+        } else {
+            theirs.clone()
+        }
+    }
+
+    let mut merged = theirs.clone();
+    merged.path = pick(&base.path, &ours.path, &theirs.path);
+    merged.context = pick(&base.context, &ours.context, &theirs.context);
+    merged.suggestion = pick(&base.suggestion, &ours.suggestion, &theirs.suggestion);
+    merged.kind = pick(&base.kind, &ours.kind, &theirs.kind);
+    merged.anchor = pick(&base.anchor, &ours.anchor, &theirs.anchor);
+    if ours.status != base.status && theirs.status == base.status {
+        merged.status = ours.status;
+        merged.resolved_at_ms = ours.resolved_at_ms;
+    }
+
+    merged.replies = union(&theirs.replies, &ours.replies);
+    merged
+        .replies
+        .sort_by_key(|r| r.created_at_ms.unwrap_or(u64::MAX));
+    merged.edits = union(&theirs.edits, &ours.edits);
+    merged
+        .edits
+        .sort_by_key(|e| e.edited_at_ms.unwrap_or(u64::MAX));
+    // Each side that edited the message recorded the base text; keep one copy.
+    let mut base_recorded = false;
+    merged.edits.retain(|e| {
+        base.edits.contains(e)
+            || e.message != base.message
+            || !std::mem::replace(&mut base_recorded, true)
+    });
+
+    let ours_changed = ours.message != base.message;
+    let theirs_changed = theirs.message != base.message;
+    if ours_changed && (!theirs_changed || ours.edited_at_ms() > theirs.edited_at_ms()) {
+        merged.message = ours.message.clone();
+    }
+    if ours_changed && theirs_changed && ours.message != theirs.message {
+        // Keep the losing text in the history so neither edit is lost.
+        let (loser, edited_at_ms) = if merged.message == ours.message {
+            (&theirs.message, theirs.edited_at_ms())
+        } else {
+            (&ours.message, ours.edited_at_ms())
+        };
+        merged.edits.push(crate::core::CommentEdit {
+            message: loser.clone(),
+            edited_at_ms,
+        });
+    }
+    merged
+        .edits
+        .sort_by_key(|e| e.edited_at_ms.unwrap_or(u64::MAX));
+    merged
+}
+
+/// `first` followed by the items of `second` it does not already contain.
+fn union<T: PartialEq + Clone>(first: &[T], second: &[T]) -> Vec<T> {
+    let mut out = first.to_vec();
+    for item in second {
+        if !out.contains(item) {
+            out.push(item.clone());
+        }
+    }
+    out
+}
+
+fn tip(git: &Repository) -> Result<Option<Oid>, git2::Error> {
+    match git.refname_to_id(COMMENTS_REF) {
+        Ok(oid) => Ok(Some(oid)),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Move the ref to `new`, failing if it no longer points at `old`.
+fn set_tip(git: &Repository, new: Oid, old: Option<Oid>) -> Result<(), git2::Error> {
+    let log = "quickdiff: update comments";
+    match old {
+        Some(old) => git.reference_matching(COMMENTS_REF, new, true, old, log)?,
+        None => git.reference(COMMENTS_REF, new, false, log)?,
+    };
+    Ok(())
+}
+
+fn state_at(git: &Repository, commit: Oid) -> Result<CommentsState, CommentStoreError> {
+    let tree = git.find_commit(commit)?.tree()?;
+    let Some(entry) = tree.get_name(STATE_FILE) else {
+        return Ok(CommentsState::default());
+    };
+    let blob = git.find_blob(entry.id())?;
+    Ok(serde_json::from_slice(blob.content())?)
+}
+
+fn commit_state(
+    git: &Repository,
+    state: &CommentsState,
+    parents: &[Commit<'_>],
+    message: &str,
+) -> Result<Oid, CommentStoreError> {
+    let blob = git.blob(serde_json::to_string_pretty(state)?.as_bytes())?;
+    let mut builder = git.treebuilder(None)?;
+    builder.insert(STATE_FILE, blob, git2::FileMode::Blob.into())?;
+    let tree = git.find_tree(builder.write()?)?;
+    let signature = git
+        .signature()
+        .or_else(|_| Signature::now("quickdiff", "quickdiff@localhost"))?;
+    let parents: Vec<&Commit<'_>> = parents.iter().collect();
+    Ok(git.commit(None, &signature, &signature, message, &tree, &parents)?)
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<std::process::Output, CommentStoreError> {
+    Ok(Command::new("git").arg("-C").arg(dir).args(args).output()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Anchor, CommentContext, CommentEdit, CommentReply};
+
+    fn comment(id: CommentId, message: &str) -> Comment {
+        Comment {
+            id,
+            path: RelPath::new("src/lib.rs"),
+            context: CommentContext::Worktree,
+            author: None,
+            message: message.to_string(),
+            suggestion: None,
+            kind: None,
+            status: CommentStatus::Open,
+            anchor: Anchor {
+                selectors: Vec::new(),
+            },
+            created_at_ms: Some(id),
+            resolved_at_ms: None,
+            replies: Vec::new(),
+            edits: Vec::new(),
+        }
+    }
+
+    fn state(comments: Vec<Comment>) -> CommentsState {
+        CommentsState {
+            version: 1,
+            next_id: comments.iter().map(|c| c.id).max().unwrap_or(0) + 1,
+            comments,
+        }
+    }
+
+    #[test]
+    fn merge_keeps_additions_and_renumbers_colliding_ids() {
+        let base = state(vec![comment(1, "shared")]);
+        let ours = state(vec![comment(1, "shared"), comment(2, "mine")]);
+        let theirs = state(vec![comment(1, "shared"), comment(2, "theirs")]);
+
+        let merged = merge_states(&base, &ours, &theirs);
+        let messages: Vec<_> = merged
+            .comments
+            .iter()
+            .map(|c| (c.id, c.message.as_str()))
+            .collect();
+        assert_eq!(messages, vec![(1, "shared"), (2, "theirs"), (3, "mine")]);
+        assert_eq!(merged.next_id, 4);
+    }
+
+    #[test]
+    fn merge_combines_field_changes_and_threads() {
+        let base = state(vec![comment(1, "original")]);
+        let mut ours = base.clone();
+        ours.comments[0].status = CommentStatus::Resolved;
+        ours.comments[0].resolved_at_ms = Some(50);
+        ours.comments[0].replies.push(CommentReply {
+            author: None,
+            message: "done".to_string(),
+            created_at_ms: Some(20),
+        });
+        let mut theirs = base.clone();
+        theirs.comments[0].kind = Some(CommentKind::Bug);
+        theirs.comments[0].replies.push(CommentReply {
+            author: None,
+            message: "why?".to_string(),
+            created_at_ms: Some(10),
+        });
+
+        let merged = merge_states(&base, &ours, &theirs);
+        let c = &merged.comments[0];
+        assert_eq!(c.status, CommentStatus::Resolved);
+        assert_eq!(c.kind, Some(CommentKind::Bug));
+        let replies: Vec<_> = c.replies.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(replies, vec!["why?", "done"]);
+    }
+
+    #[test]
+    fn merge_conflicting_edits_keeps_later_and_records_other() {
+        let base = state(vec![comment(1, "original")]);
+        let edit = |message: &str, at: u64| {
+            let mut s = base.clone();
+            s.comments[0].message = message.to_string();
+            s.comments[0].edits.push(CommentEdit {
+                message: "original".to_string(),
+                edited_at_ms: Some(at),
+            });
+            s
+        };
+        let merged = merge_states(&base, &edit("ours", 200), &edit("theirs", 100));
+        let c = &merged.comments[0];
+        assert_eq!(c.message, "ours");
+        let history: Vec<_> = c.edits.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(history, vec!["original", "theirs"]);
+    }
+
+    #[test]
+    fn merge_delete_wins_over_untouched_but_not_over_edit() {
+        let base = state(vec![comment(1, "a"), comment(2, "b")]);
+        let ours = state(vec![comment(2, "b")]);
+        let mut theirs = base.clone();
+        theirs.comments[1].message = "b edited".to_string();
+        let merged = merge_states(&base, &ours, &base);
+        assert_eq!(merged.comments.len(), 1);
+        assert_eq!(merged.comments[0].id, 2);
+
+        let ours = state(vec![comment(1, "a")]);
+        let merged = merge_states(&base, &ours, &theirs);
+        assert_eq!(merged.comments.len(), 2);
+        assert_eq!(merged.comments[1].message, "b edited");
+    }
+}
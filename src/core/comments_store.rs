@@ -1,12 +1,16 @@
 //! Comment persistence with repo-local storage.
 
-use std::path::PathBuf;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::core::{Anchor, Comment, CommentContext, CommentId, CommentStatus, RelPath, RepoRoot};
+use crate::core::{
+    Anchor, Comment, CommentContext, CommentEdit, CommentId, CommentKind, CommentReply,
+    CommentStatus, GitRefCommentStore, RelPath, RepoRoot,
+};
 
 /// Errors from comment store operations.
 #[derive(Debug, Error)]
@@ -18,6 +22,35 @@ pub enum CommentStoreError {
     /// JSON serialization/deserialization error.
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
+    /// Git error from the ref-backed store.
+    #[error("git error: {0}")]
+    Git(#[from] git2::Error),
+    /// Pushing or pulling comments to or from a remote failed.
+    #[error("{0}")]
+    Sync(String),
+}
+
+/// Where comments are persisted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommentBackend {
+    /// `.quickdiff/comments.json` (see [`FileCommentStore`]).
+    #[default]
+    File,
+    /// The `refs/quickdiff/comments` git ref, shareable over remotes
+    /// (see [`crate::core::GitRefCommentStore`]).
+    GitRef,
+}
+
+/// Open the comment store configured for `repo_root`.
+#[must_use = "this returns a Result that should be checked"]
+pub fn open_comment_store(
+    repo_root: &RepoRoot,
+) -> Result<Box<dyn CommentStore>, CommentStoreError> {
+    Ok(match repo_root.comment_backend() {
+        CommentBackend::File => Box::new(FileCommentStore::open(repo_root)?),
+        CommentBackend::GitRef => Box::new(GitRefCommentStore::open(repo_root)?),
+    })
 }
 
 /// Persisted state schema.
@@ -41,6 +74,179 @@ impl Default for CommentsState {
     }
 }
 
+/// Mutations shared by the stores. Each returns its result and whether the state
+/// changed; `now` is the timestamp to record (`None` for the in-memory store).
+impl CommentsState {
+    pub(crate) fn list(&self, include_resolved: bool) -> Vec<&Comment> {
+        self.comments
+            .iter()
+            .filter(|c| include_resolved || c.status == CommentStatus::Open)
+            .collect()
+    }
+
+    pub(crate) fn list_for_path(&self, path: &RelPath, include_resolved: bool) -> Vec<&Comment> {
+        self.comments
+            .iter()
+            .filter(|c| c.path == *path && (include_resolved || c.status == CommentStatus::Open))
+            .collect()
+    }
+
+    pub(crate) fn get(&self, id: CommentId) -> Option<&Comment> {
+        self.comments.iter().find(|c| c.id == id)
+    }
+
+    fn find_mut(&mut self, id: CommentId) -> Option<&mut Comment> {
+        self.comments.iter_mut().find(|c| c.id == id)
+    }
+
+    pub(crate) fn add(&mut self, comment: NewComment, now: Option<u64>) -> (CommentId, bool) {
+        let NewComment {
+            path,
+            context,
+            author,
+            message,
+            kind,
+            suggestion,
+            anchor,
+        } = comment;
+        let id = self.next_id;
+        self.next_id += 1;
+        self.comments.push(Comment {
+            id,
+            path,
+            context,
+            author,
+            message,
+            suggestion,
+            kind,
+            status: CommentStatus::Open,
+            anchor,
+            created_at_ms: now,
+            resolved_at_ms: None,
+            replies: Vec::new(),
+            edits: Vec::new(),
+        });
+        (id, true)
+    }
+
+    pub(crate) fn reply(
+        &mut self,
+        id: CommentId,
+        author: Option<String>,
+        message: String,
+        now: Option<u64>,
+    ) -> (bool, bool) {
+        match self.find_mut(id) {
+            Some(comment) => {
+                comment.replies.push(CommentReply {
+                    author,
+                    message,
+                    created_at_ms: now,
+                });
+                (true, true)
+            }
+            None => (false, false),
+        }
+    }
+
+    pub(crate) fn set_status(
+        &mut self,
+        id: CommentId,
+        status: CommentStatus,
+        now: Option<u64>,
+    ) -> (bool, bool) {
+        match self.find_mut(id) {
+            Some(comment) => {
+                comment.status = status;
+                comment.resolved_at_ms = match status {
+                    CommentStatus::Resolved => now,
+                    CommentStatus::Open => None,
+                };
+                (true, true)
+            }
+            None => (false, false),
+        }
+    }
+
+    pub(crate) fn edit(
+        &mut self,
+        id: CommentId,
+        message: String,
+        now: Option<u64>,
+    ) -> (bool, bool) {
+        match self.find_mut(id) {
+            Some(comment) if comment.message != message => {
+                let previous = std::mem::replace(&mut comment.message, message);
+                comment.edits.push(CommentEdit {
+                    message: previous,
+                    edited_at_ms: now,
+                });
+                (true, true)
+            }
+            Some(_) => (true, false),
+            None => (false, false),
+        }
+    }
+
+    pub(crate) fn set_kind(&mut self, id: CommentId, kind: Option<CommentKind>) -> (bool, bool) {
+        match self.find_mut(id) {
+            Some(comment) => {
+                let changed = comment.kind != kind;
+                comment.kind = kind;
+                (true, changed)
+            }
+            None => (false, false),
+        }
+    }
+
+    pub(crate) fn delete(&mut self, id: CommentId) -> (bool, bool) {
+        let before = self.comments.len();
+        self.comments.retain(|c| c.id != id);
+        let deleted = self.comments.len() != before;
+        (deleted, deleted)
+    }
+}
+
+/// A comment to add to a [`CommentStore`]; the store assigns its id, status
+/// and timestamps.
+#[derive(Debug, Clone)]
+pub struct NewComment {
+    /// File the comment is on.
+    pub path: RelPath,
+    /// Diff the comment was made on.
+    pub context: CommentContext,
+    /// Who wrote it.
+    pub author: Option<String>,
+    /// Comment text.
+    pub message: String,
+    /// Severity.
+    pub kind: Option<CommentKind>,
+    /// Replacement text for the anchored new-side lines.
+    pub suggestion: Option<String>,
+    /// Where the comment sits in the diff.
+    pub anchor: Anchor,
+}
+
+impl NewComment {
+    /// A plain comment without author, kind or suggestion.
+    pub fn new(
+        path: RelPath,
+        context: CommentContext,
+        message: impl Into<String>,
+        anchor: Anchor,
+    ) -> Self {
+        Self {
+            path,
+            context,
+            author: None,
+            message: message.into(),
+            kind: None,
+            suggestion: None,
+            anchor,
+        }
+    }
+}
+
 /// Trait for comment storage operations.
 pub trait CommentStore {
     /// List all comments, optionally filtering by status.
@@ -50,22 +256,49 @@ pub trait CommentStore {
     fn list_for_path(&self, path: &RelPath, include_resolved: bool) -> Vec<&Comment>;
 
     /// Add a new comment, returns the assigned ID.
-    fn add(
+    fn add(&mut self, comment: NewComment) -> Result<CommentId, CommentStoreError>;
+
+    /// Append a reply to a comment's thread. Returns `false` if the comment does not exist.
+    fn reply(
         &mut self,
-        path: RelPath,
-        context: CommentContext,
+        id: CommentId,
+        author: Option<String>,
         message: String,
-        anchor: Anchor,
-    ) -> Result<CommentId, CommentStoreError>;
+    ) -> Result<bool, CommentStoreError>;
 
-    /// Resolve a comment by ID.
+    /// Resolve a comment (and with it, its thread) by ID.
     fn resolve(&mut self, id: CommentId) -> Result<bool, CommentStoreError>;
 
+    /// Reopen a resolved comment. Returns `false` if the comment does not exist.
+    fn reopen(&mut self, id: CommentId) -> Result<bool, CommentStoreError>;
+
+    /// Replace a comment's message, keeping the old text in its edit history.
+    /// Returns `false` if the comment does not exist.
+    fn edit(&mut self, id: CommentId, message: String) -> Result<bool, CommentStoreError>;
+
+    /// Change a comment's severity. Returns `false` if the comment does not exist.
+    fn set_kind(
+        &mut self,
+        id: CommentId,
+        kind: Option<CommentKind>,
+    ) -> Result<bool, CommentStoreError>;
+
+    /// Delete a comment and its replies. Returns `false` if the comment does not exist.
+    fn delete(&mut self, id: CommentId) -> Result<bool, CommentStoreError>;
+
     /// Get a comment by ID.
     fn get(&self, id: CommentId) -> Option<&Comment>;
 }
 
 /// File-backed comment store under `.quickdiff/comments.json`.
+///
+/// With shared worktree state the file lives in the common git dir instead
+/// (see [`RepoRoot::state_dir`]).
+///
+/// Several processes may write the file at once (the TUI, `comments import`, CI).
+/// Every mutation takes an advisory lock on `comments.json.lock`, re-reads the file
+/// and applies itself to what is on disk, so concurrent writers merge instead of
+/// the last one dropping the others' comments.
 pub struct FileCommentStore {
     state_path: PathBuf,
     state: CommentsState,
@@ -75,103 +308,137 @@ impl FileCommentStore {
     /// Open or create a comment store for the given repo.
     #[must_use = "this returns a Result that should be checked"]
     pub fn open(repo_root: &RepoRoot) -> Result<Self, CommentStoreError> {
-        let dir = repo_root.path().join(".quickdiff");
-        let state_path = dir.join("comments.json");
-
-        let state = if state_path.exists() {
-            let content = std::fs::read_to_string(&state_path)?;
-            serde_json::from_str(&content)?
-        } else {
-            CommentsState::default()
-        };
-
+        let state_path = Self::path_for(repo_root);
+        let state = Self::read_state(&state_path)?;
         Ok(Self { state_path, state })
     }
 
-    /// Save state to disk using atomic write.
+    /// Location of the comments file for `repo_root`.
+    pub fn path_for(repo_root: &RepoRoot) -> PathBuf {
+        repo_root.state_dir().join("comments.json")
+    }
+
+    /// Re-read the file, picking up changes made by other processes.
     #[must_use = "this returns a Result that should be checked"]
-    pub fn save(&self) -> Result<(), CommentStoreError> {
-        // Ensure parent directory exists
-        if let Some(parent) = self.state_path.parent() {
-            std::fs::create_dir_all(parent)?;
+    pub fn reload(&mut self) -> Result<(), CommentStoreError> {
+        self.state = Self::read_state(&self.state_path)?;
+        Ok(())
+    }
+
+    fn read_state(path: &Path) -> Result<CommentsState, CommentStoreError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(CommentsState::default()),
+            Err(e) => Err(e.into()),
         }
+    }
 
-        // Atomic write: temp file + rename
+    fn write_state(&self) -> Result<(), CommentStoreError> {
+        // Atomic write: temp file + rename, so readers never see a partial file
         let temp_path = self.state_path.with_extension("json.tmp");
         let content = serde_json::to_string_pretty(&self.state)?;
         std::fs::write(&temp_path, content)?;
         std::fs::rename(&temp_path, &self.state_path)?;
-
         Ok(())
     }
 
-    /// Get current timestamp in milliseconds.
-    fn now_ms() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_millis() as u64)
-            .unwrap_or(0)
+    /// Take the exclusive advisory lock on a sidecar file, because the comments
+    /// file itself is replaced on every write.
+    fn lock(&self) -> Result<File, CommentStoreError> {
+        lock_file(&self.state_path.with_extension("json.lock"))
+    }
+
+    /// Apply `f` to the latest state on disk while holding the lock.
+    ///
+    /// `f` returns its result and whether it changed anything; unchanged state is
+    /// not written back.
+    fn update<T>(
+        &mut self,
+        f: impl FnOnce(&mut CommentsState) -> (T, bool),
+    ) -> Result<T, CommentStoreError> {
+        let _lock = self.lock()?;
+        self.state = Self::read_state(&self.state_path)?;
+        let (result, changed) = f(&mut self.state);
+        if changed {
+            self.write_state()?;
+        }
+        Ok(result)
     }
 }
 
 impl CommentStore for FileCommentStore {
     fn list(&self, include_resolved: bool) -> Vec<&Comment> {
-        self.state
-            .comments
-            .iter()
-            .filter(|c| include_resolved || c.status == CommentStatus::Open)
-            .collect()
+        self.state.list(include_resolved)
     }
 
     fn list_for_path(&self, path: &RelPath, include_resolved: bool) -> Vec<&Comment> {
-        self.state
-            .comments
-            .iter()
-            .filter(|c| c.path == *path && (include_resolved || c.status == CommentStatus::Open))
-            .collect()
+        self.state.list_for_path(path, include_resolved)
+    }
+
+    fn add(&mut self, comment: NewComment) -> Result<CommentId, CommentStoreError> {
+        self.update(|state| state.add(comment, Some(now_ms())))
     }
 
-    fn add(
+    fn reply(
         &mut self,
-        path: RelPath,
-        context: CommentContext,
+        id: CommentId,
+        author: Option<String>,
         message: String,
-        anchor: Anchor,
-    ) -> Result<CommentId, CommentStoreError> {
-        let id = self.state.next_id;
-        self.state.next_id += 1;
+    ) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.reply(id, author, message, Some(now_ms())))
+    }
 
-        let comment = Comment {
-            id,
-            path,
-            context,
-            message,
-            status: CommentStatus::Open,
-            anchor,
-            created_at_ms: Some(Self::now_ms()),
-            resolved_at_ms: None,
-        };
+    fn resolve(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.set_status(id, CommentStatus::Resolved, Some(now_ms())))
+    }
 
-        self.state.comments.push(comment);
-        self.save()?;
+    fn reopen(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.set_status(id, CommentStatus::Open, None))
+    }
 
-        Ok(id)
+    fn edit(&mut self, id: CommentId, message: String) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.edit(id, message, Some(now_ms())))
     }
 
-    fn resolve(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
-        if let Some(comment) = self.state.comments.iter_mut().find(|c| c.id == id) {
-            comment.status = CommentStatus::Resolved;
-            comment.resolved_at_ms = Some(Self::now_ms());
-            self.save()?;
-            Ok(true)
-        } else {
-            Ok(false)
-        }
+    fn set_kind(
+        &mut self,
+        id: CommentId,
+        kind: Option<CommentKind>,
+    ) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.set_kind(id, kind))
+    }
+
+    fn delete(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
+        self.update(|state| state.delete(id))
     }
 
     fn get(&self, id: CommentId) -> Option<&Comment> {
-        self.state.comments.iter().find(|c| c.id == id)
+        self.state.get(id)
+    }
+}
+
+/// Current timestamp in milliseconds.
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Take an exclusive advisory lock on `path`, creating it (and its directory) if needed.
+///
+/// The lock is released when the returned file is dropped.
+pub(crate) fn lock_file(path: &Path) -> Result<File, CommentStoreError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
     }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)?;
+    file.lock()?;
+    Ok(file)
 }
 
 /// In-memory comment store (for testing).
@@ -189,57 +456,52 @@ impl MemoryCommentStore {
 
 impl CommentStore for MemoryCommentStore {
     fn list(&self, include_resolved: bool) -> Vec<&Comment> {
-        self.state
-            .comments
-            .iter()
-            .filter(|c| include_resolved || c.status == CommentStatus::Open)
-            .collect()
+        self.state.list(include_resolved)
     }
 
     fn list_for_path(&self, path: &RelPath, include_resolved: bool) -> Vec<&Comment> {
-        self.state
-            .comments
-            .iter()
-            .filter(|c| c.path == *path && (include_resolved || c.status == CommentStatus::Open))
-            .collect()
+        self.state.list_for_path(path, include_resolved)
     }
 
-    fn add(
+    fn add(&mut self, comment: NewComment) -> Result<CommentId, CommentStoreError> {
+        Ok(self.state.add(comment, None).0)
+    }
+
+    fn reply(
         &mut self,
-        path: RelPath,
-        context: CommentContext,
+        id: CommentId,
+        author: Option<String>,
         message: String,
-        anchor: Anchor,
-    ) -> Result<CommentId, CommentStoreError> {
-        let id = self.state.next_id;
-        self.state.next_id += 1;
+    ) -> Result<bool, CommentStoreError> {
+        Ok(self.state.reply(id, author, message, None).0)
+    }
 
-        let comment = Comment {
-            id,
-            path,
-            context,
-            message,
-            status: CommentStatus::Open,
-            anchor,
-            created_at_ms: None,
-            resolved_at_ms: None,
-        };
+    fn resolve(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
+        Ok(self.state.set_status(id, CommentStatus::Resolved, None).0)
+    }
 
-        self.state.comments.push(comment);
-        Ok(id)
+    fn reopen(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
+        Ok(self.state.set_status(id, CommentStatus::Open, None).0)
     }
 
-    fn resolve(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
-        if let Some(comment) = self.state.comments.iter_mut().find(|c| c.id == id) {
-            comment.status = CommentStatus::Resolved;
-            Ok(true)
-        } else {
-            Ok(false)
-        }
+    fn edit(&mut self, id: CommentId, message: String) -> Result<bool, CommentStoreError> {
+        Ok(self.state.edit(id, message, None).0)
+    }
+
+    fn set_kind(
+        &mut self,
+        id: CommentId,
+        kind: Option<CommentKind>,
+    ) -> Result<bool, CommentStoreError> {
+        Ok(self.state.set_kind(id, kind).0)
+    }
+
+    fn delete(&mut self, id: CommentId) -> Result<bool, CommentStoreError> {
+        Ok(self.state.delete(id).0)
     }
 
     fn get(&self, id: CommentId) -> Option<&Comment> {
-        self.state.comments.iter().find(|c| c.id == id)
+        self.state.get(id)
     }
 }
 
@@ -263,12 +525,12 @@ mod tests {
         let mut store = MemoryCommentStore::new();
 
         let id = store
-            .add(
+            .add(NewComment::new(
                 RelPath::new("src/main.rs"),
                 CommentContext::Worktree,
                 "Fix this".to_string(),
                 test_anchor(),
-            )
+            ))
             .unwrap();
 
         assert_eq!(id, 1);
@@ -286,12 +548,12 @@ mod tests {
         let mut store = MemoryCommentStore::new();
 
         let id = store
-            .add(
+            .add(NewComment::new(
                 RelPath::new("test.rs"),
                 CommentContext::Worktree,
                 "TODO".to_string(),
                 test_anchor(),
-            )
+            ))
             .unwrap();
 
         assert_eq!(store.list(false).len(), 1);
@@ -305,6 +567,67 @@ mod tests {
         assert_eq!(store.list(true).len(), 1);
     }
 
+    #[test]
+    fn memory_store_reply_threads_under_comment() {
+        let mut store = MemoryCommentStore::new();
+        let id = store
+            .add(NewComment::new(
+                RelPath::new("test.rs"),
+                CommentContext::Worktree,
+                "Why?".to_string(),
+                test_anchor(),
+            ))
+            .unwrap();
+
+        assert!(
+            store
+                .reply(id, Some("Ada".to_string()), "Because.".to_string())
+                .unwrap()
+        );
+        assert!(!store.reply(999, None, "lost".to_string()).unwrap());
+
+        let comment = store.get(id).unwrap();
+        assert_eq!(comment.replies.len(), 1);
+        assert_eq!(comment.replies[0].author.as_deref(), Some("Ada"));
+
+        // Resolving closes the thread, replies included.
+        store.resolve(id).unwrap();
+        assert!(store.list(false).is_empty());
+        assert_eq!(store.list(true)[0].replies.len(), 1);
+    }
+
+    #[test]
+    fn memory_store_edit_delete_reopen() {
+        let mut store = MemoryCommentStore::new();
+        let id = store
+            .add(NewComment::new(
+                RelPath::new("test.rs"),
+                CommentContext::Worktree,
+                "tpyo".to_string(),
+                test_anchor(),
+            ))
+            .unwrap();
+
+        assert!(store.edit(id, "typo".to_string()).unwrap());
+        // Saving the same text again does not add a history entry.
+        assert!(store.edit(id, "typo".to_string()).unwrap());
+        let comment = store.get(id).unwrap();
+        assert_eq!(comment.message, "typo");
+        assert_eq!(comment.edits.len(), 1);
+        assert_eq!(comment.edits[0].message, "tpyo");
+
+        store.resolve(id).unwrap();
+        assert!(store.reopen(id).unwrap());
+        assert_eq!(store.get(id).unwrap().status, CommentStatus::Open);
+        assert!(store.get(id).unwrap().resolved_at_ms.is_none());
+
+        assert!(store.delete(id).unwrap());
+        assert!(store.get(id).is_none());
+        assert!(!store.delete(id).unwrap());
+        assert!(!store.edit(id, "gone".to_string()).unwrap());
+        assert!(!store.reopen(id).unwrap());
+    }
+
     #[test]
     fn memory_store_resolve_nonexistent() {
         let mut store = MemoryCommentStore::new();
@@ -329,12 +652,15 @@ mod tests {
             };
 
             let id = store
-                .add(
-                    RelPath::new("file.rs"),
-                    CommentContext::Worktree,
-                    "Review this".to_string(),
-                    test_anchor(),
-                )
+                .add(NewComment {
+                    path: RelPath::new("file.rs"),
+                    context: CommentContext::Worktree,
+                    author: None,
+                    message: "Review this".to_string(),
+                    kind: Some(CommentKind::Bug),
+                    suggestion: None,
+                    anchor: test_anchor(),
+                })
                 .unwrap();
 
             assert_eq!(id, 1);
@@ -349,7 +675,111 @@ mod tests {
             assert_eq!(state.next_id, 2);
             assert_eq!(state.comments.len(), 1);
             assert_eq!(state.comments[0].message, "Review this");
+            assert_eq!(state.comments[0].kind, Some(CommentKind::Bug));
             assert!(state.comments[0].context.matches(&CommentContext::Worktree));
         }
     }
+
+    #[test]
+    fn file_store_persists_edit_history() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let state_path = temp_dir.path().join(".quickdiff").join("comments.json");
+        let mut store = FileCommentStore {
+            state_path: state_path.clone(),
+            state: CommentsState::default(),
+        };
+        let id = store
+            .add(NewComment::new(
+                RelPath::new("file.rs"),
+                CommentContext::Worktree,
+                "first".to_string(),
+                test_anchor(),
+            ))
+            .unwrap();
+        store.edit(id, "second".to_string()).unwrap();
+
+        let content = std::fs::read_to_string(&state_path).unwrap();
+        let state: CommentsState = serde_json::from_str(&content).unwrap();
+        let comment = &state.comments[0];
+        assert_eq!(comment.message, "second");
+        assert_eq!(comment.edits[0].message, "first");
+        assert!(comment.edited_at_ms().is_some());
+    }
+
+    #[test]
+    fn file_store_merges_concurrent_writers() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let state_path = temp_dir.path().join(".quickdiff").join("comments.json");
+        let open = || FileCommentStore {
+            state_path: state_path.clone(),
+            state: FileCommentStore::read_state(&state_path).unwrap(),
+        };
+        let add = |store: &mut FileCommentStore, message: &str| {
+            store
+                .add(NewComment::new(
+                    RelPath::new("file.rs"),
+                    CommentContext::Worktree,
+                    message.to_string(),
+                    test_anchor(),
+                ))
+                .unwrap()
+        };
+
+        // Both stores were opened before either wrote.
+        let mut tui = open();
+        let mut agent = open();
+        let first = add(&mut tui, "from the tui");
+        let second = add(&mut agent, "from the agent");
+        assert_ne!(first, second);
+        assert!(agent.resolve(first).unwrap());
+
+        // The TUI's copy is stale, but its edit applies to what is on disk and
+        // keeps the agent's comment and resolve.
+        assert_eq!(tui.state.comments.len(), 1);
+        assert!(tui.edit(first, "edited later".to_string()).unwrap());
+
+        let state = FileCommentStore::read_state(&state_path).unwrap();
+        assert_eq!(state.comments.len(), 2);
+        assert_eq!(state.comments[0].message, "edited later");
+        assert_eq!(state.comments[0].status, CommentStatus::Resolved);
+        assert_eq!(state.comments[1].message, "from the agent");
+        assert_eq!(state.next_id, 3);
+    }
+
+    #[test]
+    fn file_store_locks_out_interleaved_threads() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let state_path = temp_dir.path().join("comments.json");
+
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let state_path = state_path.clone();
+                std::thread::spawn(move || {
+                    let mut store = FileCommentStore {
+                        state_path,
+                        state: CommentsState::default(),
+                    };
+                    for i in 0..10 {
+                        store
+                            .add(NewComment::new(
+                                RelPath::new("file.rs"),
+                                CommentContext::Worktree,
+                                format!("{}-{}", t, i),
+                                test_anchor(),
+                            ))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let state = FileCommentStore::read_state(&state_path).unwrap();
+        let mut ids: Vec<_> = state.comments.iter().map(|c| c.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 40);
+    }
 }
@@ -5,6 +5,8 @@ use std::sync::OnceLock;
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::{CommentBackend, RenameDetection};
+
 /// Resolved view preferences used by the app.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ViewPreferences {
@@ -41,6 +43,25 @@ pub struct QuickdiffConfig {
     /// Whether to show line numbers.
     #[serde(default, alias = "show_line_numbers")]
     pub line_numbers: Option<bool>,
+    /// Globs that are always excluded from file lists (repo config only).
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Minimum similarity percent for rename/copy detection in revision diffs.
+    #[serde(default)]
+    pub rename_threshold: Option<u8>,
+    /// Whether to detect copies as well as renames.
+    #[serde(default)]
+    pub detect_copies: Option<bool>,
+    /// Share viewed state and comments across all worktrees of a git repo.
+    #[serde(default)]
+    pub shared_state: Option<bool>,
+    /// Where comments are stored: `file` (default) or `git-ref`.
+    #[serde(default)]
+    pub comments_backend: Option<CommentBackend>,
+    /// Editor command for `o`, optionally a `{file}`/`{line}`/`{col}` template
+    /// (global config only, so a checkout cannot choose what gets run).
+    #[serde(default)]
+    pub editor: Option<String>,
 }
 
 impl QuickdiffConfig {
@@ -55,6 +76,21 @@ impl QuickdiffConfig {
             prefs.line_numbers = line_numbers;
         }
     }
+
+    fn merge_renames(&self, renames: &mut RenameDetection, warnings: &mut Vec<String>) {
+        if let Some(threshold) = self.rename_threshold {
+            if threshold > 100 {
+                warnings.push(format!(
+                    "rename_threshold must be 0-100, got {}; using 100",
+                    threshold
+                ));
+            }
+            renames.threshold = threshold.min(100);
+        }
+        if let Some(copies) = self.detect_copies {
+            renames.copies = copies;
+        }
+    }
 }
 
 /// CLI overrides that outrank config files.
@@ -69,6 +105,16 @@ pub struct ConfigOverrides {
 pub struct LoadedPreferences {
     /// Final merged view preferences.
     pub prefs: ViewPreferences,
+    /// Always-excluded path globs from the repo config.
+    pub excludes: Vec<String>,
+    /// Rename/copy detection settings (global, then repo config).
+    pub renames: RenameDetection,
+    /// Whether review state is shared across git worktrees.
+    pub shared_state: bool,
+    /// Where comments are stored.
+    pub comments_backend: CommentBackend,
+    /// Editor command from the global config.
+    pub editor: Option<String>,
     /// Non-fatal warnings encountered while loading config.
     pub warnings: Vec<String>,
 }
@@ -78,20 +124,46 @@ static CONFIG_DIR: OnceLock<PathBuf> = OnceLock::new();
 /// Resolve preferences from defaults + global config + repo config + CLI overrides.
 pub fn load_preferences(repo_root: &Path, overrides: &ConfigOverrides) -> LoadedPreferences {
     let mut prefs = ViewPreferences::default();
+    let mut excludes = Vec::new();
+    let mut renames = RenameDetection::default();
+    let mut shared_state = false;
+    let mut comments_backend = CommentBackend::default();
+    let mut editor = None;
     let mut warnings = Vec::new();
 
     if let Some(global) = load_config_file(&global_config_path(), "global", &mut warnings) {
         global.merge_into(&mut prefs);
+        global.merge_renames(&mut renames, &mut warnings);
+        shared_state = global.shared_state.unwrap_or(shared_state);
+        comments_backend = global.comments_backend.unwrap_or(comments_backend);
+        editor = global.editor;
     }
     if let Some(repo) = load_config_file(&repo_config_path(repo_root), "repo", &mut warnings) {
         repo.merge_into(&mut prefs);
+        repo.merge_renames(&mut renames, &mut warnings);
+        shared_state = repo.shared_state.unwrap_or(shared_state);
+        comments_backend = repo.comments_backend.unwrap_or(comments_backend);
+        excludes = repo.exclude.unwrap_or_default();
+        if repo.editor.is_some() {
+            warnings.push(
+                "editor is only read from the global config; ignoring repo setting".to_string(),
+            );
+        }
     }
 
     if let Some(theme) = &overrides.theme {
         prefs.theme = theme.clone();
     }
 
-    LoadedPreferences { prefs, warnings }
+    LoadedPreferences {
+        prefs,
+        excludes,
+        renames,
+        shared_state,
+        comments_backend,
+        editor,
+        warnings,
+    }
 }
 
 /// Persist the current preferences to the global config file.
@@ -101,11 +173,14 @@ pub fn save_global_preferences(prefs: &ViewPreferences) -> std::io::Result<()> {
         std::fs::create_dir_all(parent)?;
     }
 
+    // Keep settings that are not view preferences (e.g. rename detection).
+    let existing = load_config_file(&path, "global", &mut Vec::new()).unwrap_or_default();
     let config = QuickdiffConfig {
         theme: Some(prefs.theme.clone()),
-        layout: None,
         wrap_lines: Some(prefs.wrap_lines),
         line_numbers: Some(prefs.line_numbers),
+        exclude: None,
+        ..existing
     };
 
     let content = toml::to_string_pretty(&config)
@@ -186,6 +261,12 @@ mod tests {
             layout: None,
             wrap_lines: Some(true),
             line_numbers: None,
+            exclude: None,
+            rename_threshold: None,
+            detect_copies: None,
+            shared_state: None,
+            comments_backend: None,
+            editor: None,
         }
         .merge_into(&mut prefs);
         QuickdiffConfig {
@@ -193,6 +274,12 @@ mod tests {
             layout: None,
             wrap_lines: None,
             line_numbers: Some(false),
+            exclude: None,
+            rename_threshold: None,
+            detect_copies: None,
+            shared_state: None,
+            comments_backend: None,
+            editor: None,
         }
         .merge_into(&mut prefs);
         if let Some(theme) = Some("gruvbox".to_string()) {
@@ -215,4 +302,38 @@ mod tests {
         assert!(config.is_none());
         assert_eq!(warnings.len(), 1);
     }
+
+    #[test]
+    fn comments_backend_parses_kebab_case() {
+        let config: QuickdiffConfig = toml::from_str("comments_backend = \"git-ref\"").unwrap();
+        assert_eq!(config.comments_backend, Some(CommentBackend::GitRef));
+        assert!(toml::from_str::<QuickdiffConfig>("comments_backend = \"notes\"").is_err());
+    }
+
+    #[test]
+    fn repo_config_parses_exclude_globs() {
+        let config: QuickdiffConfig =
+            toml::from_str("exclude = [\"vendor/**\", \"*.lock\"]").unwrap();
+        assert_eq!(
+            config.exclude,
+            Some(vec!["vendor/**".to_string(), "*.lock".to_string()])
+        );
+    }
+
+    #[test]
+    fn rename_settings_merge_and_clamp() {
+        let mut renames = RenameDetection::default();
+        let mut warnings = Vec::new();
+        assert!(!renames.copies);
+        let config: QuickdiffConfig =
+            toml::from_str("rename_threshold = 70\ndetect_copies = true").unwrap();
+        config.merge_renames(&mut renames, &mut warnings);
+        assert_eq!(renames.threshold, 70);
+        assert!(renames.copies);
+
+        let config: QuickdiffConfig = toml::from_str("rename_threshold = 150").unwrap();
+        config.merge_renames(&mut renames, &mut warnings);
+        assert_eq!(renames.threshold, 100);
+        assert_eq!(warnings.len(), 1);
+    }
 }
@@ -0,0 +1,492 @@
+//! Remote control of a running TUI over a Unix socket.
+//!
+//! Each message is one line of JSON. Clients send [`ControlCommand`]s and get a
+//! [`ControlReply`] once the command has been applied; every connected client also
+//! receives the [`ControlEvent`]s the TUI emits.
+
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::RepoRoot;
+
+/// Environment variable naming the socket for `quickdiff ctl`.
+pub const CONTROL_SOCKET_ENV: &str = "QUICKDIFF_SOCKET";
+
+/// Default control socket for a checkout: `.quickdiff/control.sock`.
+///
+/// Per checkout rather than in the shared state dir, so TUIs in different
+/// worktrees do not collide.
+pub fn default_control_socket(repo: &RepoRoot) -> PathBuf {
+    repo.path().join(".quickdiff").join("control.sock")
+}
+
+/// A command for a running quickdiff.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Select a file and optionally scroll to a new-side line (1-based).
+    Open {
+        /// Repo-relative path.
+        path: String,
+        /// Line to show, 1-based.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        line: Option<usize>,
+    },
+    /// Refresh the file list and current diff.
+    Reload,
+    /// Show the working tree diff.
+    Worktree,
+    /// Diff against the merge base with a ref.
+    Base {
+        /// Base ref, e.g. `origin/main`.
+        base: String,
+    },
+    /// Show a single commit.
+    Commit {
+        /// Revision to show.
+        commit: String,
+    },
+    /// Show a `from..to` range.
+    Range {
+        /// Starting revision.
+        from: String,
+        /// Ending revision.
+        to: String,
+    },
+    /// Quit the TUI.
+    Quit,
+}
+
+/// An event emitted by the TUI to every connected client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ControlEvent {
+    /// A different file was selected.
+    FileSelected {
+        /// Repo-relative path.
+        path: String,
+    },
+    /// A comment was created in the TUI.
+    CommentAdded {
+        /// Comment id.
+        id: u64,
+        /// Repo-relative path.
+        path: String,
+    },
+    /// The diff source changed.
+    SourceChanged {
+        /// Display form of the new source.
+        source: String,
+    },
+}
+
+/// Answer to a [`ControlCommand`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControlReply {
+    /// Whether the command was applied.
+    pub ok: bool,
+    /// Why it was not.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ControlReply {
+    /// Reply for a command's outcome.
+    pub fn from_result(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => Self {
+                ok: true,
+                error: None,
+            },
+            Err(error) => Self {
+                ok: false,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Identifies a connected client, for replying to its commands.
+pub type ControlClientId = u64;
+
+/// Longest command line accepted from a client; longer ones drop the client.
+const MAX_LINE_LEN: usize = 64 * 1024;
+
+/// Most output queued for a client that is not reading; more drops the client.
+const MAX_PENDING_OUTPUT: usize = 1024 * 1024;
+
+struct ControlClient {
+    id: ControlClientId,
+    stream: UnixStream,
+    buffer: Vec<u8>,
+    /// Bytes not yet accepted by the non-blocking socket.
+    outgoing: Vec<u8>,
+}
+
+impl ControlClient {
+    /// Queue a message and write what the socket takes. Returns `false` once
+    /// the client should be dropped.
+    fn send<T: Serialize>(&mut self, message: &T) -> bool {
+        let Ok(mut line) = serde_json::to_vec(message) else {
+            return false;
+        };
+        line.push(b'\n');
+        self.outgoing.extend_from_slice(&line);
+        self.flush()
+    }
+
+    /// Write queued output until the socket would block. Returns `false` once
+    /// the client should be dropped.
+    fn flush(&mut self) -> bool {
+        while !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => return false,
+                Ok(n) => {
+                    self.outgoing.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => return false,
+            }
+        }
+        self.outgoing.len() <= MAX_PENDING_OUTPUT
+    }
+}
+
+/// Non-blocking control socket served from the TUI event loop.
+///
+/// The socket file is removed on drop.
+pub struct ControlServer {
+    path: PathBuf,
+    listener: UnixListener,
+    clients: Vec<ControlClient>,
+    next_id: ControlClientId,
+}
+
+impl ControlServer {
+    /// Listen on `path`, replacing a stale socket left by a crashed instance.
+    ///
+    /// Fails with `AddrInUse` if another instance is still listening there, and
+    /// with `AlreadyExists` if something other than a socket is in the way.
+    pub fn bind(path: &Path) -> io::Result<Self> {
+        match std::fs::symlink_metadata(path) {
+            Ok(meta) if !meta.file_type().is_socket() => {
+                return Err(io::Error::new(
+                    ErrorKind::AlreadyExists,
+                    format!("{} exists and is not a socket", path.display()),
+                ));
+            }
+            Ok(_) => {
+                if UnixStream::connect(path).is_ok() {
+                    return Err(io::Error::new(
+                        ErrorKind::AddrInUse,
+                        format!("{} is in use by another quickdiff", path.display()),
+                    ));
+                }
+                std::fs::remove_file(path)?;
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let listener = UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            listener,
+            clients: Vec::new(),
+            next_id: 1,
+        })
+    }
+
+    /// Socket path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Accept new clients, flush queued output and collect complete commands
+    /// without blocking.
+    ///
+    /// Malformed lines are answered with an error reply right away. Clients
+    /// sending a line longer than 64 KiB are dropped.
+    pub fn poll(&mut self) -> Vec<(ControlClientId, ControlCommand)> {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_ok() {
+                self.clients.push(ControlClient {
+                    id: self.next_id,
+                    stream,
+                    buffer: Vec::new(),
+                    outgoing: Vec::new(),
+                });
+                self.next_id += 1;
+            }
+        }
+
+        let mut commands = Vec::new();
+        self.clients.retain_mut(|client| {
+            if !client.flush() {
+                return false;
+            }
+            let mut chunk = [0u8; 4096];
+            // A client may write a command and hang up at once; still run the command.
+            let mut open = true;
+            loop {
+                match client.stream.read(&mut chunk) {
+                    Ok(0) => {
+                        open = false;
+                        break;
+                    }
+                    Ok(n) => {
+                        client.buffer.extend_from_slice(&chunk[..n]);
+                        let partial = client
+                            .buffer
+                            .iter()
+                            .rev()
+                            .position(|&b| b == b'\n')
+                            .unwrap_or(client.buffer.len());
+                        if partial > MAX_LINE_LEN {
+                            return false;
+                        }
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(_) => {
+                        open = false;
+                        break;
+                    }
+                }
+            }
+            while let Some(end) = client.buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = client.buffer.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                match serde_json::from_str(line) {
+                    Ok(command) => commands.push((client.id, command)),
+                    Err(e) => {
+                        let reply = ControlReply::from_result(Err(format!("bad command: {}", e)));
+                        if !client.send(&reply) {
+                            open = false;
+                        }
+                    }
+                }
+            }
+            open && client.buffer.len() <= MAX_LINE_LEN
+        });
+        commands
+    }
+
+    /// Send the reply for a command to the client that sent it.
+    pub fn reply(&mut self, client: ControlClientId, reply: &ControlReply) {
+        self.clients.retain_mut(|c| c.id != client || c.send(reply));
+    }
+
+    /// Send an event to every connected client, dropping those that went away
+    /// or stopped reading.
+    pub fn emit(&mut self, event: &ControlEvent) {
+        self.clients.retain_mut(|c| c.send(event));
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        let _removed = std::fs::remove_file(&self.path);
+    }
+}
+
+fn write_line<T: Serialize>(stream: &mut UnixStream, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    stream.write_all(&line)
+}
+
+/// Send a command to the instance listening on `path` and wait for its reply.
+///
+/// Events that arrive before the reply are skipped.
+pub fn send_control_command(
+    path: &Path,
+    command: &ControlCommand,
+    timeout: Duration,
+) -> io::Result<ControlReply> {
+    let mut stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(timeout))?;
+    write_line(&mut stream, command)?;
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "quickdiff closed the connection without replying",
+            ));
+        }
+        if let Ok(reply) = serde_json::from_str::<ControlReply>(&line) {
+            return Ok(reply);
+        }
+    }
+}
+
+/// Connect to the instance listening on `path` and read its events line by line.
+pub fn subscribe_control_events(
+    path: &Path,
+) -> io::Result<impl Iterator<Item = io::Result<String>>> {
+    Ok(BufReader::new(UnixStream::connect(path)?).lines())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn command_wire_format() {
+        let open = ControlCommand::Open {
+            path: "src/foo.rs".to_string(),
+            line: Some(120),
+        };
+        let json = serde_json::to_string(&open).unwrap();
+        assert_eq!(json, r#"{"command":"open","path":"src/foo.rs","line":120}"#);
+        let base: ControlCommand =
+            serde_json::from_str(r#"{"command":"base","base":"origin/main"}"#).unwrap();
+        assert_eq!(
+            base,
+            ControlCommand::Base {
+                base: "origin/main".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn server_round_trips_commands_and_events() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("control.sock");
+        let mut server = ControlServer::bind(&path).unwrap();
+        assert!(ControlServer::bind(&path).is_err());
+
+        let events = std::thread::spawn({
+            let path = path.clone();
+            move || {
+                let mut lines = subscribe_control_events(&path).unwrap();
+                lines.next().unwrap().unwrap()
+            }
+        });
+        let client = std::thread::spawn({
+            let path = path.clone();
+            move || send_control_command(&path, &ControlCommand::Reload, Duration::from_secs(10))
+        });
+
+        let mut received = Vec::new();
+        for _ in 0..500 {
+            received.extend(server.poll());
+            if !received.is_empty() && server.clients.len() == 2 {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(received.len(), 1);
+        let (id, command) = received.remove(0);
+        assert_eq!(command, ControlCommand::Reload);
+
+        server.emit(&ControlEvent::FileSelected {
+            path: "a.rs".to_string(),
+        });
+        server.reply(id, &ControlReply::from_result(Ok(())));
+
+        assert!(client.join().unwrap().unwrap().ok);
+        assert_eq!(
+            events.join().unwrap(),
+            r#"{"event":"file_selected","path":"a.rs"}"#
+        );
+
+        drop(server);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn bind_refuses_to_replace_non_sockets() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("control.sock");
+        std::fs::write(&path, "keep me").unwrap();
+        let err = ControlServer::bind(&path).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+
+        let link = dir.path().join("link.sock");
+        std::os::unix::fs::symlink(&path, &link).unwrap();
+        assert!(ControlServer::bind(&link).is_err());
+        assert!(std::fs::symlink_metadata(&link).is_ok());
+    }
+
+    #[test]
+    fn drops_clients_sending_overlong_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("control.sock");
+        let mut server = ControlServer::bind(&path).unwrap();
+
+        let mut stream = UnixStream::connect(&path).unwrap();
+        stream.write_all(&vec![b'x'; MAX_LINE_LEN + 1]).unwrap();
+        for _ in 0..500 {
+            assert!(server.poll().is_empty());
+            if server.next_id > 1 && server.clients.is_empty() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(server.next_id, 2);
+        assert!(server.clients.is_empty());
+    }
+
+    #[test]
+    fn queues_output_for_slow_readers() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("control.sock");
+        let mut server = ControlServer::bind(&path).unwrap();
+
+        let (start, started) = std::sync::mpsc::channel::<()>();
+        let reader = std::thread::spawn({
+            let path = path.clone();
+            move || {
+                let stream = UnixStream::connect(&path).unwrap();
+                started.recv().unwrap();
+                BufReader::new(stream)
+                    .lines()
+                    .map(|line| line.unwrap())
+                    .take(15000)
+                    .last()
+            }
+        });
+        while server.clients.is_empty() {
+            server.poll();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        // More than the socket buffer holds while the client is not reading.
+        for i in 0..15000 {
+            server.emit(&ControlEvent::FileSelected {
+                path: format!("src/file_{i}.rs"),
+            });
+        }
+        assert_eq!(server.clients.len(), 1);
+        assert!(!server.clients[0].outgoing.is_empty());
+
+        start.send(()).unwrap();
+        while !reader.is_finished() {
+            server.poll();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(
+            reader.join().unwrap().as_deref(),
+            Some(r#"{"event":"file_selected","path":"src/file_14999.rs"}"#)
+        );
+    }
+}
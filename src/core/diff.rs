@@ -572,19 +572,39 @@ fn make_hunk(rows: &[RenderRow], start: usize, end: usize) -> Hunk {
     }
 }
 
-fn digest_changed_rows(rows: &[RenderRow]) -> String {
-    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
-    const FNV_PRIME: u64 = 0x100000001b3;
+/// Incremental FNV-1a 64-bit hash, used for hunk digests and line fingerprints.
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    pub(crate) fn new() -> Self {
+        Self(Self::OFFSET)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// The hash as 16 lowercase hex digits.
+    pub(crate) fn finish_hex(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
 
-    let mut hash = FNV_OFFSET;
+fn digest_changed_rows(rows: &[RenderRow]) -> String {
+    let mut hash = Fnv1a::new();
     for row in rows {
         match row.kind {
             ChangeKind::Delete | ChangeKind::Replace => {
                 if let Some(ref old) = row.old {
-                    for byte in b"-".iter().chain(old.content.as_bytes()).chain(b"\n") {
-                        hash ^= *byte as u64;
-                        hash = hash.wrapping_mul(FNV_PRIME);
-                    }
+                    hash.write(b"-");
+                    hash.write(old.content.as_bytes());
+                    hash.write(b"\n");
                 }
             }
             _ => {}
@@ -592,23 +612,30 @@ fn digest_changed_rows(rows: &[RenderRow]) -> String {
         match row.kind {
             ChangeKind::Insert | ChangeKind::Replace => {
                 if let Some(ref new) = row.new {
-                    for byte in b"+".iter().chain(new.content.as_bytes()).chain(b"\n") {
-                        hash ^= *byte as u64;
-                        hash = hash.wrapping_mul(FNV_PRIME);
-                    }
+                    hash.write(b"+");
+                    hash.write(new.content.as_bytes());
+                    hash.write(b"\n");
                 }
             }
             _ => {}
         }
     }
 
-    format!("{:016x}", hash)
+    hash.finish_hex()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn fnv1a_matches_reference_vectors() {
+        assert_eq!(Fnv1a::new().finish_hex(), "cbf29ce484222325");
+        let mut hash = Fnv1a::new();
+        hash.write(b"a");
+        assert_eq!(hash.finish_hex(), "af63dc4c8601ec8c");
+    }
+
     // Property-based tests
     mod proptest_tests {
         use super::*;
@@ -0,0 +1,114 @@
+//! Editor command lines that open a file at a line and column.
+
+use std::path::Path;
+
+/// Where to open the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditorTarget<'a> {
+    /// File to open.
+    pub path: &'a Path,
+    /// Line, 1-based.
+    pub line: usize,
+    /// Column, 1-based.
+    pub column: usize,
+}
+
+/// Build the full command line for `command` (program plus its own args).
+///
+/// A command containing `{file}` is a template: `{file}`, `{line}` and `{col}`
+/// are substituted in every argument. Otherwise the position is passed the way
+/// the editor expects it, recognised by program name; unknown editors get the
+/// file only.
+pub fn editor_invocation(command: &[String], target: &EditorTarget<'_>) -> Vec<String> {
+    let file = target.path.to_string_lossy();
+    let (line, col) = (target.line, target.column);
+
+    if command.iter().any(|part| part.contains("{file}")) {
+        return command
+            .iter()
+            .map(|part| {
+                part.replace("{file}", &file)
+                    .replace("{line}", &line.to_string())
+                    .replace("{col}", &col.to_string())
+            })
+            .collect();
+    }
+
+    let mut parts = command.to_vec();
+    let program = command
+        .first()
+        .and_then(|p| Path::new(p).file_stem())
+        .map(|p| p.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match program.as_str() {
+        "vim" | "nvim" | "gvim" | "mvim" | "vimx" => {
+            parts.push(format!("+call cursor({}, {})", line, col));
+            parts.push(file.into_owned());
+        }
+        "vi" | "nvi" | "elvis" | "ex" => {
+            parts.push(format!("+{}", line));
+            parts.push(file.into_owned());
+        }
+        "nano" => {
+            parts.push(format!("+{},{}", line, col));
+            parts.push(file.into_owned());
+        }
+        "emacs" | "emacsclient" | "kak" | "micro" => {
+            parts.push(format!("+{}:{}", line, col));
+            parts.push(file.into_owned());
+        }
+        "code" | "code-insiders" | "codium" | "vscodium" | "cursor" | "windsurf" => {
+            parts.push("--goto".to_string());
+            parts.push(format!("{}:{}:{}", file, line, col));
+        }
+        "hx" | "helix" | "subl" | "sublime_text" | "zed" | "zeditor" => {
+            parts.push(format!("{}:{}:{}", file, line, col));
+        }
+        _ => parts.push(file.into_owned()),
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invoke(command: &str) -> Vec<String> {
+        let command: Vec<String> = command.split(' ').map(str::to_string).collect();
+        editor_invocation(
+            &command,
+            &EditorTarget {
+                path: Path::new("/repo/src/lib.rs"),
+                line: 12,
+                column: 5,
+            },
+        )
+    }
+
+    #[test]
+    fn known_editors_get_their_position_syntax() {
+        assert_eq!(invoke("vi"), ["vi", "+12", "/repo/src/lib.rs"]);
+        assert_eq!(
+            invoke("/usr/bin/nvim"),
+            ["/usr/bin/nvim", "+call cursor(12, 5)", "/repo/src/lib.rs"]
+        );
+        assert_eq!(
+            invoke("code --wait"),
+            ["code", "--wait", "--goto", "/repo/src/lib.rs:12:5"]
+        );
+        assert_eq!(
+            invoke("emacsclient -t"),
+            ["emacsclient", "-t", "+12:5", "/repo/src/lib.rs"]
+        );
+        assert_eq!(invoke("hx"), ["hx", "/repo/src/lib.rs:12:5"]);
+        assert_eq!(invoke("ed"), ["ed", "/repo/src/lib.rs"]);
+    }
+
+    #[test]
+    fn templates_substitute_placeholders() {
+        assert_eq!(
+            invoke("idea --line {line} --column {col} {file}"),
+            ["idea", "--line", "12", "--column", "5", "/repo/src/lib.rs"]
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! Static analysis findings (SARIF, cargo diagnostics) to import as comments.
+
+use std::path::Path;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::core::{CommentKind, RelPath};
+
+/// Errors from parsing analysis output.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum FindingsError {
+    /// The input is not valid JSON of the expected shape.
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// One diagnostic at a location in a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// File path as reported by the tool (relative, absolute or a `file://` URI).
+    pub path: String,
+    /// First line (0-indexed).
+    pub start_line: usize,
+    /// Last line (0-indexed, inclusive).
+    pub end_line: usize,
+    /// Diagnostic text.
+    pub message: String,
+    /// Rule or lint identifier, e.g. `clippy::needless_return`.
+    pub rule_id: Option<String>,
+    /// Severity mapped onto comment kinds.
+    pub kind: CommentKind,
+}
+
+impl Finding {
+    /// Comment text: the rule id followed by the message.
+    pub fn comment_message(&self) -> String {
+        match &self.rule_id {
+            Some(rule) => format!("{}: {}", rule, self.message.trim()),
+            None => self.message.trim().to_string(),
+        }
+    }
+
+    /// The path relative to `root`, or `None` if it lies outside it.
+    pub fn relative_path(&self, root: &Path) -> Option<RelPath> {
+        let path = self.path.strip_prefix("file://").unwrap_or(&self.path);
+        let path = percent_decode(path);
+        let path = Path::new(&path);
+        let relative = if path.is_absolute() {
+            path.strip_prefix(root)
+                .ok()
+                .map(Path::to_path_buf)
+                .or_else(|| {
+                    let canonical = path.canonicalize().ok()?;
+                    canonical.strip_prefix(root).ok().map(Path::to_path_buf)
+                })?
+        } else {
+            path.strip_prefix("./").unwrap_or(path).to_path_buf()
+        };
+        RelPath::try_new(relative.to_str()?.replace('\\', "/")).ok()
+    }
+}
+
+/// Severity of a SARIF level or rustc diagnostic level.
+fn kind_for_level(level: &str) -> CommentKind {
+    match level {
+        "error" => CommentKind::Bug,
+        "warning" => CommentKind::Warning,
+        _ => CommentKind::Suggestion,
+    }
+}
+
+/// Parse a SARIF 2.1 log. Results without a file location are skipped.
+pub fn parse_sarif(input: &str) -> Result<Vec<Finding>, FindingsError> {
+    let log: SarifLog = serde_json::from_str(input)?;
+    let mut findings = Vec::new();
+    for run in log.runs {
+        for result in run.results {
+            let rule_id = result.rule_id.or(result.rule.and_then(|r| r.id));
+            // Results may omit the level and inherit it from the rule.
+            let level = result.level.or_else(|| {
+                let rule = run
+                    .tool
+                    .driver
+                    .rules
+                    .iter()
+                    .find(|r| Some(&r.id) == rule_id.as_ref())?;
+                rule.default_configuration.as_ref()?.level.clone()
+            });
+            let kind = kind_for_level(level.as_deref().unwrap_or("warning"));
+            let Some(location) = result
+                .locations
+                .into_iter()
+                .find_map(|l| l.physical_location)
+            else {
+                continue;
+            };
+            let (Some(uri), Some(region)) = (location.artifact_location.uri, location.region)
+            else {
+                continue;
+            };
+            let Some(start) = region.start_line.filter(|&line| line > 0) else {
+                continue;
+            };
+            let end = region.end_line.unwrap_or(start).max(start);
+            findings.push(Finding {
+                path: uri,
+                start_line: start - 1,
+                end_line: end - 1,
+                message: result.message.text.unwrap_or_default(),
+                rule_id: rule_id.clone(),
+                kind,
+            });
+        }
+    }
+    Ok(findings)
+}
+
+/// Parse `cargo --message-format=json` output (one JSON object per line).
+///
+/// Only `compiler-message` lines with a primary span become findings; other lines,
+/// including non-JSON ones, are ignored.
+pub fn parse_cargo_diagnostics(input: &str) -> Result<Vec<Finding>, FindingsError> {
+    let mut findings = Vec::new();
+    for line in input.lines() {
+        let line = line.trim();
+        if !line.starts_with('{') {
+            continue;
+        }
+        let Ok(message) = serde_json::from_str::<CargoMessage>(line) else {
+            continue;
+        };
+        if message.reason != "compiler-message" {
+            continue;
+        }
+        let Some(diagnostic) = message.message else {
+            continue;
+        };
+        let Some(span) = diagnostic.spans.iter().find(|s| s.is_primary) else {
+            continue;
+        };
+        if span.line_start == 0 {
+            continue;
+        }
+        findings.push(Finding {
+            path: span.file_name.clone(),
+            start_line: span.line_start - 1,
+            end_line: span.line_end.max(span.line_start) - 1,
+            message: diagnostic.message,
+            rule_id: diagnostic.code.map(|c| c.code),
+            kind: kind_for_level(&diagnostic.level),
+        });
+    }
+    Ok(findings)
+}
+
+/// Decode `%XX` escapes in a URI path.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(hex) = s.get(i + 1..i + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[derive(Deserialize)]
+struct SarifLog {
+    #[serde(default)]
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Deserialize)]
+struct SarifRun {
+    tool: SarifTool,
+    #[serde(default)]
+    results: Vec<SarifResult>,
+}
+
+#[derive(Deserialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Deserialize)]
+struct SarifDriver {
+    #[serde(default)]
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    default_configuration: Option<SarifRuleConfiguration>,
+}
+
+#[derive(Deserialize)]
+struct SarifRuleConfiguration {
+    level: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: Option<String>,
+    rule: Option<SarifRuleReference>,
+    level: Option<String>,
+    message: SarifMessage,
+    #[serde(default)]
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Deserialize)]
+struct SarifRuleReference {
+    id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SarifMessage {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: Option<SarifPhysicalLocation>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: Option<SarifRegion>,
+}
+
+#[derive(Deserialize)]
+struct SarifArtifactLocation {
+    uri: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: Option<usize>,
+    end_line: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<CargoDiagnostic>,
+}
+
+#[derive(Deserialize)]
+struct CargoDiagnostic {
+    message: String,
+    code: Option<CargoCode>,
+    level: String,
+    #[serde(default)]
+    spans: Vec<CargoSpan>,
+}
+
+#[derive(Deserialize)]
+struct CargoCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct CargoSpan {
+    file_name: String,
+    line_start: usize,
+    line_end: usize,
+    is_primary: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sarif_results_with_rule_default_levels() {
+        let sarif = r#"{
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {"driver": {"name": "semgrep", "rules": [
+                    {"id": "no-unwrap", "defaultConfiguration": {"level": "error"}}
+                ]}},
+                "results": [
+                    {
+                        "ruleId": "no-unwrap",
+                        "message": {"text": "avoid unwrap"},
+                        "locations": [{"physicalLocation": {
+                            "artifactLocation": {"uri": "src/lib.rs"},
+                            "region": {"startLine": 3, "endLine": 4}
+                        }}]
+                    },
+                    {
+                        "ruleId": "style",
+                        "level": "note",
+                        "message": {"text": "consider"},
+                        "locations": [{"physicalLocation": {
+                            "artifactLocation": {"uri": "file:///repo/src/my%20file.rs"},
+                            "region": {"startLine": 1}
+                        }}]
+                    },
+                    {"ruleId": "nowhere", "message": {"text": "no location"}}
+                ]
+            }]
+        }"#;
+        let findings = parse_sarif(sarif).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].kind, CommentKind::Bug);
+        assert_eq!((findings[0].start_line, findings[0].end_line), (2, 3));
+        assert_eq!(findings[0].comment_message(), "no-unwrap: avoid unwrap");
+        assert_eq!(findings[1].kind, CommentKind::Suggestion);
+        assert_eq!(
+            findings[1]
+                .relative_path(Path::new("/repo"))
+                .unwrap()
+                .as_str(),
+            "src/my file.rs"
+        );
+    }
+
+    #[test]
+    fn parses_cargo_compiler_messages() {
+        let output = concat!(
+            r#"{"reason":"compiler-artifact","target":{}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"message":"unneeded `return` statement","code":{"code":"clippy::needless_return"},"level":"warning","spans":[{"file_name":"src/lib.rs","line_start":5,"line_end":5,"is_primary":true}]}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"message":"1 warning emitted","code":null,"level":"warning","spans":[]}}"#,
+            "\n",
+            "   Compiling quickdiff v0.8.2\n",
+        );
+        let findings = parse_cargo_diagnostics(output).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].path, "src/lib.rs");
+        assert_eq!(findings[0].start_line, 4);
+        assert_eq!(findings[0].kind, CommentKind::Warning);
+        assert_eq!(
+            findings[0].comment_message(),
+            "clippy::needless_return: unneeded `return` statement"
+        );
+    }
+}
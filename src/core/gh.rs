@@ -1,11 +1,14 @@
 //! GitHub CLI (`gh`) wrapper for PR operations.
 
-use std::io::Read;
+use std::io::{Read, Write};
 use std::process::{Command, Stdio};
 
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
 use thiserror::Error;
 
+use crate::core::{PullRequestReview, RelPath, ReviewSide};
+
 /// Maximum diff size to load (50 MiB). Matches repo::MAX_FILE_SIZE.
 pub const MAX_DIFF_SIZE: usize = 50 * 1024 * 1024;
 
@@ -25,6 +28,9 @@ pub enum GhError {
     /// I/O error running command.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+    /// A git command or repository lookup failed.
+    #[error("git error: {0}")]
+    GitError(String),
     /// Diff exceeds maximum allowed size.
     #[error("PR diff too large: exceeded {max} bytes")]
     DiffTooLarge {
@@ -36,7 +42,7 @@ pub enum GhError {
 /// Filter for PR listing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum PRFilter {
-    /// All open PRs.
+    /// All PRs.
     #[default]
     All,
     /// PRs authored by current user.
@@ -45,6 +51,232 @@ pub enum PRFilter {
     ReviewRequested,
 }
 
+/// PR state to list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PRState {
+    /// Open PRs, drafts included.
+    #[default]
+    Open,
+    /// Open draft PRs only.
+    Draft,
+    /// Merged PRs.
+    Merged,
+    /// PRs closed without being merged.
+    Closed,
+}
+
+impl PRState {
+    /// The state after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Open => Self::Draft,
+            Self::Draft => Self::Merged,
+            Self::Merged => Self::Closed,
+            Self::Closed => Self::Open,
+        }
+    }
+
+    /// Lowercase name for display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Draft => "draft",
+            Self::Merged => "merged",
+            Self::Closed => "closed",
+        }
+    }
+}
+
+/// Order of the PR list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PRSort {
+    /// Most recently created first.
+    #[default]
+    Newest,
+    /// Least recently created first.
+    Oldest,
+    /// Most recently updated first.
+    RecentlyUpdated,
+    /// Least recently updated first.
+    LeastRecentlyUpdated,
+}
+
+impl PRSort {
+    /// The order after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Newest => Self::Oldest,
+            Self::Oldest => Self::RecentlyUpdated,
+            Self::RecentlyUpdated => Self::LeastRecentlyUpdated,
+            Self::LeastRecentlyUpdated => Self::Newest,
+        }
+    }
+
+    /// Short name for display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Newest => "newest",
+            Self::Oldest => "oldest",
+            Self::RecentlyUpdated => "recently updated",
+            Self::LeastRecentlyUpdated => "least recently updated",
+        }
+    }
+
+    /// GitHub search qualifier for this order.
+    fn qualifier(self) -> &'static str {
+        match self {
+            Self::Newest => "sort:created-desc",
+            Self::Oldest => "sort:created-asc",
+            Self::RecentlyUpdated => "sort:updated-desc",
+            Self::LeastRecentlyUpdated => "sort:updated-asc",
+        }
+    }
+}
+
+/// Number of PRs the picker fetches per page.
+pub const PR_PAGE_SIZE: usize = 30;
+
+/// Which PRs [`list_prs`] asks `gh` for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PRQuery {
+    /// Whose PRs to list.
+    pub filter: PRFilter,
+    /// PR state.
+    pub state: PRState,
+    /// Free text passed to `gh --search`.
+    pub search: String,
+    /// Only PRs with this label.
+    pub label: Option<String>,
+    /// Only PRs by this author; takes precedence over [`PRFilter::Mine`].
+    pub author: Option<String>,
+    /// List order.
+    pub sort: PRSort,
+    /// Maximum number of PRs to fetch.
+    pub limit: usize,
+}
+
+impl Default for PRQuery {
+    fn default() -> Self {
+        Self {
+            filter: PRFilter::default(),
+            state: PRState::default(),
+            search: String::new(),
+            label: None,
+            author: None,
+            sort: PRSort::default(),
+            limit: PR_PAGE_SIZE,
+        }
+    }
+}
+
+impl PRQuery {
+    /// Set search text, label and author from one line of input.
+    ///
+    /// `label:NAME` and `author:LOGIN` words become the label and author
+    /// filters; the remaining words are the search text.
+    pub fn set_search_input(&mut self, input: &str) {
+        self.label = None;
+        self.author = None;
+        let mut words = Vec::new();
+        for word in input.split_whitespace() {
+            if let Some(label) = word.strip_prefix("label:").filter(|l| !l.is_empty()) {
+                self.label = Some(label.to_string());
+            } else if let Some(author) = word.strip_prefix("author:").filter(|a| !a.is_empty()) {
+                self.author = Some(author.to_string());
+            } else {
+                words.push(word);
+            }
+        }
+        self.search = words.join(" ");
+    }
+
+    /// The input line [`Self::set_search_input`] turns into this query's
+    /// search text, label and author.
+    pub fn search_input(&self) -> String {
+        let mut words = Vec::new();
+        if let Some(label) = &self.label {
+            words.push(format!("label:{label}"));
+        }
+        if let Some(author) = &self.author {
+            words.push(format!("author:{author}"));
+        }
+        if !self.search.is_empty() {
+            words.push(self.search.clone());
+        }
+        words.join(" ")
+    }
+
+    /// Arguments for `gh pr list`.
+    fn args(&self) -> Vec<String> {
+        let mut args: Vec<String> = vec![
+            "pr".into(),
+            "list".into(),
+            "--json".into(),
+            PR_LIST_FIELDS.into(),
+            "--limit".into(),
+            self.limit.to_string(),
+            "--state".into(),
+        ];
+        let mut search = Vec::new();
+        match self.state {
+            PRState::Open => args.push("open".into()),
+            PRState::Draft => {
+                args.push("open".into());
+                args.push("--draft".into());
+            }
+            PRState::Merged => args.push("merged".into()),
+            PRState::Closed => {
+                // `gh` counts merged PRs as closed.
+                args.push("closed".into());
+                search.push("is:unmerged");
+            }
+        }
+
+        if let Some(author) = &self.author {
+            args.push("--author".into());
+            args.push(author.clone());
+        } else if self.filter == PRFilter::Mine {
+            args.push("--author".into());
+            args.push("@me".into());
+        }
+        if let Some(label) = &self.label {
+            args.push("--label".into());
+            args.push(label.clone());
+        }
+
+        if self.filter == PRFilter::ReviewRequested {
+            search.push("review-requested:@me");
+        }
+        if !self.search.is_empty() {
+            search.push(&self.search);
+        }
+        // Searches default to best match, so any search names its order.
+        if !search.is_empty() || self.sort != PRSort::Newest {
+            search.push(self.sort.qualifier());
+        }
+        if !search.is_empty() {
+            args.push("--search".into());
+            args.push(search.join(" "));
+        }
+        args
+    }
+}
+
+/// Fields requested from `gh pr list`.
+const PR_LIST_FIELDS: &str = "number,title,headRefName,baseRefName,author,additions,deletions,\
+changedFiles,isDraft,reviewDecision,statusCheckRollup";
+
+/// Review decision on a PR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewDecision {
+    /// Approved by the required reviewers.
+    Approved,
+    /// A reviewer requested changes.
+    ChangesRequested,
+    /// Still needs an approving review.
+    ReviewRequired,
+}
+
 /// PR author info.
 #[derive(Debug, Clone, Deserialize)]
 pub struct PRAuthor {
@@ -75,6 +307,323 @@ pub struct PullRequest {
     /// Whether this is a draft PR.
     #[serde(default)]
     pub is_draft: bool,
+    /// Review decision, if reviews are required or one was given.
+    #[serde(default, deserialize_with = "deserialize_review_decision")]
+    pub review_decision: Option<ReviewDecision>,
+    /// Combined outcome of the PR's CI checks; `None` when it has none.
+    #[serde(
+        default,
+        rename = "statusCheckRollup",
+        deserialize_with = "deserialize_check_rollup"
+    )]
+    pub checks: Option<CheckBucket>,
+}
+
+fn deserialize_review_decision<'de, D>(d: D) -> Result<Option<ReviewDecision>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<String>::deserialize(d)?.as_deref() {
+        Some("APPROVED") => Some(ReviewDecision::Approved),
+        Some("CHANGES_REQUESTED") => Some(ReviewDecision::ChangesRequested),
+        Some("REVIEW_REQUIRED") => Some(ReviewDecision::ReviewRequired),
+        _ => None,
+    })
+}
+
+/// One entry of `statusCheckRollup`: a check run (`status`, `conclusion`)
+/// or a commit status (`state`).
+#[derive(Deserialize)]
+struct RollupJson {
+    status: Option<String>,
+    conclusion: Option<String>,
+    state: Option<String>,
+}
+
+impl RollupJson {
+    fn bucket(&self) -> CheckBucket {
+        if let Some(state) = self.state.as_deref().filter(|s| !s.is_empty()) {
+            return match state {
+                "SUCCESS" => CheckBucket::Pass,
+                "FAILURE" | "ERROR" => CheckBucket::Fail,
+                _ => CheckBucket::Pending,
+            };
+        }
+        if self.status.as_deref() != Some("COMPLETED") {
+            return CheckBucket::Pending;
+        }
+        match self.conclusion.as_deref() {
+            Some("SUCCESS") => CheckBucket::Pass,
+            Some("NEUTRAL" | "SKIPPED" | "STALE") => CheckBucket::Skipping,
+            Some("CANCELLED") => CheckBucket::Cancel,
+            _ => CheckBucket::Fail,
+        }
+    }
+}
+
+/// Fold `statusCheckRollup` into one outcome: any failure fails, then any
+/// pending check is pending.
+fn deserialize_check_rollup<'de, D>(d: D) -> Result<Option<CheckBucket>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let rollup = Option::<Vec<RollupJson>>::deserialize(d)?.unwrap_or_default();
+    let buckets: Vec<CheckBucket> = rollup.iter().map(RollupJson::bucket).collect();
+    Ok(if buckets.is_empty() {
+        None
+    } else if buckets
+        .iter()
+        .any(|b| matches!(b, CheckBucket::Fail | CheckBucket::Cancel))
+    {
+        Some(CheckBucket::Fail)
+    } else if buckets.contains(&CheckBucket::Pending) {
+        Some(CheckBucket::Pending)
+    } else if buckets.contains(&CheckBucket::Pass) {
+        Some(CheckBucket::Pass)
+    } else {
+        Some(CheckBucket::Skipping)
+    })
+}
+
+/// Commits a PR's diff is taken between, present in the local repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrRevisions {
+    /// Merge base of the PR head and its base branch.
+    pub base: String,
+    /// Head commit of the PR.
+    pub head: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrRefs {
+    head_ref_oid: String,
+    base_ref_oid: String,
+    base_ref_name: String,
+    url: String,
+}
+
+/// A review thread on a PR, as GitHub reports it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewThread {
+    /// File the thread is on.
+    pub path: RelPath,
+    /// Side of the diff the thread's lines are on.
+    pub side: ReviewSide,
+    /// Last line of the thread in the current diff; `None` once outdated.
+    pub line: Option<u32>,
+    /// First line of a multi-line thread.
+    pub start_line: Option<u32>,
+    /// Whether the thread was resolved.
+    pub is_resolved: bool,
+    /// Whether later pushes moved the thread's lines out of the diff.
+    pub is_outdated: bool,
+    /// Comments in the thread, oldest first.
+    pub comments: Vec<ReviewThreadComment>,
+}
+
+/// One comment in a [`ReviewThread`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewThreadComment {
+    /// GitHub login of the author, if the account still exists.
+    pub author: Option<String>,
+    /// Comment body (markdown).
+    pub body: String,
+}
+
+/// Query for a page of a PR's review threads; `{owner}` and `{repo}` are
+/// filled in by `gh`.
+const REVIEW_THREADS_QUERY: &str =
+    "query($owner: String!, $repo: String!, $number: Int!, $after: String) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100, after: $after) {
+        pageInfo { hasNextPage endCursor }
+        nodes {
+          id path line startLine diffSide isResolved isOutdated
+          comments(first: 100) {
+            pageInfo { hasNextPage endCursor }
+            nodes { author { login } body }
+          }
+        }
+      }
+    }
+  }
+}";
+
+/// Query for the comments of a review thread past its first page.
+const THREAD_COMMENTS_QUERY: &str = "query($id: ID!, $after: String) {
+  node(id: $id) {
+    ... on PullRequestReviewThread {
+      comments(first: 100, after: $after) {
+        pageInfo { hasNextPage endCursor }
+        nodes { author { login } body }
+      }
+    }
+  }
+}";
+
+/// One page of a GraphQL connection.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphQlNodes<T> {
+    nodes: Vec<T>,
+    #[serde(default)]
+    page_info: PageInfo,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+impl PageInfo {
+    /// Cursor to request the next page with, if there is one.
+    fn next_cursor(&self) -> Option<String> {
+        self.end_cursor.clone().filter(|_| self.has_next_page)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadNode {
+    #[serde(default)]
+    id: String,
+    path: String,
+    line: Option<u32>,
+    start_line: Option<u32>,
+    diff_side: ReviewSide,
+    is_resolved: bool,
+    is_outdated: bool,
+    comments: GraphQlNodes<ThreadCommentNode>,
+}
+
+#[derive(Deserialize)]
+struct ThreadCommentNode {
+    author: Option<PRAuthor>,
+    body: String,
+}
+
+/// Outcome of a CI check, as `gh pr checks` groups them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckBucket {
+    /// Succeeded.
+    Pass,
+    /// Failed or errored.
+    Fail,
+    /// Skipped or neutral.
+    Skipping,
+    /// Cancelled.
+    Cancel,
+    /// Queued or running (and any state `gh` adds later).
+    #[serde(other)]
+    Pending,
+}
+
+/// A CI check on a PR.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PrCheck {
+    /// Check name.
+    pub name: String,
+    /// Workflow the check belongs to, if any.
+    #[serde(default)]
+    pub workflow: String,
+    /// Outcome.
+    pub bucket: CheckBucket,
+    /// Link to the check run.
+    #[serde(default)]
+    pub link: String,
+}
+
+/// Counts of a PR's checks by outcome.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChecksSummary {
+    /// Checks that passed.
+    pub passed: usize,
+    /// Checks that failed or were cancelled.
+    pub failed: usize,
+    /// Checks still queued or running.
+    pub pending: usize,
+    /// Skipped checks.
+    pub skipped: usize,
+}
+
+impl ChecksSummary {
+    /// Tally a list of checks.
+    pub fn of(checks: &[PrCheck]) -> Self {
+        let mut summary = Self::default();
+        for check in checks {
+            match check.bucket {
+                CheckBucket::Pass => summary.passed += 1,
+                CheckBucket::Fail | CheckBucket::Cancel => summary.failed += 1,
+                CheckBucket::Pending => summary.pending += 1,
+                CheckBucket::Skipping => summary.skipped += 1,
+            }
+        }
+        summary
+    }
+}
+
+/// Description and conversation of a PR.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrDetails {
+    /// PR description (markdown).
+    pub body: String,
+    /// Label names.
+    pub labels: Vec<String>,
+    /// Users and teams asked to review.
+    pub review_requests: Vec<String>,
+    /// Top-level conversation, oldest first.
+    pub comments: Vec<PrComment>,
+}
+
+/// A top-level comment in a PR's conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrComment {
+    /// GitHub login of the author, if the account still exists.
+    pub author: Option<String>,
+    /// Comment body (markdown).
+    pub body: String,
+    /// When the comment was posted (RFC 3339).
+    pub created_at: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DetailsJson {
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    labels: Vec<LabelJson>,
+    #[serde(default)]
+    review_requests: Vec<ReviewerJson>,
+    #[serde(default)]
+    comments: Vec<CommentJson>,
+}
+
+#[derive(Deserialize)]
+struct LabelJson {
+    name: String,
+}
+
+/// A requested reviewer: a user has a `login`, a team a `name` or `slug`.
+#[derive(Deserialize)]
+struct ReviewerJson {
+    login: Option<String>,
+    name: Option<String>,
+    slug: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentJson {
+    author: Option<PRAuthor>,
+    body: String,
+    #[serde(default)]
+    created_at: String,
 }
 
 /// Check if `gh` CLI is available and authenticated.
@@ -86,34 +635,10 @@ pub fn is_gh_available() -> bool {
         .unwrap_or(false)
 }
 
-/// List open (non-draft) PRs for the repository at `repo_path`.
-pub fn list_prs(
-    repo_path: &std::path::Path,
-    filter: PRFilter,
-) -> Result<Vec<PullRequest>, GhError> {
-    let mut args = vec![
-        "pr",
-        "list",
-        "--state",
-        "open",
-        "--json",
-        "number,title,headRefName,baseRefName,author,additions,deletions,changedFiles,isDraft",
-    ];
-
-    match filter {
-        PRFilter::All => {}
-        PRFilter::Mine => {
-            args.push("--author");
-            args.push("@me");
-        }
-        PRFilter::ReviewRequested => {
-            args.push("--search");
-            args.push("review-requested:@me");
-        }
-    }
-
+/// List the PRs matching `query` for the repository at `repo_path`.
+pub fn list_prs(repo_path: &std::path::Path, query: &PRQuery) -> Result<Vec<PullRequest>, GhError> {
     let output = Command::new("gh")
-        .args(&args)
+        .args(query.args())
         .current_dir(repo_path)
         .output()?;
 
@@ -125,11 +650,7 @@ pub fn list_prs(
         return Err(GhError::ApiError(stderr.trim().to_string()));
     }
 
-    let prs: Vec<PullRequest> =
-        serde_json::from_slice(&output.stdout).map_err(|e| GhError::ParseError(e.to_string()))?;
-
-    // Filter out drafts
-    Ok(prs.into_iter().filter(|pr| !pr.is_draft).collect())
+    serde_json::from_slice(&output.stdout).map_err(|e| GhError::ParseError(e.to_string()))
 }
 
 /// Get the unified diff for a PR.
@@ -186,6 +707,173 @@ pub fn get_pr_diff(repo_path: &std::path::Path, pr_number: u32) -> Result<String
     String::from_utf8(buffer).map_err(|e| GhError::ParseError(format!("Invalid UTF-8: {}", e)))
 }
 
+/// Fetch the review threads of a PR through the GraphQL API.
+///
+/// Follows the page cursors of both the threads and each thread's comments,
+/// so busy PRs come back complete.
+pub fn get_pr_review_threads(
+    repo_path: &std::path::Path,
+    pr_number: u32,
+) -> Result<Vec<ReviewThread>, GhError> {
+    let number = format!("number={}", pr_number);
+    let mut threads = Vec::new();
+    let mut after = None;
+    loop {
+        let json = run_graphql(
+            repo_path,
+            REVIEW_THREADS_QUERY,
+            &["owner={owner}", "repo={repo}", &number],
+            after.as_deref(),
+        )?;
+        let page: GraphQlNodes<ThreadNode> =
+            parse_connection(&json, "/data/repository/pullRequest/reviewThreads")?;
+        for mut node in page.nodes {
+            let mut cursor = node.comments.page_info.next_cursor();
+            while let Some(after) = cursor {
+                let json = run_graphql(
+                    repo_path,
+                    THREAD_COMMENTS_QUERY,
+                    &[&format!("id={}", node.id)],
+                    Some(&after),
+                )?;
+                let more: GraphQlNodes<ThreadCommentNode> =
+                    parse_connection(&json, "/data/node/comments")?;
+                node.comments.nodes.extend(more.nodes);
+                cursor = more.page_info.next_cursor();
+            }
+            threads.push(review_thread(node));
+        }
+        match page.page_info.next_cursor() {
+            Some(cursor) => after = Some(cursor),
+            None => return Ok(threads),
+        }
+    }
+}
+
+/// Run a GraphQL query through `gh api graphql`, passing `after` as the
+/// page cursor when set.
+fn run_graphql(
+    repo_path: &std::path::Path,
+    query: &str,
+    fields: &[&str],
+    after: Option<&str>,
+) -> Result<Vec<u8>, GhError> {
+    let mut cmd = Command::new("gh");
+    cmd.args(["api", "graphql"]);
+    for field in fields {
+        cmd.args(["-F", field]);
+    }
+    if let Some(after) = after {
+        cmd.args(["-f", &format!("after={}", after)]);
+    }
+    let output = cmd
+        .args(["-f", &format!("query={}", query)])
+        .current_dir(repo_path)
+        .stdin(Stdio::null())
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GhError::ApiError(stderr.trim().to_string()));
+    }
+    Ok(output.stdout)
+}
+
+/// The connection at `pointer` in a GraphQL response.
+fn parse_connection<T: DeserializeOwned>(
+    json: &[u8],
+    pointer: &str,
+) -> Result<GraphQlNodes<T>, GhError> {
+    let value: serde_json::Value =
+        serde_json::from_slice(json).map_err(|e| GhError::ParseError(e.to_string()))?;
+    let connection = value
+        .pointer(pointer)
+        .cloned()
+        .ok_or_else(|| GhError::ParseError(format!("missing {}", pointer)))?;
+    serde_json::from_value(connection).map_err(|e| GhError::ParseError(e.to_string()))
+}
+
+fn review_thread(node: ThreadNode) -> ReviewThread {
+    ReviewThread {
+        path: RelPath::new(&node.path),
+        side: node.diff_side,
+        line: node.line,
+        start_line: node.start_line,
+        is_resolved: node.is_resolved,
+        is_outdated: node.is_outdated,
+        comments: node
+            .comments
+            .nodes
+            .into_iter()
+            .map(|c| ReviewThreadComment {
+                author: c.author.map(|a| a.login),
+                body: c.body,
+            })
+            .collect(),
+    }
+}
+
+/// Make the head and merge base of a PR available in the local repository.
+///
+/// Missing commits are fetched from the PR's repository (`refs/pull/N/head`
+/// and the base branch) without creating local refs.
+pub fn fetch_pr_revisions(
+    repo_path: &std::path::Path,
+    pr_number: u32,
+) -> Result<PrRevisions, GhError> {
+    let output = Command::new("gh")
+        .args(["pr", "view", &pr_number.to_string()])
+        .args(["--json", "headRefOid,baseRefOid,baseRefName,url"])
+        .current_dir(repo_path)
+        .stdin(Stdio::null())
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GhError::ApiError(stderr.trim().to_string()));
+    }
+
+    let refs: PrRefs =
+        serde_json::from_slice(&output.stdout).map_err(|e| GhError::ParseError(e.to_string()))?;
+    let git_error = |e: git2::Error| GhError::GitError(e.message().to_string());
+    let repo = git2::Repository::open(repo_path).map_err(git_error)?;
+    let head = git2::Oid::from_str(&refs.head_ref_oid).map_err(git_error)?;
+    let base = git2::Oid::from_str(&refs.base_ref_oid).map_err(git_error)?;
+    let present = |oid| repo.find_commit(oid).is_ok();
+
+    if !present(head) || !present(base) {
+        // The PR URL is `<repository>/pull/<number>`.
+        let remote = refs
+            .url
+            .rsplit_once("/pull/")
+            .map_or(refs.url.as_str(), |(repository, _)| repository);
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(repo_path)
+            .args(["fetch", "--quiet", "--no-tags", remote])
+            .arg(format!("refs/pull/{}/head", pr_number))
+            .arg(format!("refs/heads/{}", refs.base_ref_name))
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdin(Stdio::null())
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(GhError::GitError(stderr.trim().to_string()));
+        }
+        if !present(head) || !present(base) {
+            return Err(GhError::GitError(
+                "fetched PR commits are not in the repository".to_string(),
+            ));
+        }
+    }
+
+    let merge_base = repo.merge_base(head, base).map_err(git_error)?;
+    Ok(PrRevisions {
+        base: merge_base.to_string(),
+        head: head.to_string(),
+    })
+}
+
 /// Approve a PR.
 pub fn approve_pr(
     repo_path: &std::path::Path,
@@ -251,6 +939,107 @@ pub fn request_changes_pr(
     Ok(())
 }
 
+/// Submit a review with inline comments to a PR.
+///
+/// Posts to the pull request reviews API through `gh api`, so the whole review
+/// lands at once.
+pub fn submit_pr_review(
+    repo_path: &std::path::Path,
+    pr_number: u32,
+    review: &PullRequestReview,
+) -> Result<(), GhError> {
+    let payload = serde_json::to_vec(review).map_err(|e| GhError::ParseError(e.to_string()))?;
+    let endpoint = format!("repos/{{owner}}/{{repo}}/pulls/{}/reviews", pr_number);
+    let mut child = Command::new("gh")
+        .args(["api", "--method", "POST", &endpoint, "--input", "-"])
+        .current_dir(repo_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| GhError::ApiError("Failed to capture gh stdin".to_string()))?
+        .write_all(&payload)?;
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GhError::ApiError(stderr.trim().to_string()));
+    }
+
+    Ok(())
+}
+
+/// Fetch the CI checks of a PR.
+///
+/// `gh pr checks` exits non-zero while checks fail or are pending, so its
+/// output is used whenever it parses; a PR without checks yields an empty list.
+pub fn get_pr_checks(repo_path: &std::path::Path, pr_number: u32) -> Result<Vec<PrCheck>, GhError> {
+    let output = Command::new("gh")
+        .args(["pr", "checks", &pr_number.to_string()])
+        .args(["--json", "name,workflow,bucket,link"])
+        .current_dir(repo_path)
+        .stdin(Stdio::null())
+        .output()?;
+
+    if let Ok(checks) = serde_json::from_slice(&output.stdout) {
+        return Ok(checks);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("no checks reported") {
+        return Ok(Vec::new());
+    }
+    if !output.status.success() {
+        return Err(GhError::ApiError(stderr.trim().to_string()));
+    }
+    Err(GhError::ParseError(
+        "unexpected gh pr checks output".to_string(),
+    ))
+}
+
+/// Fetch the description, labels, requested reviewers and conversation of a PR.
+pub fn get_pr_details(repo_path: &std::path::Path, pr_number: u32) -> Result<PrDetails, GhError> {
+    let output = Command::new("gh")
+        .args(["pr", "view", &pr_number.to_string()])
+        .args(["--json", "body,labels,reviewRequests,comments"])
+        .current_dir(repo_path)
+        .stdin(Stdio::null())
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GhError::ApiError(stderr.trim().to_string()));
+    }
+
+    parse_pr_details(&output.stdout)
+}
+
+fn parse_pr_details(json: &[u8]) -> Result<PrDetails, GhError> {
+    let details: DetailsJson =
+        serde_json::from_slice(json).map_err(|e| GhError::ParseError(e.to_string()))?;
+    Ok(PrDetails {
+        body: details.body,
+        labels: details.labels.into_iter().map(|l| l.name).collect(),
+        review_requests: details
+            .review_requests
+            .into_iter()
+            .filter_map(|r| r.login.or(r.name).or(r.slug))
+            .collect(),
+        comments: details
+            .comments
+            .into_iter()
+            .map(|c| PrComment {
+                author: c.author.map(|a| a.login),
+                body: c.body,
+                created_at: c.created_at,
+            })
+            .collect(),
+    })
+}
+
 /// Open a PR in the browser.
 pub fn open_pr_in_browser(repo_path: &std::path::Path, pr_number: u32) -> Result<(), GhError> {
     let pr_num_str = pr_number.to_string();
@@ -275,4 +1064,140 @@ mod tests {
     fn pr_filter_default_is_all() {
         assert_eq!(PRFilter::default(), PRFilter::All);
     }
+
+    #[test]
+    fn default_query_lists_open_prs() {
+        let args = PRQuery::default().args();
+        assert!(args.windows(2).any(|w| w == ["--state", "open"]));
+        assert!(args.windows(2).any(|w| w == ["--limit", "30"]));
+        assert!(!args.iter().any(|a| a == "--search" || a == "--draft"));
+    }
+
+    #[test]
+    fn query_args_combine_filters_and_search() {
+        let mut query = PRQuery {
+            filter: PRFilter::ReviewRequested,
+            state: PRState::Closed,
+            sort: PRSort::RecentlyUpdated,
+            limit: 60,
+            ..PRQuery::default()
+        };
+        query.set_search_input("label:bug fix parser author:alice");
+        assert_eq!(query.search, "fix parser");
+        assert_eq!(query.search_input(), "label:bug author:alice fix parser");
+
+        let args = query.args();
+        assert!(args.windows(2).any(|w| w == ["--state", "closed"]));
+        assert!(args.windows(2).any(|w| w == ["--limit", "60"]));
+        assert!(args.windows(2).any(|w| w == ["--author", "alice"]));
+        assert!(args.windows(2).any(|w| w == ["--label", "bug"]));
+        assert!(args.windows(2).any(|w| w
+            == [
+                "--search",
+                "is:unmerged review-requested:@me fix parser sort:updated-desc"
+            ]));
+
+        let drafts = PRQuery {
+            state: PRState::Draft,
+            filter: PRFilter::Mine,
+            ..PRQuery::default()
+        }
+        .args();
+        assert!(drafts.iter().any(|a| a == "--draft"));
+        assert!(drafts.windows(2).any(|w| w == ["--author", "@me"]));
+    }
+
+    #[test]
+    fn parses_review_decision_and_check_rollup() {
+        let json = br#"[
+            {"number":1,"title":"a","headRefName":"h","baseRefName":"main","author":{"login":"x"},
+             "additions":1,"deletions":0,"changedFiles":1,"isDraft":true,"reviewDecision":"APPROVED",
+             "statusCheckRollup":[
+                {"__typename":"CheckRun","status":"COMPLETED","conclusion":"SUCCESS"},
+                {"__typename":"StatusContext","state":"PENDING"}]},
+            {"number":2,"title":"b","headRefName":"h","baseRefName":"main","author":{"login":"x"},
+             "additions":1,"deletions":0,"changedFiles":1,"reviewDecision":"",
+             "statusCheckRollup":[
+                {"__typename":"CheckRun","status":"COMPLETED","conclusion":"TIMED_OUT"},
+                {"__typename":"CheckRun","status":"IN_PROGRESS","conclusion":""}]},
+            {"number":3,"title":"c","headRefName":"h","baseRefName":"main","author":{"login":"x"},
+             "additions":1,"deletions":0,"changedFiles":1,"statusCheckRollup":[]}
+        ]"#;
+        let prs: Vec<PullRequest> = serde_json::from_slice(json).unwrap();
+        assert!(prs[0].is_draft);
+        assert_eq!(prs[0].review_decision, Some(ReviewDecision::Approved));
+        assert_eq!(prs[0].checks, Some(CheckBucket::Pending));
+        assert_eq!(prs[1].review_decision, None);
+        assert_eq!(prs[1].checks, Some(CheckBucket::Fail));
+        assert_eq!(prs[2].checks, None);
+    }
+
+    #[test]
+    fn parses_review_threads() {
+        let json = br#"{"data":{"repository":{"pullRequest":{"reviewThreads":{
+            "pageInfo":{"hasNextPage":true,"endCursor":"Y3Vyc29y"},"nodes":[
+            {"id":"T1","path":"src/lib.rs","line":12,"startLine":10,"diffSide":"RIGHT",
+             "isResolved":false,"isOutdated":false,
+             "comments":{"pageInfo":{"hasNextPage":false,"endCursor":"MQ"},
+                         "nodes":[{"author":{"login":"alice"},"body":"why?"},
+                                  {"author":null,"body":"because"}]}},
+            {"id":"T2","path":"old.rs","line":null,"startLine":null,"diffSide":"LEFT",
+             "isResolved":true,"isOutdated":true,"comments":{"nodes":[]}}
+        ]}}}}}"#;
+        let page: GraphQlNodes<ThreadNode> =
+            parse_connection(json, "/data/repository/pullRequest/reviewThreads").unwrap();
+        assert_eq!(page.page_info.next_cursor().as_deref(), Some("Y3Vyc29y"));
+        assert_eq!(page.nodes[0].comments.page_info.next_cursor(), None);
+        assert_eq!(page.nodes[1].comments.page_info.next_cursor(), None);
+        let threads: Vec<_> = page.nodes.into_iter().map(review_thread).collect();
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].path.as_str(), "src/lib.rs");
+        assert_eq!(threads[0].side, ReviewSide::Right);
+        assert_eq!(
+            (threads[0].start_line, threads[0].line),
+            (Some(10), Some(12))
+        );
+        assert_eq!(threads[0].comments[0].author.as_deref(), Some("alice"));
+        assert_eq!(threads[0].comments[1].author, None);
+        assert!(threads[1].is_resolved && threads[1].is_outdated);
+        assert_eq!(threads[1].line, None);
+    }
+
+    #[test]
+    fn parses_pr_details() {
+        let json = br#"{"body":"Fixes the parser.","labels":[{"name":"bug","color":"d73a4a"}],
+            "reviewRequests":[{"__typename":"User","login":"bob"},
+                              {"__typename":"Team","name":"Core","slug":"core"}],
+            "comments":[{"author":{"login":"carol"},"body":"LGTM","createdAt":"2025-05-01T10:00:00Z"},
+                        {"author":null,"body":"ghost","createdAt":"2025-05-02T10:00:00Z"}]}"#;
+        let details = parse_pr_details(json).unwrap();
+        assert_eq!(details.body, "Fixes the parser.");
+        assert_eq!(details.labels, ["bug"]);
+        assert_eq!(details.review_requests, ["bob", "Core"]);
+        assert_eq!(details.comments[0].author.as_deref(), Some("carol"));
+        assert_eq!(details.comments[0].created_at, "2025-05-01T10:00:00Z");
+        assert_eq!(details.comments[1].author, None);
+    }
+
+    #[test]
+    fn summarizes_checks_by_bucket() {
+        let checks: Vec<PrCheck> = serde_json::from_str(
+            r#"[{"name":"build","workflow":"CI","bucket":"pass","link":""},
+                {"name":"lint","bucket":"fail"},
+                {"name":"deploy","bucket":"cancel"},
+                {"name":"docs","bucket":"skipping"},
+                {"name":"e2e","bucket":"queued-somehow"}]"#,
+        )
+        .unwrap();
+        assert_eq!(checks[4].bucket, CheckBucket::Pending);
+        assert_eq!(
+            ChecksSummary::of(&checks),
+            ChecksSummary {
+                passed: 1,
+                failed: 2,
+                pending: 1,
+                skipped: 1
+            }
+        );
+    }
 }
@@ -1,27 +1,49 @@
 //! Core primitives for quickdiff (no TUI dependencies).
 
 mod comments;
+mod comments_ref;
 mod comments_store;
 mod config;
+#[cfg(unix)]
+mod control;
 mod diff;
+mod editor;
+mod findings;
 mod fuzzy;
 mod gh;
+mod pathspec;
 mod pr_diff;
+mod pr_review;
+mod relocate;
+mod renames;
 mod repo;
 mod stdin_input;
+mod suggestion;
 mod text;
 mod viewed;
 mod watcher;
+mod worktree;
 
 pub use comments::*;
+pub use comments_ref::*;
 pub use comments_store::*;
 pub use config::*;
+#[cfg(unix)]
+pub use control::*;
 pub use diff::*;
+pub use editor::*;
+pub use findings::*;
 pub use fuzzy::*;
 pub use gh::*;
+pub use pathspec::*;
 pub use pr_diff::*;
+pub use pr_review::*;
+pub use relocate::*;
+pub use renames::*;
 pub use repo::*;
 pub use stdin_input::*;
+pub use suggestion::*;
 pub use text::*;
 pub use viewed::*;
 pub use watcher::*;
+pub use worktree::*;
@@ -0,0 +1,331 @@
+//! Git-style pathspecs for filtering changed files.
+//!
+//! Supported syntax:
+//! - Literal paths match the file itself or anything below it (`src`, `src/lib.rs`).
+//! - Globs use `*` (within one path component), `**` (across components),
+//!   `?` and `[...]` classes. Globs without a `/` match at any depth (`*.snap`).
+//! - `:!pattern`, `:^pattern` and `:(exclude)pattern` exclude matching paths.
+
+/// A single parsed pathspec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pathspec {
+    pattern: Vec<char>,
+    is_glob: bool,
+    anchored: bool,
+}
+
+impl Pathspec {
+    fn parse(raw: &str) -> Option<Self> {
+        let trimmed = raw.trim().trim_start_matches("./");
+        let anchored_prefix = trimmed.starts_with('/');
+        let trimmed = trimmed.trim_start_matches('/').trim_end_matches('/');
+        if trimmed.is_empty() {
+            return None;
+        }
+
+        let is_glob = trimmed.contains(['*', '?', '[']);
+        Some(Self {
+            pattern: trimmed.chars().collect(),
+            is_glob,
+            anchored: anchored_prefix || !is_glob || trimmed.contains('/'),
+        })
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        if !self.is_glob {
+            let pattern: String = self.pattern.iter().collect();
+            return path == pattern
+                || path
+                    .strip_prefix(pattern.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'));
+        }
+
+        let chars: Vec<char> = path.chars().collect();
+        // Try the whole path and every directory prefix, so `vendor/*` also
+        // covers files nested deeper below `vendor/`.
+        let prefix_ends = chars
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c == '/')
+            .map(|(i, _)| i)
+            .chain(std::iter::once(chars.len()));
+
+        let starts: Vec<usize> = if self.anchored {
+            vec![0]
+        } else {
+            std::iter::once(0)
+                .chain(
+                    chars
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, c)| **c == '/')
+                        .map(|(i, _)| i + 1),
+                )
+                .collect()
+        };
+
+        prefix_ends.into_iter().any(|end| {
+            starts
+                .iter()
+                .filter(|&&start| start <= end)
+                .any(|&start| glob_match(&self.pattern, &chars[start..end]))
+        })
+    }
+}
+
+/// Include/exclude pathspec filter applied to changed file lists.
+///
+/// An empty filter matches every path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathFilter {
+    includes: Vec<Pathspec>,
+    excludes: Vec<Pathspec>,
+    always_excluded: Vec<Pathspec>,
+}
+
+impl PathFilter {
+    /// Build a filter from user pathspecs (e.g. repeated `-f` flags).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdiff::core::PathFilter;
+    ///
+    /// let filter = PathFilter::new(["src/**", ":!**/*.snap"]);
+    /// assert!(filter.matches("src/lib.rs"));
+    /// assert!(!filter.matches("src/snapshots/a.snap"));
+    /// assert!(!filter.matches("README.md"));
+    /// ```
+    pub fn new<I, S>(specs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut filter = Self::default();
+        for spec in specs {
+            let spec = spec.as_ref();
+            if let Some(excluded) = strip_exclude_magic(spec) {
+                filter.excludes.extend(Pathspec::parse(excluded));
+            } else {
+                filter.includes.extend(Pathspec::parse(spec));
+            }
+        }
+        filter
+    }
+
+    /// Add globs that are always excluded (e.g. the repo config `exclude` list).
+    #[must_use]
+    pub fn with_excludes<I, S>(mut self, globs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.always_excluded.extend(
+            globs
+                .into_iter()
+                .filter_map(|glob| Pathspec::parse(glob.as_ref())),
+        );
+        self
+    }
+
+    /// Whether the filter accepts every path.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.includes.is_empty() && self.excludes.is_empty() && self.always_excluded.is_empty()
+    }
+
+    /// Whether the user supplied any pathspecs (ignoring config excludes).
+    #[must_use]
+    pub fn has_pathspecs(&self) -> bool {
+        !self.includes.is_empty() || !self.excludes.is_empty()
+    }
+
+    /// Check whether a repository-relative path passes the filter.
+    #[must_use]
+    pub fn matches(&self, path: &str) -> bool {
+        if !self.includes.is_empty() && !self.includes.iter().any(|spec| spec.matches(path)) {
+            return false;
+        }
+        !self
+            .excludes
+            .iter()
+            .chain(&self.always_excluded)
+            .any(|spec| spec.matches(path))
+    }
+}
+
+fn strip_exclude_magic(spec: &str) -> Option<&str> {
+    spec.strip_prefix(":!")
+        .or_else(|| spec.strip_prefix(":^"))
+        .or_else(|| spec.strip_prefix(":(exclude)"))
+}
+
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+            if let Some(after_slash) = rest.strip_prefix(&['/']) {
+                // `**/` matches zero or more leading directories.
+                glob_match(after_slash, path)
+                    || path
+                        .iter()
+                        .enumerate()
+                        .any(|(i, c)| *c == '/' && glob_match(after_slash, &path[i + 1..]))
+            } else {
+                (0..=path.len()).any(|i| glob_match(rest, &path[i..]))
+            }
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            for i in 0..=path.len() {
+                if glob_match(rest, &path[i..]) {
+                    return true;
+                }
+                if path.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => {
+            path.first().is_some_and(|c| *c != '/') && glob_match(&pattern[1..], &path[1..])
+        }
+        Some('[') => match parse_class(&pattern[1..]) {
+            Some((class, consumed)) => path.first().is_some_and(|c| {
+                *c != '/' && class.matches(*c) && glob_match(&pattern[1 + consumed..], &path[1..])
+            }),
+            None => path.first() == Some(&'[') && glob_match(&pattern[1..], &path[1..]),
+        },
+        Some(literal) => path.first() == Some(literal) && glob_match(&pattern[1..], &path[1..]),
+    }
+}
+
+struct CharClass {
+    negated: bool,
+    ranges: Vec<(char, char)>,
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        self.ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)) != self.negated
+    }
+}
+
+/// Parse a `[...]` class body. Returns the class and the chars consumed including `]`.
+fn parse_class(body: &[char]) -> Option<(CharClass, usize)> {
+    let mut i = 0;
+    let negated = matches!(body.first(), Some('!' | '^'));
+    if negated {
+        i += 1;
+    }
+
+    let mut ranges = Vec::new();
+    let mut first = true;
+    while i < body.len() {
+        let c = body[i];
+        if c == ']' && !first {
+            return Some((CharClass { negated, ranges }, i + 1));
+        }
+        first = false;
+        if body.get(i + 1) == Some(&'-') && body.get(i + 2).is_some_and(|end| *end != ']') {
+            ranges.push((c, body[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((c, c));
+            i += 1;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let filter = PathFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.matches("any/path.rs"));
+    }
+
+    #[test]
+    fn literal_matches_file_or_directory() {
+        let filter = PathFilter::new(["src"]);
+        assert!(filter.matches("src"));
+        assert!(filter.matches("src/lib.rs"));
+        assert!(!filter.matches("srcs/lib.rs"));
+        assert!(!filter.matches("other/src/lib.rs"));
+
+        let filter = PathFilter::new(["src/"]);
+        assert!(filter.matches("src/ui/mod.rs"));
+    }
+
+    #[test]
+    fn single_star_stays_within_component() {
+        let filter = PathFilter::new(["src/*.rs"]);
+        assert!(filter.matches("src/lib.rs"));
+        assert!(!filter.matches("src/ui/mod.rs"));
+    }
+
+    #[test]
+    fn double_star_crosses_components() {
+        let filter = PathFilter::new(["src/**/*.rs"]);
+        assert!(filter.matches("src/lib.rs"));
+        assert!(filter.matches("src/ui/app/mod.rs"));
+        assert!(!filter.matches("tests/ui.rs"));
+
+        let filter = PathFilter::new(["src/**"]);
+        assert!(filter.matches("src/a/b/c.txt"));
+    }
+
+    #[test]
+    fn slashless_glob_matches_at_any_depth() {
+        let filter = PathFilter::new(["*.snap"]);
+        assert!(filter.matches("a.snap"));
+        assert!(filter.matches("tests/snapshots/a.snap"));
+        assert!(!filter.matches("a.snapshot"));
+    }
+
+    #[test]
+    fn excludes_remove_matches() {
+        let filter = PathFilter::new(["src/**", ":!**/*.snap", ":(exclude)src/gen"]);
+        assert!(filter.matches("src/lib.rs"));
+        assert!(!filter.matches("src/tests/a.snap"));
+        assert!(!filter.matches("src/gen/out.rs"));
+    }
+
+    #[test]
+    fn exclude_only_filter_keeps_everything_else() {
+        let filter = PathFilter::new([":^docs"]);
+        assert!(filter.matches("src/lib.rs"));
+        assert!(!filter.matches("docs/notes.md"));
+    }
+
+    #[test]
+    fn config_excludes_apply_without_pathspecs() {
+        let filter = PathFilter::default().with_excludes(["vendor/*", "third_party"]);
+        assert!(!filter.is_empty());
+        assert!(!filter.has_pathspecs());
+        assert!(!filter.matches("vendor/crate/lib.rs"));
+        assert!(!filter.matches("third_party/x.c"));
+        assert!(filter.matches("src/vendor.rs"));
+    }
+
+    #[test]
+    fn character_classes() {
+        let filter = PathFilter::new(["file[0-9].txt", "data[!a].csv"]);
+        assert!(filter.matches("file3.txt"));
+        assert!(!filter.matches("filex.txt"));
+        assert!(filter.matches("datab.csv"));
+        assert!(!filter.matches("dataa.csv"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_char() {
+        let filter = PathFilter::new(["src/?.rs"]);
+        assert!(filter.matches("src/a.rs"));
+        assert!(!filter.matches("src/ab.rs"));
+    }
+}
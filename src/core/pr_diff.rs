@@ -1,6 +1,6 @@
 //! Parser for unified diff output from `gh pr diff`.
 
-use crate::core::{FileChangeKind, RelPath};
+use crate::core::{DiffSide, FileChangeKind, FileMode, RelPath};
 
 /// A file changed in a PR with its patch content.
 #[derive(Debug, Clone)]
@@ -11,6 +11,12 @@ pub struct PRChangedFile {
     pub old_path: Option<RelPath>,
     /// Type of change.
     pub kind: FileChangeKind,
+    /// Mode before the change (absent for added files).
+    pub old_mode: Option<FileMode>,
+    /// Mode after the change (absent for deleted files).
+    pub new_mode: Option<FileMode>,
+    /// Similarity percent reported for renames and copies.
+    pub similarity: Option<u8>,
     /// Number of lines added.
     pub additions: usize,
     /// Number of lines deleted.
@@ -56,6 +62,175 @@ pub fn parse_unified_diff(raw_diff: &str) -> Vec<PRChangedFile> {
     files
 }
 
+/// Where a line of [`PatchSides`] content sits in the real file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatchLine {
+    /// 1-based line number in the file on that side; for a gap separator,
+    /// the first line it stands in for.
+    pub file_line: u32,
+    /// 0-based index of the hunk the line belongs to (or, for a gap
+    /// separator, the hunk that follows it).
+    pub hunk: usize,
+    /// Whether this is a separator for file lines the patch leaves out.
+    pub gap: bool,
+}
+
+/// Both sides of a patch, reassembled from its hunks.
+///
+/// PR files are shown from the patch alone, so `old` and `new` hold only the
+/// lines the hunks cover; `old_lines`/`new_lines` map each of them back to
+/// its line in the file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatchSides {
+    /// Old-side lines, joined with newlines.
+    pub old: String,
+    /// New-side lines, joined with newlines.
+    pub new: String,
+    /// File position of each old-side line.
+    pub old_lines: Vec<PatchLine>,
+    /// File position of each new-side line.
+    pub new_lines: Vec<PatchLine>,
+}
+
+impl PatchSides {
+    /// File positions of the lines on one side.
+    pub fn lines(&self, side: DiffSide) -> &[PatchLine] {
+        match side {
+            DiffSide::Old => &self.old_lines,
+            DiffSide::New => &self.new_lines,
+        }
+    }
+
+    /// 1-based file line of a 0-based content line; `None` for gap separators.
+    pub fn file_line(&self, side: DiffSide, line: usize) -> Option<u32> {
+        self.lines(side)
+            .get(line)
+            .filter(|l| !l.gap)
+            .map(|l| l.file_line)
+    }
+
+    /// Content line showing a 1-based file line, if the patch covers it.
+    pub fn content_line(&self, side: DiffSide, file_line: u32) -> Option<usize> {
+        self.lines(side)
+            .iter()
+            .position(|l| !l.gap && l.file_line == file_line)
+    }
+
+    /// Highest file line number the patch shows.
+    pub fn max_file_line(&self) -> u32 {
+        self.old_lines
+            .iter()
+            .chain(&self.new_lines)
+            .filter(|l| !l.gap)
+            .map(|l| l.file_line)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Split a single-file patch into its old and new sides.
+pub fn split_patch(patch: &str) -> PatchSides {
+    split_patch_lines(patch, false)
+}
+
+/// Like [`split_patch`], with a separator line such as `⋯ 12 unchanged lines`
+/// on both sides wherever the patch skips part of the file before or between
+/// its hunks.
+pub fn split_patch_with_gaps(patch: &str) -> PatchSides {
+    split_patch_lines(patch, true)
+}
+
+fn split_patch_lines(patch: &str, gaps: bool) -> PatchSides {
+    let mut old: Vec<std::borrow::Cow<'_, str>> = Vec::new();
+    let mut new: Vec<std::borrow::Cow<'_, str>> = Vec::new();
+    let mut sides = PatchSides::default();
+    let mut hunk: Option<usize> = None;
+    let (mut old_line, mut new_line) = (1u32, 1u32);
+
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            let (o, n) = parse_hunk_starts(line).unwrap_or((old_line, new_line));
+            let next_hunk = hunk.map_or(0, |h| h + 1);
+            // Unchanged lines are the same on both sides; an empty side
+            // (`-0,0` or `+0,0`) has no position to measure from.
+            let skipped = if n > 0 {
+                n.saturating_sub(new_line)
+            } else {
+                o.saturating_sub(old_line)
+            };
+            if gaps && skipped > 0 {
+                let text = format!(
+                    "⋯ {} unchanged line{}",
+                    skipped,
+                    if skipped == 1 { "" } else { "s" }
+                );
+                old.push(text.clone().into());
+                new.push(text.into());
+                sides.old_lines.push(PatchLine {
+                    file_line: old_line,
+                    hunk: next_hunk,
+                    gap: true,
+                });
+                sides.new_lines.push(PatchLine {
+                    file_line: new_line,
+                    hunk: next_hunk,
+                    gap: true,
+                });
+            }
+            old_line = o;
+            new_line = n;
+            hunk = Some(next_hunk);
+            continue;
+        }
+
+        let Some(hunk) = hunk else {
+            continue;
+        };
+        let at = |file_line| PatchLine {
+            file_line,
+            hunk,
+            gap: false,
+        };
+
+        if line.starts_with('-') && !line.starts_with("---") {
+            // Deleted line - only in old
+            old.push(line[1..].into());
+            sides.old_lines.push(at(old_line));
+            old_line += 1;
+        } else if line.starts_with('+') && !line.starts_with("+++") {
+            // Added line - only in new
+            new.push(line[1..].into());
+            sides.new_lines.push(at(new_line));
+            new_line += 1;
+        } else if line.starts_with(' ') || line.is_empty() {
+            // Context line - in both
+            let content = line.strip_prefix(' ').unwrap_or(line);
+            old.push(content.into());
+            new.push(content.into());
+            sides.old_lines.push(at(old_line));
+            sides.new_lines.push(at(new_line));
+            old_line += 1;
+            new_line += 1;
+        }
+    }
+
+    sides.old = old.join("\n");
+    sides.new = new.join("\n");
+    sides
+}
+
+/// Parse the start lines from `@@ -a,b +c,d @@`.
+fn parse_hunk_starts(header: &str) -> Option<(u32, u32)> {
+    let mut parts = header.split_whitespace().skip(1);
+    let start = |part: Option<&str>, sign: char| -> Option<u32> {
+        let range = part?.strip_prefix(sign)?;
+        range.split(',').next()?.parse().ok()
+    };
+    let old = start(parts.next(), '-')?;
+    let new = start(parts.next(), '+')?;
+    Some((old, new))
+}
+
 fn parse_file_chunk(chunk: &str) -> Option<PRChangedFile> {
     let lines: Vec<&str> = chunk.lines().collect();
     let first_line = lines.first()?;
@@ -66,21 +241,56 @@ fn parse_file_chunk(chunk: &str) -> Option<PRChangedFile> {
     // Determine status from diff metadata
     let mut kind = FileChangeKind::Modified;
     let mut old_path = None;
+    let mut old_mode = None;
+    let mut new_mode = None;
+    let mut index_mode = None;
+    let mut similarity = None;
 
     for line in lines.iter().take(10) {
-        if line.starts_with("new file mode") {
+        if let Some(mode) = line.strip_prefix("new file mode ") {
             kind = FileChangeKind::Added;
-        } else if line.starts_with("deleted file mode") {
+            new_mode = FileMode::from_octal(mode);
+        } else if let Some(mode) = line.strip_prefix("deleted file mode ") {
             kind = FileChangeKind::Deleted;
-        } else if line.starts_with("rename from ") {
+            old_mode = FileMode::from_octal(mode);
+        } else if let Some(mode) = line.strip_prefix("old mode ") {
+            old_mode = FileMode::from_octal(mode);
+        } else if let Some(mode) = line.strip_prefix("new mode ") {
+            new_mode = FileMode::from_octal(mode);
+        } else if let Some(rest) = line.strip_prefix("index ") {
+            // "index abc..def 100644" carries the mode when it is unchanged.
+            index_mode = rest
+                .split_whitespace()
+                .nth(1)
+                .and_then(FileMode::from_octal);
+        } else if let Some(from) = line.strip_prefix("rename from ") {
             kind = FileChangeKind::Renamed;
-            let from = line.strip_prefix("rename from ").unwrap_or("");
             old_path = Some(RelPath::new(from));
+        } else if let Some(from) = line.strip_prefix("copy from ") {
+            kind = FileChangeKind::Copied;
+            old_path = Some(RelPath::new(from));
+        } else if let Some(score) = line.strip_prefix("similarity index ") {
+            similarity = score.trim_end_matches('%').parse().ok();
+        }
+    }
+
+    if let Some(mode) = index_mode {
+        match kind {
+            FileChangeKind::Added => new_mode = new_mode.or(Some(mode)),
+            FileChangeKind::Deleted => old_mode = old_mode.or(Some(mode)),
+            _ if old_mode.is_none() && new_mode.is_none() => {
+                old_mode = Some(mode);
+                new_mode = Some(mode);
+            }
+            _ => {}
         }
     }
 
     // For renames without explicit "rename from", use the a/ path
-    if kind == FileChangeKind::Renamed && old_path.is_none() && old_path_str != new_path_str {
+    if matches!(kind, FileChangeKind::Renamed | FileChangeKind::Copied)
+        && old_path.is_none()
+        && old_path_str != new_path_str
+    {
         old_path = Some(RelPath::new(old_path_str));
     }
 
@@ -108,6 +318,9 @@ fn parse_file_chunk(chunk: &str) -> Option<PRChangedFile> {
         path: RelPath::new(new_path_str),
         old_path,
         kind,
+        old_mode,
+        new_mode,
+        similarity,
         additions,
         deletions,
         patch: chunk.to_string(),
@@ -213,6 +426,42 @@ fn find_closing_quote(s: &str, start: usize) -> Option<usize> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn split_patch_maps_lines_to_file_positions() {
+        let patch = "diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -1,2 +1,2 @@\n a\n-b\n+B\n@@ -10,2 +10,3 @@\n x\n+y\n z\n";
+        let sides = split_patch(patch);
+        assert_eq!(sides.old, "a\nb\nx\nz");
+        assert_eq!(sides.new, "a\nB\nx\ny\nz");
+        let lines = |v: &[PatchLine]| v.iter().map(|l| (l.file_line, l.hunk)).collect::<Vec<_>>();
+        assert_eq!(lines(&sides.old_lines), [(1, 0), (2, 0), (10, 1), (11, 1)]);
+        assert_eq!(
+            lines(&sides.new_lines),
+            [(1, 0), (2, 0), (10, 1), (11, 1), (12, 1)]
+        );
+    }
+
+    #[test]
+    fn split_patch_with_gaps_marks_skipped_lines() {
+        let patch = "diff --git a/f b/f\n--- a/f\n+++ b/f\n@@ -3,2 +3,2 @@\n a\n-b\n+B\n@@ -10,1 +10,2 @@\n x\n+y\n";
+        let sides = split_patch_with_gaps(patch);
+        assert_eq!(
+            sides.new,
+            "⋯ 2 unchanged lines\na\nB\n⋯ 5 unchanged lines\nx\ny"
+        );
+        assert_eq!(
+            sides.old,
+            "⋯ 2 unchanged lines\na\nb\n⋯ 5 unchanged lines\nx"
+        );
+        let numbers: Vec<_> = (0..6)
+            .map(|line| sides.file_line(DiffSide::New, line))
+            .collect();
+        assert_eq!(numbers, [None, Some(3), Some(4), None, Some(10), Some(11)]);
+        assert_eq!(sides.new_lines[3].hunk, 1);
+        assert_eq!(sides.content_line(DiffSide::Old, 10), Some(4));
+        assert_eq!(sides.content_line(DiffSide::Old, 5), None);
+        assert_eq!(sides.max_file_line(), 11);
+    }
+
     #[test]
     fn parse_simple_diff() {
         let diff = r#"diff --git a/src/main.rs b/src/main.rs
@@ -288,12 +537,32 @@ index abc123..def456 100644
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].path.as_str(), "new_name.rs");
         assert_eq!(files[0].kind, FileChangeKind::Renamed);
+        assert_eq!(files[0].similarity, Some(95));
         assert_eq!(
             files[0].old_path.as_ref().map(|p| p.as_str()),
             Some("old_name.rs")
         );
     }
 
+    #[test]
+    fn parse_copy() {
+        let diff = r#"diff --git a/src/a.rs b/src/b.rs
+similarity index 88%
+copy from src/a.rs
+copy to src/b.rs
+index abc123..def456 100644
+--- a/src/a.rs
++++ b/src/b.rs
+@@ -1 +1 @@
+-a
++b
+"#;
+        let files = parse_unified_diff(diff);
+        assert_eq!(files[0].kind, FileChangeKind::Copied);
+        assert_eq!(files[0].old_path.as_ref().unwrap().as_str(), "src/a.rs");
+        assert_eq!(files[0].similarity, Some(88));
+    }
+
     #[test]
     fn parse_multiple_files() {
         let diff = r#"diff --git a/a.rs b/a.rs
@@ -379,4 +648,44 @@ index 0000000..abc123
         // Unquoted strings pass through
         assert_eq!(unquote_path("unquoted"), "unquoted");
     }
+
+    #[test]
+    fn parse_mode_change() {
+        let diff = r#"diff --git a/run.sh b/run.sh
+old mode 100644
+new mode 100755
+diff --git a/link b/link
+deleted file mode 100644
+index abc123..0000000
+--- a/link
++++ /dev/null
+@@ -1 +0,0 @@
+-target
+"#;
+        let files = parse_unified_diff(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path.as_str(), "link");
+        assert_eq!(files[0].old_mode, Some(FileMode::Regular));
+        assert_eq!(files[0].new_mode, None);
+        assert_eq!(files[1].path.as_str(), "run.sh");
+        assert_eq!(files[1].kind, FileChangeKind::Modified);
+        assert_eq!(files[1].old_mode, Some(FileMode::Regular));
+        assert_eq!(files[1].new_mode, Some(FileMode::Executable));
+        assert_eq!(files[1].additions + files[1].deletions, 0);
+    }
+
+    #[test]
+    fn parse_unchanged_mode_from_index_line() {
+        let diff = r#"diff --git a/src/main.rs b/src/main.rs
+index abc123..def456 100755
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1 +1 @@
+-a
++b
+"#;
+        let files = parse_unified_diff(diff);
+        assert_eq!(files[0].old_mode, Some(FileMode::Executable));
+        assert_eq!(files[0].new_mode, Some(FileMode::Executable));
+    }
 }
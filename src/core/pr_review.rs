@@ -0,0 +1,459 @@
+//! Map local comments onto a GitHub pull request review.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    Comment, CommentId, DiffResult, DiffSide, PRChangedFile, PatchSides, RelPath, ReviewThread,
+    TextBuffer, relocate_anchor, split_patch_with_gaps,
+};
+
+/// Overall verdict of a submitted review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewEvent {
+    /// Comment without a verdict.
+    #[default]
+    Comment,
+    /// Approve the PR.
+    Approve,
+    /// Request changes.
+    RequestChanges,
+}
+
+/// Side of the PR diff an inline comment is on, as GitHub names it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ReviewSide {
+    /// Old (base) side.
+    Left,
+    /// New (head) side.
+    Right,
+}
+
+impl From<ReviewSide> for DiffSide {
+    fn from(side: ReviewSide) -> Self {
+        match side {
+            ReviewSide::Left => Self::Old,
+            ReviewSide::Right => Self::New,
+        }
+    }
+}
+
+impl From<DiffSide> for ReviewSide {
+    fn from(side: DiffSide) -> Self {
+        match side {
+            DiffSide::Old => Self::Left,
+            DiffSide::New => Self::Right,
+        }
+    }
+}
+
+/// An inline comment in a review, in GitHub's pull request review format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReviewInlineComment {
+    /// Path of the file in the PR.
+    pub path: String,
+    /// Comment body (markdown).
+    pub body: String,
+    /// Last line the comment covers, in the file on `side`.
+    pub line: u32,
+    /// Side of `line`.
+    pub side: ReviewSide,
+    /// First line of a multi-line comment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<u32>,
+    /// Side of `start_line`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_side: Option<ReviewSide>,
+}
+
+/// A review ready to send to GitHub.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PullRequestReview {
+    /// Verdict.
+    pub event: ReviewEvent,
+    /// Summary body; may be empty for a comment-only review.
+    pub body: String,
+    /// Inline comments.
+    pub comments: Vec<ReviewInlineComment>,
+}
+
+/// A local comment that could not be placed in the PR diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmappedComment {
+    /// Comment id.
+    pub id: CommentId,
+    /// File the comment is on.
+    pub path: RelPath,
+    /// Why it could not be placed.
+    pub reason: &'static str,
+}
+
+/// Result of [`map_review_comments`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReviewMapping {
+    /// Inline comments, in input order.
+    pub comments: Vec<ReviewInlineComment>,
+    /// Ids of the comments in `comments`, index for index.
+    pub ids: Vec<CommentId>,
+    /// Comments left out of the review.
+    pub unmapped: Vec<UnmappedComment>,
+}
+
+/// Place local comments on the lines of a PR diff.
+///
+/// Each comment is relocated in the content the PR view shows for its file:
+/// the whole old and new files when `full_contents` returns them, otherwise
+/// the patch hunks from [`split_patch_with_gaps`]. Its lines are then
+/// translated to file line numbers, leaving out lines at either end that the
+/// patch does not show. GitHub only accepts lines inside a single hunk;
+/// comments that fall outside the diff or span hunks are returned in
+/// `unmapped`, as are suggestions on old-side lines or on a range the patch
+/// only partly shows, since GitHub would replace different lines than the
+/// ones the suggestion was written for.
+pub fn map_review_comments(
+    comments: &[&Comment],
+    files: &[PRChangedFile],
+    mut full_contents: impl FnMut(&PRChangedFile) -> Option<(String, String)>,
+) -> ReviewMapping {
+    let mut mapping = ReviewMapping::default();
+    for comment in comments {
+        match map_comment(comment, files, &mut full_contents) {
+            Ok(inline) => {
+                mapping.comments.push(inline);
+                mapping.ids.push(comment.id);
+            }
+            Err(reason) => mapping.unmapped.push(UnmappedComment {
+                id: comment.id,
+                path: comment.path.clone(),
+                reason,
+            }),
+        }
+    }
+    mapping
+}
+
+fn map_comment(
+    comment: &Comment,
+    files: &[PRChangedFile],
+    full_contents: &mut impl FnMut(&PRChangedFile) -> Option<(String, String)>,
+) -> Result<ReviewInlineComment, &'static str> {
+    let file = files
+        .iter()
+        .find(|f| f.path == comment.path)
+        .ok_or("file is not part of the PR")?;
+    let sides = split_patch_with_gaps(&file.patch);
+    let full = full_contents(file);
+    let (old_text, new_text) = match &full {
+        Some((old, new)) => (old.as_str(), new.as_str()),
+        None => (sides.old.as_str(), sides.new.as_str()),
+    };
+    let old = TextBuffer::new(old_text.as_bytes());
+    let new = TextBuffer::new(new_text.as_bytes());
+    let diff = DiffResult::compute(&old, &new);
+    let found =
+        relocate_anchor(&comment.anchor, &diff, &old, &new).ok_or("lines are not in the diff")?;
+
+    let file_line = |line: usize| match full {
+        Some(_) => Some(line as u32 + 1),
+        None => sides.file_line(found.side, line),
+    };
+    // Context around a hunk comment may reach past what the patch shows.
+    let covered: Vec<(u32, usize)> = (found.start_line..=found.end_line)
+        .filter_map(|line| {
+            let file_line = file_line(line)?;
+            let line = sides.content_line(found.side, file_line)?;
+            Some((file_line, sides.lines(found.side)[line].hunk))
+        })
+        .collect();
+    let (Some(&(start, start_hunk)), Some(&(end, end_hunk))) = (covered.first(), covered.last())
+    else {
+        return Err("lines are not in the diff");
+    };
+    if start_hunk != end_hunk {
+        return Err("lines span several hunks");
+    }
+    if comment.suggestion.is_some() {
+        if found.side == DiffSide::Old {
+            return Err("suggestions can only replace new-side lines");
+        }
+        if covered.len() != found.end_line - found.start_line + 1 {
+            return Err("suggestion covers lines the diff does not show");
+        }
+    }
+
+    let side = ReviewSide::from(found.side);
+    let multi_line = start != end;
+    Ok(ReviewInlineComment {
+        path: comment.path.as_str().to_string(),
+        body: review_body(comment),
+        line: end,
+        side,
+        start_line: multi_line.then_some(start),
+        start_side: multi_line.then_some(side),
+    })
+}
+
+/// Lines a GitHub review thread covers in the content shown for its file.
+///
+/// `sides` is the patch content from [`split_patch_with_gaps`], or `None`
+/// when the view shows whole files. Returns the side and the 0-based,
+/// inclusive span, or `None` for outdated threads and lines the view does
+/// not show.
+pub fn place_review_thread(
+    thread: &ReviewThread,
+    sides: Option<&PatchSides>,
+) -> Option<(DiffSide, usize, usize)> {
+    if thread.is_outdated {
+        return None;
+    }
+    let end_line = thread.line?;
+    let start_line = thread.start_line.unwrap_or(end_line);
+    let side = DiffSide::from(thread.side);
+    let Some(sides) = sides else {
+        let end = (end_line as usize).checked_sub(1)?;
+        let start = (start_line as usize).checked_sub(1).unwrap_or(end).min(end);
+        return Some((side, start, end));
+    };
+    let end = sides.content_line(side, end_line)?;
+    let start = sides
+        .content_line(side, start_line)
+        .filter(|&start| start <= end)
+        .unwrap_or(end);
+    Some((side, start, end))
+}
+
+/// Comment message with its suggested change as a GitHub suggestion block.
+fn review_body(comment: &Comment) -> String {
+    let message = comment.message.trim_end();
+    match &comment.suggestion {
+        Some(suggestion) => {
+            let block = if suggestion.is_empty() {
+                "```suggestion\n```".to_string()
+            } else {
+                format!("```suggestion\n{}\n```", suggestion.trim_end_matches('\n'))
+            };
+            if message.is_empty() {
+                block
+            } else {
+                format!("{}\n\n{}", message, block)
+            }
+        }
+        None => message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        Anchor, CommentContext, CommentStatus, Selector, parse_unified_diff,
+        selector_from_line_range,
+    };
+
+    const PATCH: &str = "diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn a() {
+-    1
++    2
+ }
+@@ -20,2 +20,3 @@
+ fn b() {
++    3
+ }
+";
+
+    fn comment(id: u64, path: &str, side: DiffSide, start: usize, end: usize) -> Comment {
+        let sides = split_patch_with_gaps(PATCH);
+        let text = match side {
+            DiffSide::Old => sides.old,
+            DiffSide::New => sides.new,
+        };
+        comment_in(&text, id, path, side, start, end)
+    }
+
+    fn comment_in(
+        text: &str,
+        id: u64,
+        path: &str,
+        side: DiffSide,
+        start: usize,
+        end: usize,
+    ) -> Comment {
+        let selector =
+            selector_from_line_range(&TextBuffer::new(text.as_bytes()), side, start, end).unwrap();
+        Comment {
+            id,
+            path: RelPath::new(path),
+            context: CommentContext::PullRequest { number: 1 },
+            author: None,
+            message: format!("comment {}", id),
+            suggestion: None,
+            kind: None,
+            status: CommentStatus::Open,
+            anchor: Anchor {
+                selectors: vec![Selector::LineRangeV1(selector)],
+            },
+            created_at_ms: None,
+            resolved_at_ms: None,
+            replies: Vec::new(),
+            edits: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn maps_patch_lines_to_file_lines() {
+        let files = parse_unified_diff(PATCH);
+        // Line 3 of the patch content is the gap separator between the hunks.
+        let mut suggested = comment(2, "src/lib.rs", DiffSide::New, 4, 5);
+        suggested.suggestion = Some("    3;".to_string());
+        let comments = [
+            comment(1, "src/lib.rs", DiffSide::Old, 1, 1),
+            suggested,
+            comment(3, "src/lib.rs", DiffSide::New, 1, 4),
+            comment(4, "README.md", DiffSide::New, 0, 0),
+            comment(5, "src/lib.rs", DiffSide::New, 3, 3),
+        ];
+        let refs: Vec<&Comment> = comments.iter().collect();
+        let mapping = map_review_comments(&refs, &files, |_| None);
+
+        assert_eq!(mapping.ids, [1, 2]);
+        assert_eq!(mapping.comments[0].line, 2);
+        assert_eq!(mapping.comments[0].side, ReviewSide::Left);
+        assert_eq!(mapping.comments[0].start_line, None);
+        assert_eq!(mapping.comments[1].start_line, Some(20));
+        assert_eq!(mapping.comments[1].line, 21);
+        assert_eq!(mapping.comments[1].start_side, Some(ReviewSide::Right));
+        assert_eq!(
+            mapping.comments[1].body,
+            "comment 2\n\n```suggestion\n    3;\n```"
+        );
+
+        let reasons: Vec<_> = mapping.unmapped.iter().map(|u| (u.id, u.reason)).collect();
+        assert_eq!(
+            reasons,
+            [
+                (3, "lines span several hunks"),
+                (4, "file is not part of the PR"),
+                (5, "lines are not in the diff")
+            ]
+        );
+    }
+
+    #[test]
+    fn refuses_suggestions_it_cannot_place() {
+        let files = parse_unified_diff(PATCH);
+        let suggest = |mut comment: Comment| {
+            comment.suggestion = Some("    3;".to_string());
+            comment
+        };
+        // Line 3 is the gap separator, so only lines 4..=5 reach the patch.
+        let comments = [
+            suggest(comment(1, "src/lib.rs", DiffSide::New, 3, 5)),
+            suggest(comment(2, "src/lib.rs", DiffSide::Old, 1, 1)),
+            comment(3, "src/lib.rs", DiffSide::New, 3, 5),
+        ];
+        let refs: Vec<&Comment> = comments.iter().collect();
+        let mapping = map_review_comments(&refs, &files, |_| None);
+
+        // Without a suggestion the shortened range is still worth posting.
+        assert_eq!(mapping.ids, [3]);
+        assert_eq!(mapping.comments[0].start_line, Some(20));
+        assert_eq!(mapping.comments[0].line, 21);
+        let reasons: Vec<_> = mapping.unmapped.iter().map(|u| (u.id, u.reason)).collect();
+        assert_eq!(
+            reasons,
+            [
+                (1, "suggestion covers lines the diff does not show"),
+                (2, "suggestions can only replace new-side lines")
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_full_file_lines() {
+        let files = parse_unified_diff(PATCH);
+        let file = |middle: &str, last: &str| {
+            let mut lines = vec!["fn a() {", middle, "}"];
+            lines.extend(std::iter::repeat_n("// filler", 16));
+            lines.extend(["fn b() {", last, "}"]);
+            lines.join("\n")
+        };
+        let old = file("    1", "}");
+        let new = file("    2", "    3");
+        let comments = [
+            comment_in(&new, 1, "src/lib.rs", DiffSide::New, 19, 20),
+            comment_in(&new, 2, "src/lib.rs", DiffSide::New, 9, 9),
+        ];
+        let refs: Vec<&Comment> = comments.iter().collect();
+        let mapping = map_review_comments(&refs, &files, |_| Some((old.clone(), new.clone())));
+
+        assert_eq!(mapping.ids, [1]);
+        assert_eq!(mapping.comments[0].start_line, Some(20));
+        assert_eq!(mapping.comments[0].line, 21);
+        assert_eq!(mapping.unmapped[0].reason, "lines are not in the diff");
+    }
+
+    #[test]
+    fn places_threads_on_patch_lines() {
+        let sides = split_patch_with_gaps(PATCH);
+        let sides = Some(&sides);
+        let thread = |line, start_line, side, is_outdated| ReviewThread {
+            path: RelPath::new("src/lib.rs"),
+            side,
+            line,
+            start_line,
+            is_resolved: false,
+            is_outdated,
+            comments: Vec::new(),
+        };
+        assert_eq!(
+            place_review_thread(&thread(Some(21), Some(20), ReviewSide::Right, false), sides),
+            Some((DiffSide::New, 4, 5))
+        );
+        // Whole files are shown line for line.
+        assert_eq!(
+            place_review_thread(&thread(Some(21), Some(20), ReviewSide::Right, false), None),
+            Some((DiffSide::New, 19, 20))
+        );
+        assert_eq!(
+            place_review_thread(&thread(Some(2), None, ReviewSide::Left, false), sides),
+            Some((DiffSide::Old, 1, 1))
+        );
+        // Outside the patch, or outdated.
+        assert_eq!(
+            place_review_thread(&thread(Some(50), None, ReviewSide::Right, false), sides),
+            None
+        );
+        assert_eq!(
+            place_review_thread(&thread(Some(2), None, ReviewSide::Right, true), sides),
+            None
+        );
+    }
+
+    #[test]
+    fn review_serializes_in_github_format() {
+        let review = PullRequestReview {
+            event: ReviewEvent::RequestChanges,
+            body: String::new(),
+            comments: vec![ReviewInlineComment {
+                path: "a.rs".to_string(),
+                body: "x".to_string(),
+                line: 3,
+                side: ReviewSide::Right,
+                start_line: None,
+                start_side: None,
+            }],
+        };
+        assert_eq!(
+            serde_json::to_value(&review).unwrap(),
+            serde_json::json!({
+                "event": "REQUEST_CHANGES",
+                "body": "",
+                "comments": [{"path": "a.rs", "body": "x", "line": 3, "side": "RIGHT"}]
+            })
+        );
+    }
+}
@@ -0,0 +1,449 @@
+//! Relocating comment anchors onto a diff that has moved on since the comment was made.
+//!
+//! Strategies run from most to least certain:
+//! 1. exact hunk digest / line fingerprint at the recorded position,
+//! 2. the recorded lines and their context searched in a window around the old position,
+//! 3. the recorded new-side lines found on the old side (the change landed in the base,
+//!    e.g. after a rebase or commit).
+
+use serde::Serialize;
+
+use crate::core::{
+    Anchor, ContextSelectorV1, DiffResult, DiffSide, LineRangeSelectorV1, Selector, TextBuffer,
+    hunk_line_span, row_for_line, selector_from_line_range,
+};
+
+/// How far (in lines) the context-window strategy looks around the old position.
+const RELOCATE_WINDOW: usize = 200;
+
+/// Minimum combined score for a fuzzy context-window match.
+const MIN_FUZZY_SCORE: f32 = 0.6;
+
+/// How sure the relocator is that a comment still points at the intended code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnchorConfidence {
+    /// Only found on the old side after the change landed.
+    Low,
+    /// Found nearby, but the lines or their surroundings changed.
+    Medium,
+    /// The same lines with matching surroundings, at a different position.
+    High,
+    /// The recorded hunk or lines are unchanged and in place.
+    Exact,
+}
+
+impl AnchorConfidence {
+    /// Lowercase name (`exact`, `high`, ...).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnchorConfidence::Low => "low",
+            AnchorConfidence::Medium => "medium",
+            AnchorConfidence::High => "high",
+            AnchorConfidence::Exact => "exact",
+        }
+    }
+}
+
+/// Whether a comment could be placed on the current diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnchorStatus {
+    /// Found exactly where it was made.
+    Anchored,
+    /// Found somewhere else.
+    Relocated,
+    /// Not found.
+    Stale,
+}
+
+impl AnchorStatus {
+    /// Status for the outcome of [`relocate_anchor`].
+    pub fn of(found: Option<&AnchorMatch>) -> Self {
+        match found {
+            Some(m) if m.confidence == AnchorConfidence::Exact => AnchorStatus::Anchored,
+            Some(_) => AnchorStatus::Relocated,
+            None => AnchorStatus::Stale,
+        }
+    }
+
+    /// Lowercase name (`anchored`, `relocated`, `stale`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnchorStatus::Anchored => "anchored",
+            AnchorStatus::Relocated => "relocated",
+            AnchorStatus::Stale => "stale",
+        }
+    }
+}
+
+/// Strategy that located a comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnchorStrategy {
+    /// Hunk digest matched a current hunk.
+    Digest,
+    /// Line fingerprint matched at the recorded position.
+    Fingerprint,
+    /// Lines found near the recorded position.
+    ContextWindow,
+    /// New-side lines found on the old side.
+    OldSide,
+}
+
+/// Where a comment lands on the current diff.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AnchorMatch {
+    /// Strategy that produced the match.
+    pub strategy: AnchorStrategy,
+    /// Confidence in the match.
+    pub confidence: AnchorConfidence,
+    /// Side the lines are on.
+    pub side: DiffSide,
+    /// First matched line (0-indexed).
+    pub start_line: usize,
+    /// Last matched line (0-indexed, inclusive).
+    pub end_line: usize,
+    /// Render row showing `start_line`, if it is part of the diff.
+    pub row: Option<usize>,
+    /// Matched hunk index, for whole-hunk anchors that matched by digest.
+    #[serde(skip)]
+    pub hunk: Option<usize>,
+}
+
+impl AnchorMatch {
+    /// Whether `line` on `side` is covered by the match.
+    pub fn contains(&self, side: DiffSide, line: usize) -> bool {
+        self.side == side && (self.start_line..=self.end_line).contains(&line)
+    }
+}
+
+/// Place an anchor on the current diff, trying each strategy in turn.
+///
+/// Returns `None` when the comment is stale.
+pub fn relocate_anchor(
+    anchor: &Anchor,
+    diff: &DiffResult,
+    old: &TextBuffer,
+    new: &TextBuffer,
+) -> Option<AnchorMatch> {
+    let buffer_for = |side| match side {
+        DiffSide::Old => old,
+        DiffSide::New => new,
+    };
+    let context = anchor.selectors.iter().find_map(|sel| match sel {
+        Selector::ContextV1(ctx) => Some(ctx),
+        _ => None,
+    });
+
+    // 1. Exact: selectors are tried in order.
+    for sel in &anchor.selectors {
+        match sel {
+            Selector::LineRangeV1(r) if r.matches(buffer_for(r.side)) => {
+                return Some(lines_match(
+                    diff,
+                    AnchorStrategy::Fingerprint,
+                    AnchorConfidence::Exact,
+                    r.side,
+                    r.start_line,
+                    r.end_line,
+                ));
+            }
+            Selector::DiffHunkV1(h) => {
+                let Some((idx, hunk)) = diff
+                    .hunks()
+                    .iter()
+                    .enumerate()
+                    .find(|(_, hunk)| hunk.digest_hex == h.digest_hex)
+                else {
+                    continue;
+                };
+                let (side, start_line, end_line) = hunk_line_span(hunk);
+                return Some(AnchorMatch {
+                    strategy: AnchorStrategy::Digest,
+                    confidence: AnchorConfidence::Exact,
+                    side,
+                    start_line,
+                    end_line,
+                    row: Some(hunk.start_row),
+                    hunk: Some(idx),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    // 2. Context window around the recorded position.
+    if let Some(ctx) = context
+        && let Some((start, confidence)) = search_context_window(ctx, buffer_for(ctx.side))
+    {
+        return Some(lines_match(
+            diff,
+            AnchorStrategy::ContextWindow,
+            confidence,
+            ctx.side,
+            start,
+            start + ctx.lines.len() - 1,
+        ));
+    }
+
+    // Older range comments carry no text; look for the same fingerprint nearby.
+    if context.is_none()
+        && let Some(r) = anchor.selectors.iter().find_map(|sel| match sel {
+            Selector::LineRangeV1(r) => Some(r),
+            _ => None,
+        })
+        && let Some(start) = search_fingerprint_window(r, buffer_for(r.side))
+    {
+        return Some(lines_match(
+            diff,
+            AnchorStrategy::ContextWindow,
+            AnchorConfidence::High,
+            r.side,
+            start,
+            start + (r.end_line - r.start_line),
+        ));
+    }
+
+    // 3. New-side lines that now live on the old side.
+    let ctx = context.filter(|ctx| ctx.side == DiffSide::New)?;
+    let start = find_lines_nearest(&ctx.lines, old, ctx.start_line)?;
+    Some(lines_match(
+        diff,
+        AnchorStrategy::OldSide,
+        AnchorConfidence::Low,
+        DiffSide::Old,
+        start,
+        start + ctx.lines.len() - 1,
+    ))
+}
+
+fn lines_match(
+    diff: &DiffResult,
+    strategy: AnchorStrategy,
+    confidence: AnchorConfidence,
+    side: DiffSide,
+    start_line: usize,
+    end_line: usize,
+) -> AnchorMatch {
+    AnchorMatch {
+        strategy,
+        confidence,
+        side,
+        start_line,
+        end_line,
+        row: row_for_line(diff, side, start_line),
+        hunk: None,
+    }
+}
+
+/// Compare a recorded line against a buffer line, ignoring surrounding whitespace.
+fn same_line(recorded: &str, buffer: &TextBuffer, line: usize) -> bool {
+    buffer
+        .line(line)
+        .is_some_and(|bytes| String::from_utf8_lossy(bytes).trim() == recorded.trim())
+}
+
+fn window(center: usize, len: usize, buffer: &TextBuffer) -> std::ops::RangeInclusive<usize> {
+    let last_start = buffer.line_count().saturating_sub(len);
+    center.saturating_sub(RELOCATE_WINDOW).min(last_start)
+        ..=center.saturating_add(RELOCATE_WINDOW).min(last_start)
+}
+
+/// Best position for `ctx` within the window, scored by target and context line matches.
+fn search_context_window(
+    ctx: &ContextSelectorV1,
+    buffer: &TextBuffer,
+) -> Option<(usize, AnchorConfidence)> {
+    let len = ctx.lines.len();
+    if len == 0 || len > buffer.line_count() {
+        return None;
+    }
+
+    let mut best: Option<(f32, usize, usize, AnchorConfidence)> = None;
+    for pos in window(ctx.start_line, len, buffer) {
+        let target_hits = ctx
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(i, line)| same_line(line, buffer, pos + i))
+            .count();
+        let before_hits = ctx
+            .before
+            .iter()
+            .rev()
+            .enumerate()
+            .filter(|(i, line)| pos > *i && same_line(line, buffer, pos - i - 1))
+            .count();
+        let after_hits = ctx
+            .after
+            .iter()
+            .enumerate()
+            .filter(|(i, line)| same_line(line, buffer, pos + len + i))
+            .count();
+
+        let target = target_hits as f32 / len as f32;
+        let context_total = ctx.before.len() + ctx.after.len();
+        let context = if context_total == 0 {
+            1.0
+        } else {
+            (before_hits + after_hits) as f32 / context_total as f32
+        };
+        let score = 0.7 * target + 0.3 * context;
+        if score < MIN_FUZZY_SCORE {
+            continue;
+        }
+
+        let confidence = if target_hits == len && context >= 0.5 {
+            AnchorConfidence::High
+        } else {
+            AnchorConfidence::Medium
+        };
+        let distance = pos.abs_diff(ctx.start_line);
+        let better = best.is_none_or(|(best_score, best_distance, _, _)| {
+            score > best_score || (score == best_score && distance < best_distance)
+        });
+        if better {
+            best = Some((score, distance, pos, confidence));
+        }
+    }
+    best.map(|(_, _, pos, confidence)| (pos, confidence))
+}
+
+/// Nearest position in the window where the range's fingerprint matches again.
+fn search_fingerprint_window(range: &LineRangeSelectorV1, buffer: &TextBuffer) -> Option<usize> {
+    let len = range.end_line.checked_sub(range.start_line)? + 1;
+    if len > buffer.line_count() {
+        return None;
+    }
+    window(range.start_line, len, buffer)
+        .filter(|&pos| {
+            selector_from_line_range(buffer, range.side, pos, pos + len - 1)
+                .is_some_and(|candidate| candidate.fingerprint_hex == range.fingerprint_hex)
+        })
+        .min_by_key(|pos| pos.abs_diff(range.start_line))
+}
+
+/// Nearest position anywhere in `buffer` where all `lines` appear in order.
+fn find_lines_nearest(lines: &[String], buffer: &TextBuffer, near: usize) -> Option<usize> {
+    if lines.is_empty() || lines.len() > buffer.line_count() {
+        return None;
+    }
+    (0..=buffer.line_count() - lines.len())
+        .filter(|&pos| {
+            lines
+                .iter()
+                .enumerate()
+                .all(|(i, line)| same_line(line, buffer, pos + i))
+        })
+        .min_by_key(|pos| pos.abs_diff(near))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{selector_from_context, selector_from_hunk};
+
+    fn anchor_for_lines(buffer: &TextBuffer, side: DiffSide, start: usize, end: usize) -> Anchor {
+        Anchor {
+            selectors: vec![
+                Selector::LineRangeV1(selector_from_line_range(buffer, side, start, end).unwrap()),
+                Selector::ContextV1(selector_from_context(buffer, side, start, end).unwrap()),
+            ],
+        }
+    }
+
+    #[test]
+    fn exact_digest_match_keeps_hunk() {
+        let old = TextBuffer::new(b"a\nb\nc\n");
+        let new = TextBuffer::new(b"a\nB\nc\n");
+        let diff = DiffResult::compute(&old, &new);
+        let anchor = Anchor {
+            selectors: vec![Selector::DiffHunkV1(selector_from_hunk(&diff, 0).unwrap())],
+        };
+
+        let found = relocate_anchor(&anchor, &diff, &old, &new).unwrap();
+        assert_eq!(found.strategy, AnchorStrategy::Digest);
+        assert_eq!(found.confidence, AnchorConfidence::Exact);
+        assert_eq!(found.hunk, Some(0));
+        assert_eq!(AnchorStatus::of(Some(&found)), AnchorStatus::Anchored);
+    }
+
+    #[test]
+    fn moved_lines_relocate_with_high_confidence() {
+        let old = TextBuffer::new(b"x\n");
+        let new = TextBuffer::new(b"fn a() {\n    one();\n    two();\n}\nend\n");
+        let anchor = anchor_for_lines(&new, DiffSide::New, 1, 2);
+
+        // Three lines inserted above the commented code.
+        let moved =
+            TextBuffer::new(b"use a;\nuse b;\n\nfn a() {\n    one();\n    two();\n}\nend\n");
+        let diff = DiffResult::compute(&old, &moved);
+        let found = relocate_anchor(&anchor, &diff, &old, &moved).unwrap();
+        assert_eq!(found.strategy, AnchorStrategy::ContextWindow);
+        assert_eq!(found.confidence, AnchorConfidence::High);
+        assert_eq!((found.start_line, found.end_line), (4, 5));
+        assert!(found.row.is_some());
+        assert_eq!(AnchorStatus::of(Some(&found)), AnchorStatus::Relocated);
+    }
+
+    #[test]
+    fn edited_lines_relocate_with_medium_confidence() {
+        let old = TextBuffer::new(b"x\n");
+        let new = TextBuffer::new(b"a\nb\nkeep();\nalso();\nc\nd\n");
+        let anchor = anchor_for_lines(&new, DiffSide::New, 2, 3);
+
+        let edited = TextBuffer::new(b"a\nb\nkeep();\nchanged();\nc\nd\n");
+        let diff = DiffResult::compute(&old, &edited);
+        let found = relocate_anchor(&anchor, &diff, &old, &edited).unwrap();
+        assert_eq!(found.strategy, AnchorStrategy::ContextWindow);
+        assert_eq!(found.confidence, AnchorConfidence::Medium);
+        assert_eq!(found.start_line, 2);
+    }
+
+    #[test]
+    fn rebased_change_is_found_on_old_side() {
+        let new = TextBuffer::new(b"base\nadded();\n");
+        let anchor = anchor_for_lines(&new, DiffSide::New, 1, 1);
+
+        // The change is now part of the base and the new side moved on.
+        let rebased_old = TextBuffer::new(b"base\nadded();\n");
+        let rebased_new = TextBuffer::new(b"completely\ndifferent\n");
+        let diff = DiffResult::compute(&rebased_old, &rebased_new);
+        let found = relocate_anchor(&anchor, &diff, &rebased_old, &rebased_new).unwrap();
+        assert_eq!(found.strategy, AnchorStrategy::OldSide);
+        assert_eq!(found.confidence, AnchorConfidence::Low);
+        assert_eq!(found.side, DiffSide::Old);
+        assert_eq!(found.start_line, 1);
+    }
+
+    #[test]
+    fn unrelated_content_is_stale() {
+        let old = TextBuffer::new(b"x\n");
+        let new = TextBuffer::new(b"a\nb\nc\n");
+        let anchor = anchor_for_lines(&new, DiffSide::New, 1, 1);
+
+        let other = TextBuffer::new(b"p\nq\nr\n");
+        let diff = DiffResult::compute(&old, &other);
+        let found = relocate_anchor(&anchor, &diff, &old, &other);
+        assert!(found.is_none());
+        assert_eq!(AnchorStatus::of(found.as_ref()), AnchorStatus::Stale);
+    }
+
+    #[test]
+    fn legacy_range_without_context_scans_fingerprint() {
+        let old = TextBuffer::new(b"x\n");
+        let new = TextBuffer::new(b"a\nb\nc\n");
+        let anchor = Anchor {
+            selectors: vec![Selector::LineRangeV1(
+                selector_from_line_range(&new, DiffSide::New, 1, 2).unwrap(),
+            )],
+        };
+
+        let shifted = TextBuffer::new(b"z\na\nb\nc\n");
+        let diff = DiffResult::compute(&old, &shifted);
+        let found = relocate_anchor(&anchor, &diff, &old, &shifted).unwrap();
+        assert_eq!(found.confidence, AnchorConfidence::High);
+        assert_eq!(found.start_line, 2);
+    }
+}
@@ -0,0 +1,343 @@
+//! Similarity-based rename and copy detection shared by the git and jj backends.
+//!
+//! Backends list raw added/deleted/modified files; [`detect_renames`] pairs added
+//! files with deleted (rename) or modified (copy) sources whose contents are at
+//! least `threshold` percent similar, the same way for every VCS.
+
+use std::collections::HashMap;
+
+use crate::core::{ChangedFile, FileChangeKind, RelPath};
+
+/// Default similarity threshold in percent (matches git's `-M50%`).
+pub const DEFAULT_RENAME_THRESHOLD: u8 = 50;
+
+/// Upper bound on compared (target, source) pairs, like git's `diff.renameLimit`.
+const MAX_RENAME_PAIRS: usize = 250_000;
+
+/// Rename/copy detection settings for revision-range file lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenameDetection {
+    /// Minimum content similarity in percent (0-100) for a pair to count.
+    pub threshold: u8,
+    /// Also detect copies from files modified in the same change (off by
+    /// default, like git, since it compares every added file with every
+    /// modified one).
+    pub copies: bool,
+}
+
+impl Default for RenameDetection {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_RENAME_THRESHOLD,
+            copies: false,
+        }
+    }
+}
+
+/// Share of lines common to both sides, relative to the larger side.
+///
+/// Scored like git: bytes of lines shared by both sides divided by the size
+/// of the larger side.
+///
+/// # Examples
+///
+/// ```
+/// use quickdiff::core::similarity_index;
+///
+/// assert_eq!(similarity_index(b"a\nb\n", b"a\nb\n"), 100);
+/// assert_eq!(similarity_index(b"a\nb\n", b"a\nc\n"), 50);
+/// assert_eq!(similarity_index(b"a\n", b""), 0);
+/// ```
+#[must_use]
+pub fn similarity_index(old: &[u8], new: &[u8]) -> u8 {
+    if old == new {
+        return 100;
+    }
+    let larger = old.len().max(new.len());
+    if old.is_empty() || new.is_empty() {
+        return 0;
+    }
+
+    let mut remaining: HashMap<&[u8], usize> = HashMap::new();
+    for line in old.split_inclusive(|b| *b == b'\n') {
+        *remaining.entry(line).or_insert(0) += 1;
+    }
+
+    let mut shared = 0usize;
+    for line in new.split_inclusive(|b| *b == b'\n') {
+        if let Some(count) = remaining.get_mut(line)
+            && *count > 0
+        {
+            *count -= 1;
+            shared += line.len();
+        }
+    }
+
+    ((shared * 100) / larger).min(100) as u8
+}
+
+/// Pair added or untracked files with deleted or modified sources by content
+/// similarity.
+///
+/// Deleted sources become renames (the deleted entry is dropped); further matches
+/// against an already-renamed source, and matches against modified files, become
+/// copies when `opts.copies` is set. `load_old` reads a path on the old side,
+/// `load_new` on the new side; returning `None` excludes the file from pairing.
+/// Empty files are never paired, as in git: they would all match each other.
+pub(crate) fn detect_renames<L, R>(
+    files: Vec<ChangedFile>,
+    opts: &RenameDetection,
+    mut load_old: L,
+    mut load_new: R,
+) -> Vec<ChangedFile>
+where
+    L: FnMut(&RelPath) -> Option<Vec<u8>>,
+    R: FnMut(&RelPath) -> Option<Vec<u8>>,
+{
+    // An unstaged `mv` leaves the new path untracked.
+    let targets: Vec<usize> = indices_of(&files, |k| {
+        k == FileChangeKind::Added || k == FileChangeKind::Untracked
+    });
+    let sources: Vec<usize> = indices_of(&files, |k| {
+        k == FileChangeKind::Deleted || (opts.copies && k == FileChangeKind::Modified)
+    });
+    if targets.is_empty()
+        || sources.is_empty()
+        || targets.len().saturating_mul(sources.len()) > MAX_RENAME_PAIRS
+    {
+        return files;
+    }
+
+    let source_content: Vec<(usize, Vec<u8>)> = sources
+        .into_iter()
+        .filter_map(|i| load_old(&files[i].path).map(|c| (i, c)))
+        .filter(|(_, c)| !c.is_empty())
+        .collect();
+    let target_content: Vec<(usize, Vec<u8>)> = targets
+        .into_iter()
+        .filter_map(|i| load_new(&files[i].path).map(|c| (i, c)))
+        .filter(|(_, c)| !c.is_empty())
+        .collect();
+
+    let mut candidates = Vec::new();
+    for (t, new) in &target_content {
+        for (s, old) in &source_content {
+            let score = similarity_index(old, new);
+            if score >= opts.threshold {
+                candidates.push((score, *t, *s));
+            }
+        }
+    }
+    // Best score first; path order keeps ties deterministic.
+    candidates.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| files[a.1].path.cmp(&files[b.1].path))
+            .then_with(|| files[a.2].path.cmp(&files[b.2].path))
+    });
+
+    let mut matched: HashMap<usize, ChangedFile> = HashMap::new();
+    let mut renamed_sources = vec![false; files.len()];
+    for (score, t, s) in candidates {
+        if matched.contains_key(&t) {
+            continue;
+        }
+        let source = &files[s];
+        let kind = if source.kind == FileChangeKind::Deleted && !renamed_sources[s] {
+            renamed_sources[s] = true;
+            FileChangeKind::Renamed
+        } else if opts.copies {
+            FileChangeKind::Copied
+        } else {
+            continue;
+        };
+
+        let target = &files[t];
+        matched.insert(
+            t,
+            ChangedFile {
+                path: target.path.clone(),
+                kind,
+                old_path: Some(source.path.clone()),
+                old_mode: source.old_mode,
+                new_mode: target.new_mode,
+                similarity: Some(score),
+            },
+        );
+    }
+
+    let mut result: Vec<ChangedFile> = files
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| !renamed_sources[*i])
+        .map(|(i, f)| matched.remove(&i).unwrap_or(f))
+        .collect();
+    result.sort_by(|a, b| a.path.cmp(&b.path));
+    result
+}
+
+fn indices_of(files: &[ChangedFile], pred: impl Fn(FileChangeKind) -> bool) -> Vec<usize> {
+    files
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| pred(f.kind))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, kind: FileChangeKind) -> ChangedFile {
+        ChangedFile::new(RelPath::new(path), kind)
+    }
+
+    fn contents(entries: &[(&str, &str)]) -> impl FnMut(&RelPath) -> Option<Vec<u8>> {
+        let map: HashMap<String, Vec<u8>> = entries
+            .iter()
+            .map(|(p, c)| (p.to_string(), c.as_bytes().to_vec()))
+            .collect();
+        move |p| map.get(p.as_str()).cloned()
+    }
+
+    #[test]
+    fn similarity_counts_shared_lines() {
+        assert_eq!(similarity_index(b"", b""), 100);
+        assert_eq!(similarity_index(b"a\nb\nc\nd\n", b"a\nb\nc\nx\n"), 75);
+        assert_eq!(similarity_index(b"a\n", b"b\n"), 0);
+    }
+
+    #[test]
+    fn pairs_deleted_and_added_as_rename() {
+        let files = vec![
+            file("new.rs", FileChangeKind::Added),
+            file("old.rs", FileChangeKind::Deleted),
+        ];
+        let result = detect_renames(
+            files,
+            &RenameDetection::default(),
+            contents(&[("old.rs", "a\nb\nc\nd\n")]),
+            contents(&[("new.rs", "a\nb\nc\nx\n")]),
+        );
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].kind, FileChangeKind::Renamed);
+        assert_eq!(result[0].old_path.as_ref().unwrap().as_str(), "old.rs");
+        assert_eq!(result[0].similarity, Some(75));
+    }
+
+    #[test]
+    fn untracked_files_are_rename_targets() {
+        let files = vec![
+            file("new.rs", FileChangeKind::Untracked),
+            file("old.rs", FileChangeKind::Deleted),
+        ];
+        let result = detect_renames(
+            files,
+            &RenameDetection::default(),
+            contents(&[("old.rs", "a\nb\n")]),
+            contents(&[("new.rs", "a\nb\n")]),
+        );
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].kind, FileChangeKind::Renamed);
+        assert_eq!(result[0].old_path.as_ref().unwrap().as_str(), "old.rs");
+    }
+
+    #[test]
+    fn threshold_rejects_dissimilar_pairs() {
+        let files = vec![
+            file("new.rs", FileChangeKind::Added),
+            file("old.rs", FileChangeKind::Deleted),
+        ];
+        let opts = RenameDetection {
+            threshold: 90,
+            copies: true,
+        };
+        let result = detect_renames(
+            files,
+            &opts,
+            contents(&[("old.rs", "a\nb\nc\nd\n")]),
+            contents(&[("new.rs", "a\nb\nc\nx\n")]),
+        );
+
+        assert_eq!(result.len(), 2);
+        assert!(result.iter().all(|f| f.similarity.is_none()));
+    }
+
+    #[test]
+    fn copies_come_from_modified_sources() {
+        let files = vec![
+            file("copy.rs", FileChangeKind::Added),
+            file("orig.rs", FileChangeKind::Modified),
+        ];
+        let body = "x\ny\nz\n";
+        let copies = RenameDetection {
+            copies: true,
+            ..RenameDetection::default()
+        };
+        let result = detect_renames(
+            files.clone(),
+            &copies,
+            contents(&[("orig.rs", body)]),
+            contents(&[("copy.rs", body)]),
+        );
+        assert_eq!(result[0].kind, FileChangeKind::Copied);
+        assert_eq!(result[0].similarity, Some(100));
+        assert_eq!(result[1].kind, FileChangeKind::Modified);
+
+        let result = detect_renames(
+            files,
+            &RenameDetection::default(),
+            contents(&[("orig.rs", body)]),
+            contents(&[("copy.rs", body)]),
+        );
+        assert_eq!(result[0].kind, FileChangeKind::Added);
+    }
+
+    #[test]
+    fn empty_files_are_not_paired() {
+        let files = vec![
+            file("pkg/__init__.py", FileChangeKind::Added),
+            file("old/.gitkeep", FileChangeKind::Deleted),
+            file("other/__init__.py", FileChangeKind::Modified),
+        ];
+        let opts = RenameDetection {
+            copies: true,
+            ..RenameDetection::default()
+        };
+        let result = detect_renames(
+            files,
+            &opts,
+            contents(&[("old/.gitkeep", ""), ("other/__init__.py", "")]),
+            contents(&[("pkg/__init__.py", "")]),
+        );
+
+        assert_eq!(result.len(), 3);
+        assert!(result.iter().all(|f| f.similarity.is_none()));
+    }
+
+    #[test]
+    fn second_match_of_deleted_source_is_a_copy() {
+        let files = vec![
+            file("a.rs", FileChangeKind::Added),
+            file("b.rs", FileChangeKind::Added),
+            file("gone.rs", FileChangeKind::Deleted),
+        ];
+        let body = "same\ncontent\n";
+        let copies = RenameDetection {
+            copies: true,
+            ..RenameDetection::default()
+        };
+        let result = detect_renames(
+            files,
+            &copies,
+            contents(&[("gone.rs", body)]),
+            contents(&[("a.rs", body), ("b.rs", body)]),
+        );
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].kind, FileChangeKind::Renamed);
+        assert_eq!(result[1].kind, FileChangeKind::Copied);
+    }
+}
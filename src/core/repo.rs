@@ -9,7 +9,9 @@ use std::sync::Arc;
 
 use thiserror::Error;
 
-use git2::{DiffFindOptions, DiffOptions, Repository, Status, StatusOptions};
+use git2::{DiffOptions, Repository, Status, StatusOptions};
+
+use crate::core::{CommentBackend, RenameDetection, bare_repository_error, detect_renames};
 
 #[cfg(feature = "jj")]
 use chrono::Local;
@@ -27,6 +29,8 @@ use jj_lib::conflicts::{
     try_materialize_file_conflict_value,
 };
 #[cfg(feature = "jj")]
+use jj_lib::evolution::walk_predecessors;
+#[cfg(feature = "jj")]
 use jj_lib::files::FileMergeHunkLevel;
 #[cfg(feature = "jj")]
 use jj_lib::matchers::EverythingMatcher;
@@ -92,6 +96,9 @@ pub enum RepoError {
     /// Operation not supported for PR diff sources.
     #[error("operation not supported for PR diff sources; use patch extraction instead")]
     UnsupportedForPR,
+    /// Discovered a bare repository, which has no working tree to diff.
+    #[error("bare repository has no working tree; run quickdiff inside a worktree{0}")]
+    BareRepository(String),
 }
 
 /// Error when constructing a RelPath with an absolute path.
@@ -143,6 +150,15 @@ pub enum DiffSource {
         /// Base branch name.
         base: String,
     },
+    /// Two versions of the same jj change from its evolution log.
+    Evolog {
+        /// Change the versions belong to, as given by the user.
+        change: String,
+        /// Older version (commit id).
+        from: String,
+        /// Newer version (commit id).
+        to: String,
+    },
 }
 
 impl DiffSource {
@@ -218,6 +234,10 @@ fn find_jj_root(_start: &Path) -> Option<PathBuf> {
 pub struct RepoRoot {
     root: PathBuf,
     vcs: VcsType,
+    /// Git common dir when review state is shared across worktrees.
+    shared_state: Option<PathBuf>,
+    /// Where comments are persisted.
+    comment_backend: CommentBackend,
 }
 
 impl RepoRoot {
@@ -245,6 +265,8 @@ impl RepoRoot {
                     return Ok(Self {
                         root,
                         vcs: VcsType::Jj,
+                        shared_state: None,
+                        comment_backend: CommentBackend::default(),
                     });
                 }
                 Self::discover_git(path)
@@ -256,6 +278,8 @@ impl RepoRoot {
                 Ok(Self {
                     root,
                     vcs: VcsType::Jj,
+                    shared_state: None,
+                    comment_backend: CommentBackend::default(),
                 })
             }
         }
@@ -264,6 +288,9 @@ impl RepoRoot {
     /// Discover a Git repository (helper for discover).
     fn discover_git(path: &Path) -> Result<Self, RepoError> {
         let repo = Repository::discover(path).map_err(|_| RepoError::NotARepo("git"))?;
+        if repo.is_bare() {
+            return Err(bare_repository_error(&repo));
+        }
         let root = repo
             .workdir()
             .ok_or(RepoError::NotARepo("git"))?
@@ -272,9 +299,77 @@ impl RepoRoot {
         Ok(Self {
             root,
             vcs: VcsType::Git,
+            shared_state: None,
+            comment_backend: CommentBackend::default(),
         })
     }
 
+    /// Share viewed state and comments with every worktree of the same git repo.
+    ///
+    /// State then lives under the common git dir (`<common>/quickdiff/`) and is
+    /// keyed by it, instead of per-checkout `.quickdiff/`. No-op for jj repos.
+    #[must_use]
+    pub fn with_shared_state(mut self, enabled: bool) -> Self {
+        self.shared_state = if enabled { self.git_common_dir() } else { None };
+        self
+    }
+
+    /// Whether review state is shared across worktrees.
+    #[must_use]
+    pub fn shares_state(&self) -> bool {
+        self.shared_state.is_some()
+    }
+
+    /// Persist comments with the given backend (see [`open_comment_store`]).
+    #[must_use]
+    pub fn with_comment_backend(mut self, backend: CommentBackend) -> Self {
+        self.comment_backend = backend;
+        self
+    }
+
+    /// Where comments are persisted.
+    #[must_use]
+    pub fn comment_backend(&self) -> CommentBackend {
+        self.comment_backend
+    }
+
+    /// The git common dir (shared by all worktrees), if this is a git repo.
+    #[must_use]
+    pub fn git_common_dir(&self) -> Option<PathBuf> {
+        if !self.is_git() {
+            return None;
+        }
+        let repo = Repository::open(&self.root).ok()?;
+        repo.commondir().canonicalize().ok()
+    }
+
+    /// Whether this checkout is a linked worktree (`git worktree add`).
+    #[must_use]
+    pub fn is_linked_worktree(&self) -> bool {
+        self.is_git()
+            && Repository::open(&self.root)
+                .map(|repo| repo.is_worktree())
+                .unwrap_or(false)
+    }
+
+    /// Directory holding comments and other per-repo review state.
+    #[must_use]
+    pub fn state_dir(&self) -> PathBuf {
+        match &self.shared_state {
+            Some(common) => common.join("quickdiff"),
+            None => self.root.join(".quickdiff"),
+        }
+    }
+
+    /// Key for per-repo entries in the global viewed-state file.
+    #[must_use]
+    pub fn state_key(&self) -> &str {
+        match &self.shared_state {
+            Some(common) => common.to_str().unwrap_or(""),
+            None => self.as_str(),
+        }
+    }
+
     /// Get the repository root path.
     #[must_use]
     pub fn path(&self) -> &Path {
@@ -496,6 +591,16 @@ impl JjRepo {
 
                     Ok(Some(content))
                 }
+                Some(TreeValue::Symlink(id)) => {
+                    let target = repo
+                        .store()
+                        .read_symlink(path, id)
+                        .block_on()
+                        .map_err(|e| {
+                            RepoError::JjError(format!("failed to read symlink: {}", e))
+                        })?;
+                    Ok(Some(target.into_bytes()))
+                }
                 None => Ok(None),
                 _ => Ok(None),
             }
@@ -544,6 +649,20 @@ fn jj_value_exists(value: &MergedTreeValue) -> bool {
     }
 }
 
+#[cfg(feature = "jj")]
+fn jj_value_mode(value: &MergedTreeValue) -> Option<FileMode> {
+    match value.as_resolved()? {
+        Some(TreeValue::File { executable, .. }) => Some(if *executable {
+            FileMode::Executable
+        } else {
+            FileMode::Regular
+        }),
+        Some(TreeValue::Symlink(_)) => Some(FileMode::Symlink),
+        Some(TreeValue::GitSubmodule(_)) => Some(FileMode::Submodule),
+        _ => None,
+    }
+}
+
 #[cfg(feature = "jj")]
 fn list_changed_files_jj(root: &RepoRoot) -> Result<Vec<ChangedFile>, RepoError> {
     let repo = JjRepo::open(root.path())?;
@@ -591,7 +710,10 @@ fn list_changed_files_jj(root: &RepoRoot) -> Result<Vec<ChangedFile>, RepoError>
             (false, false) => continue,
         };
 
-        files.push(ChangedFile::new(RelPath::new(path_str), kind));
+        files.push(
+            ChangedFile::new(RelPath::new(path_str), kind)
+                .with_modes(jj_value_mode(&diff.before), jj_value_mode(&diff.after)),
+        );
     }
 
     files.sort_by(|a, b| a.path.cmp(&b.path));
@@ -603,6 +725,7 @@ fn list_changed_files_between_jj(
     root: &RepoRoot,
     from: &str,
     to: &str,
+    renames: &RenameDetection,
 ) -> Result<Vec<ChangedFile>, RepoError> {
     let repo = JjRepo::open(root.path())?;
     let from_commit = repo.resolve_single_commit(from)?;
@@ -640,11 +763,34 @@ fn list_changed_files_between_jj(
             (false, false) => continue,
         };
 
-        files.push(ChangedFile::new(RelPath::new(path_str), kind));
+        files.push(
+            ChangedFile::new(RelPath::new(path_str), kind)
+                .with_modes(jj_value_mode(&diff.before), jj_value_mode(&diff.after)),
+        );
     }
 
     files.sort_by(|a, b| a.path.cmp(&b.path));
-    Ok(files)
+    Ok(detect_renames(
+        files,
+        renames,
+        |path| jj_tree_content(&repo, &from_tree, path),
+        |path| jj_tree_content(&repo, &to_tree, path),
+    ))
+}
+
+/// Read a path from a jj tree for similarity scoring.
+#[cfg(feature = "jj")]
+fn jj_tree_content(
+    repo: &JjRepo,
+    tree: &jj_lib::merged_tree::MergedTree,
+    path: &RelPath,
+) -> Option<Vec<u8>> {
+    let repo_path = RepoPathBuf::from_internal_string(path.as_str().to_string()).ok()?;
+    let value = tree.path_value(&repo_path).ok()?;
+    repo.get_content_from_value(repo.repo.as_ref(), &repo_path, &value)
+        .ok()
+        .flatten()
+        .filter(|content| content.len() as u64 <= MAX_FILE_SIZE)
 }
 
 #[cfg(feature = "jj")]
@@ -704,6 +850,42 @@ fn get_parent_revision_jj(root: &RepoRoot, revision: &str) -> Result<String, Rep
     Ok(commit.parent_ids()[0].hex())
 }
 
+#[cfg(feature = "jj")]
+fn list_evolog_jj(root: &RepoRoot, change: &str) -> Result<Vec<EvologEntry>, RepoError> {
+    let repo = JjRepo::open(root.path())?;
+    let commit = repo.resolve_single_commit(change)?;
+
+    let mut entries = Vec::new();
+    for entry in walk_predecessors(repo.repo.as_ref(), std::slice::from_ref(commit.id())) {
+        let entry =
+            entry.map_err(|e| RepoError::JjError(format!("failed to walk evolog: {}", e)))?;
+        let commit = &entry.commit;
+        entries.push(EvologEntry {
+            commit_id: commit.id().hex(),
+            summary: commit
+                .description()
+                .lines()
+                .next()
+                .unwrap_or("")
+                .to_string(),
+            timestamp: format_jj_timestamp(&commit.committer().timestamp),
+            operation: entry
+                .operation
+                .as_ref()
+                .map(|op| op.metadata().description.clone()),
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(feature = "jj")]
+fn format_jj_timestamp(ts: &jj_lib::backend::Timestamp) -> String {
+    chrono::FixedOffset::east_opt(ts.tz_offset * 60)
+        .zip(chrono::DateTime::from_timestamp_millis(ts.timestamp.0))
+        .map(|(tz, time)| time.with_timezone(&tz).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
 /// Validate that a git reference doesn't look like a flag (defense in depth).
 fn validate_git_ref_format(reference: &str) -> Result<(), RepoError> {
     let reference = reference.trim();
@@ -849,6 +1031,66 @@ pub enum FileChangeKind {
     Untracked,
     /// File was renamed (best-effort; may show as delete+add).
     Renamed,
+    /// File was copied from another path that still exists.
+    Copied,
+}
+
+/// Git-style mode of a tree entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileMode {
+    /// Regular file (`100644`).
+    Regular,
+    /// Executable file (`100755`).
+    Executable,
+    /// Symbolic link (`120000`).
+    Symlink,
+    /// Submodule commit (`160000`).
+    Submodule,
+}
+
+impl FileMode {
+    /// Octal representation as printed by git.
+    #[must_use]
+    pub fn octal(self) -> &'static str {
+        match self {
+            FileMode::Regular => "100644",
+            FileMode::Executable => "100755",
+            FileMode::Symlink => "120000",
+            FileMode::Submodule => "160000",
+        }
+    }
+
+    /// Parse the octal representation used in diff headers (`new mode 100755`).
+    #[must_use]
+    pub fn from_octal(s: &str) -> Option<Self> {
+        match s.trim() {
+            "100644" | "100664" => Some(FileMode::Regular),
+            "100755" => Some(FileMode::Executable),
+            "120000" => Some(FileMode::Symlink),
+            "160000" => Some(FileMode::Submodule),
+            _ => None,
+        }
+    }
+
+    /// Entry type name, ignoring the executable bit.
+    #[must_use]
+    pub fn type_name(self) -> &'static str {
+        match self {
+            FileMode::Regular | FileMode::Executable => "file",
+            FileMode::Symlink => "symlink",
+            FileMode::Submodule => "submodule",
+        }
+    }
+
+    fn from_git2(mode: git2::FileMode) -> Option<Self> {
+        match mode {
+            git2::FileMode::Blob | git2::FileMode::BlobGroupWritable => Some(FileMode::Regular),
+            git2::FileMode::BlobExecutable => Some(FileMode::Executable),
+            git2::FileMode::Link => Some(FileMode::Symlink),
+            git2::FileMode::Commit => Some(FileMode::Submodule),
+            git2::FileMode::Unreadable | git2::FileMode::Tree => None,
+        }
+    }
 }
 
 /// A changed file in the repository.
@@ -860,6 +1102,12 @@ pub struct ChangedFile {
     pub kind: FileChangeKind,
     /// For renames, the original path.
     pub old_path: Option<RelPath>,
+    /// Mode on the old side, when it exists and is known.
+    pub old_mode: Option<FileMode>,
+    /// Mode on the new side, when it exists and is known.
+    pub new_mode: Option<FileMode>,
+    /// Content similarity in percent for detected renames and copies.
+    pub similarity: Option<u8>,
 }
 
 impl ChangedFile {
@@ -869,6 +1117,9 @@ impl ChangedFile {
             path,
             kind,
             old_path: None,
+            old_mode: None,
+            new_mode: None,
+            similarity: None,
         }
     }
 
@@ -878,6 +1129,54 @@ impl ChangedFile {
             path: new_path,
             kind: FileChangeKind::Renamed,
             old_path: Some(old_path),
+            old_mode: None,
+            new_mode: None,
+            similarity: None,
+        }
+    }
+
+    /// Attach old/new file modes.
+    #[must_use]
+    pub fn with_modes(mut self, old_mode: Option<FileMode>, new_mode: Option<FileMode>) -> Self {
+        self.old_mode = old_mode;
+        self.new_mode = new_mode;
+        self
+    }
+
+    /// Whether the file's mode differs between the two sides.
+    #[must_use]
+    pub fn has_mode_change(&self) -> bool {
+        matches!((self.old_mode, self.new_mode), (Some(old), Some(new)) if old != new)
+    }
+
+    /// Describe a mode transition, e.g. `100644 → 100755` or `file → symlink`.
+    #[must_use]
+    pub fn mode_change_label(&self) -> Option<String> {
+        let (Some(old), Some(new)) = (self.old_mode, self.new_mode) else {
+            return None;
+        };
+        if old == new {
+            None
+        } else if old.type_name() == new.type_name() {
+            Some(format!("{} → {}", old.octal(), new.octal()))
+        } else {
+            Some(format!("{} → {}", old.type_name(), new.type_name()))
+        }
+    }
+
+    /// Compact mode transition tag for narrow columns (`+x`, `-x`, `→link`).
+    #[must_use]
+    pub fn mode_change_tag(&self) -> Option<&'static str> {
+        let (Some(old), Some(new)) = (self.old_mode, self.new_mode) else {
+            return None;
+        };
+        match (old, new) {
+            (a, b) if a == b => None,
+            (FileMode::Regular, FileMode::Executable) => Some("+x"),
+            (FileMode::Executable, FileMode::Regular) => Some("-x"),
+            (_, FileMode::Symlink) => Some("→link"),
+            (_, FileMode::Submodule) => Some("→sub"),
+            (_, FileMode::Regular | FileMode::Executable) => Some("→file"),
         }
     }
 }
@@ -922,6 +1221,7 @@ pub fn list_changed_files(root: &RepoRoot) -> Result<Vec<ChangedFile>, RepoError
 
         let status = entry.status();
         let kind = status_to_change_kind(status);
+        let (old_mode, new_mode) = status_entry_modes(&entry);
 
         // Handle renames (check for both old and new paths)
         if (status.contains(Status::INDEX_RENAMED) || status.contains(Status::WT_RENAMED))
@@ -932,17 +1232,39 @@ pub fn list_changed_files(root: &RepoRoot) -> Result<Vec<ChangedFile>, RepoError
             )
             && old != new
         {
-            files.push(ChangedFile::renamed(RelPath::new(old), RelPath::new(new)));
+            files.push(
+                ChangedFile::renamed(RelPath::new(old), RelPath::new(new))
+                    .with_modes(old_mode, new_mode),
+            );
             continue;
         }
 
-        files.push(ChangedFile::new(RelPath::new(path), kind));
+        files.push(ChangedFile::new(RelPath::new(path), kind).with_modes(old_mode, new_mode));
     }
 
     files.sort_by(|a, b| a.path.cmp(&b.path));
     Ok(files)
 }
 
+/// Old (HEAD) and new (working tree, falling back to index) modes of a status entry.
+fn status_entry_modes(entry: &git2::StatusEntry<'_>) -> (Option<FileMode>, Option<FileMode>) {
+    let head_to_index = entry.head_to_index();
+    let index_to_workdir = entry.index_to_workdir();
+
+    let old_mode = head_to_index
+        .as_ref()
+        .map(|d| d.old_file().mode())
+        .or_else(|| index_to_workdir.as_ref().map(|d| d.old_file().mode()))
+        .and_then(FileMode::from_git2);
+    let new_mode = index_to_workdir
+        .as_ref()
+        .map(|d| d.new_file().mode())
+        .or_else(|| head_to_index.as_ref().map(|d| d.new_file().mode()))
+        .and_then(FileMode::from_git2);
+
+    (old_mode, new_mode)
+}
+
 /// Convert git2 Status flags to FileChangeKind.
 fn status_to_change_kind(status: Status) -> FileChangeKind {
     if status.contains(Status::WT_NEW) || status.contains(Status::INDEX_NEW) {
@@ -1061,15 +1383,20 @@ pub fn load_head_content(root: &RepoRoot, path: &RelPath) -> Result<Vec<u8>, Rep
 }
 
 /// Load content from the working tree.
-/// Returns empty content for directories, symlinks, or missing files.
+/// Symlinks load as their target path (like git stores them); directories and
+/// missing files are empty.
 /// Returns error if file exceeds `MAX_FILE_SIZE`.
 #[must_use = "this returns a Result that should be checked"]
 pub fn load_working_content(root: &RepoRoot, path: &RelPath) -> Result<Vec<u8>, RepoError> {
     let full_path = path.to_absolute(root);
 
-    // Use symlink_metadata to avoid following symlinks.
-    // Symlinks are treated as empty to avoid escaping the repo.
+    // Use symlink_metadata to avoid following symlinks: the link target is
+    // diffed as text and never dereferenced, so it cannot escape the repo.
     match std::fs::symlink_metadata(&full_path) {
+        Ok(meta) if meta.file_type().is_symlink() => {
+            let target = std::fs::read_link(&full_path)?;
+            return Ok(target.to_string_lossy().into_owned().into_bytes());
+        }
         Ok(meta) if meta.is_file() => {
             // Check file size limit
             if meta.len() > MAX_FILE_SIZE {
@@ -1079,7 +1406,7 @@ pub fn load_working_content(root: &RepoRoot, path: &RelPath) -> Result<Vec<u8>,
                 });
             }
         }
-        Ok(_) => return Ok(Vec::new()), // Directory, symlink, etc.
+        Ok(_) => return Ok(Vec::new()), // Directory, socket, etc.
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
         Err(e) => return Err(e.into()),
     }
@@ -1196,7 +1523,7 @@ pub fn load_diff_contents(
                 Ok((Vec::new(), load_working_content(root, path)?))
             }
             FileChangeKind::Deleted => Ok((load_head_content(root, path)?, Vec::new())),
-            FileChangeKind::Modified | FileChangeKind::Renamed => {
+            FileChangeKind::Modified | FileChangeKind::Renamed | FileChangeKind::Copied => {
                 let old_p = old_path.unwrap_or(path);
                 Ok((
                     load_head_content(root, old_p)?,
@@ -1213,7 +1540,10 @@ pub fn load_diff_contents(
                 FileChangeKind::Deleted => {
                     Ok((load_revision_content(root, &parent, path)?, Vec::new()))
                 }
-                FileChangeKind::Modified | FileChangeKind::Renamed | FileChangeKind::Untracked => {
+                FileChangeKind::Modified
+                | FileChangeKind::Renamed
+                | FileChangeKind::Copied
+                | FileChangeKind::Untracked => {
                     let old_p = old_path.unwrap_or(path);
                     Ok((
                         load_revision_content(root, &parent, old_p)?,
@@ -1222,10 +1552,13 @@ pub fn load_diff_contents(
                 }
             }
         }
-        DiffSource::Range { from, to } => match kind {
+        DiffSource::Range { from, to } | DiffSource::Evolog { from, to, .. } => match kind {
             FileChangeKind::Added => Ok((Vec::new(), load_revision_content(root, to, path)?)),
             FileChangeKind::Deleted => Ok((load_revision_content(root, from, path)?, Vec::new())),
-            FileChangeKind::Modified | FileChangeKind::Renamed | FileChangeKind::Untracked => {
+            FileChangeKind::Modified
+            | FileChangeKind::Renamed
+            | FileChangeKind::Copied
+            | FileChangeKind::Untracked => {
                 let old_p = old_path.unwrap_or(path);
                 Ok((
                     load_revision_content(root, from, old_p)?,
@@ -1246,7 +1579,7 @@ pub fn load_diff_contents(
                 FileChangeKind::Deleted => {
                     Ok((load_revision_content(root, &merge_base, path)?, Vec::new()))
                 }
-                FileChangeKind::Modified | FileChangeKind::Renamed => {
+                FileChangeKind::Modified | FileChangeKind::Renamed | FileChangeKind::Copied => {
                     let old_p = old_path.unwrap_or(path);
                     Ok((
                         load_revision_content(root, &merge_base, old_p)?,
@@ -1266,6 +1599,40 @@ pub fn load_diff_contents(
     }
 }
 
+/// The user's identity from git config (`Name <email>`), for attributing comments.
+///
+/// Falls back to the global git config outside git repositories.
+pub fn user_identity(root: &RepoRoot) -> Option<String> {
+    let config = Repository::open(root.path())
+        .and_then(|repo| repo.config())
+        .or_else(|_| git2::Config::open_default())
+        .ok()?;
+    let name = config
+        .get_string("user.name")
+        .ok()
+        .filter(|s| !s.is_empty());
+    let email = config
+        .get_string("user.email")
+        .ok()
+        .filter(|s| !s.is_empty());
+    match (name, email) {
+        (Some(name), Some(email)) => Some(format!("{} <{}>", name, email)),
+        (name, email) => name.or(email),
+    }
+}
+
+/// Environment variable that overrides the author of new comments, for bots and agents.
+pub const AUTHOR_ENV: &str = "QUICKDIFF_AUTHOR";
+
+/// Author recorded on new comments and replies: `$QUICKDIFF_AUTHOR`, else the git identity.
+pub fn comment_author(root: &RepoRoot) -> Option<String> {
+    std::env::var(AUTHOR_ENV)
+        .ok()
+        .map(|author| author.trim().to_string())
+        .filter(|author| !author.is_empty())
+        .or_else(|| user_identity(root))
+}
+
 /// Resolve a revision to its full commit id.
 #[must_use = "this returns a Result that should be checked"]
 pub fn resolve_revision(root: &RepoRoot, revision: &str) -> Result<String, RepoError> {
@@ -1335,17 +1702,18 @@ pub fn get_parent_revision(root: &RepoRoot, revision: &str) -> Result<String, Re
     }
 }
 
-/// List changed files between two revisions.
+/// List changed files between two revisions, pairing renames and copies per `renames`.
 #[must_use = "this returns a Result that should be checked"]
 pub fn list_changed_files_between(
     root: &RepoRoot,
     from: &str,
     to: &str,
+    renames: &RenameDetection,
 ) -> Result<Vec<ChangedFile>, RepoError> {
     if root.is_jj() {
         #[cfg(feature = "jj")]
         {
-            return list_changed_files_between_jj(root, from, to);
+            return list_changed_files_between_jj(root, from, to, renames);
         }
         #[cfg(not(feature = "jj"))]
         {
@@ -1373,63 +1741,119 @@ pub fn list_changed_files_between(
         .peel_to_tree()
         .map_err(|_| RepoError::InvalidRevision(to.to_string()))?;
 
-    // Create diff with rename/copy detection
+    // Renames and copies are paired by `detect_renames` so git and jj agree.
     let mut opts = DiffOptions::new();
-    let mut diff = repo
+    let diff = repo
         .diff_tree_to_tree(Some(&from_tree), Some(&to_tree), Some(&mut opts))
         .map_err(|e| RepoError::GitError(format!("failed to create diff: {}", e)))?;
 
-    // Enable rename detection
-    let mut find_opts = DiffFindOptions::new();
-    find_opts.renames(true);
-    diff.find_similar(Some(&mut find_opts))
-        .map_err(|e| RepoError::GitError(format!("failed to find renames: {}", e)))?;
+    let files = collect_git_deltas(&diff);
+    Ok(detect_renames(
+        files,
+        renames,
+        |path| tree_blob_content(&repo, &from_tree, path),
+        |path| tree_blob_content(&repo, &to_tree, path),
+    ))
+}
 
+/// Convert raw (rename-free) git diff deltas into changed files.
+fn collect_git_deltas(diff: &git2::Diff<'_>) -> Vec<ChangedFile> {
     let mut files = Vec::new();
 
     for delta in diff.deltas() {
-        let new_path = delta
+        let path = delta
             .new_file()
             .path()
+            .or_else(|| delta.old_file().path())
             .and_then(|p| p.to_str())
             .unwrap_or("");
-        let old_path = delta.old_file().path().and_then(|p| p.to_str());
-
-        if new_path.is_empty() {
+        if path.is_empty() || path.starts_with(".quickdiff/") || path.starts_with(".jj/") {
             continue;
         }
 
         let kind = match delta.status() {
             git2::Delta::Added => FileChangeKind::Added,
             git2::Delta::Deleted => FileChangeKind::Deleted,
-            git2::Delta::Modified => FileChangeKind::Modified,
-            git2::Delta::Renamed | git2::Delta::Copied => {
-                if let Some(old) = old_path
-                    && old != new_path
-                {
-                    files.push(ChangedFile::renamed(
-                        RelPath::new(old),
-                        RelPath::new(new_path),
-                    ));
-                    continue;
-                }
-                FileChangeKind::Modified
-            }
+            git2::Delta::Untracked => FileChangeKind::Untracked,
+            git2::Delta::Unmodified | git2::Delta::Ignored => continue,
             _ => FileChangeKind::Modified,
         };
+        let old_mode = FileMode::from_git2(delta.old_file().mode());
+        let new_mode = FileMode::from_git2(delta.new_file().mode());
 
-        files.push(ChangedFile::new(RelPath::new(new_path), kind));
+        files.push(ChangedFile::new(RelPath::new(path), kind).with_modes(old_mode, new_mode));
     }
 
     files.sort_by(|a, b| a.path.cmp(&b.path));
-    Ok(files)
+    files
+}
+
+/// Read a blob from a tree for similarity scoring; skips missing and oversized blobs.
+fn tree_blob_content(repo: &Repository, tree: &git2::Tree<'_>, path: &RelPath) -> Option<Vec<u8>> {
+    let entry = tree.get_path(Path::new(path.as_str())).ok()?;
+    let blob = repo.find_blob(entry.id()).ok()?;
+    if blob.size() as u64 > MAX_FILE_SIZE {
+        return None;
+    }
+    Some(blob.content().to_vec())
 }
 
 /// List changed files in a single commit.
 #[must_use = "this returns a Result that should be checked"]
-pub fn list_commit_files(root: &RepoRoot, commit: &str) -> Result<Vec<ChangedFile>, RepoError> {
+pub fn list_commit_files(
+    root: &RepoRoot,
+    commit: &str,
+    renames: &RenameDetection,
+) -> Result<Vec<ChangedFile>, RepoError> {
     let parent = get_parent_revision(root, commit)?;
-    list_changed_files_between(root, &parent, commit)
+    list_changed_files_between(root, &parent, commit, renames)
+}
+
+/// One version of a jj change, as recorded by `jj evolog`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvologEntry {
+    /// Commit id of this version (full hex).
+    pub commit_id: String,
+    /// First line of the commit description.
+    pub summary: String,
+    /// Committer timestamp, local to the committer (`YYYY-MM-DD HH:MM`).
+    pub timestamp: String,
+    /// Description of the operation that produced this version, when known.
+    pub operation: Option<String>,
+}
+
+/// Walk the evolution log of a jj change, newest version first.
+#[must_use = "this returns a Result that should be checked"]
+pub fn list_evolog(root: &RepoRoot, change: &str) -> Result<Vec<EvologEntry>, RepoError> {
+    if !root.is_jj() {
+        return Err(RepoError::NotARepo("jj"));
+    }
+    #[cfg(feature = "jj")]
+    {
+        list_evolog_jj(root, change.trim())
+    }
+    #[cfg(not(feature = "jj"))]
+    {
+        let _ = change;
+        Err(RepoError::JjError("jj support not enabled".to_string()))
+    }
+}
+
+/// Evolog source comparing the newest version of `change` with the one before it.
+#[must_use = "this returns a Result that should be checked"]
+pub fn latest_evolog_source(root: &RepoRoot, change: &str) -> Result<DiffSource, RepoError> {
+    let entries = list_evolog(root, change)?;
+    let [to, from, ..] = entries.as_slice() else {
+        return Err(RepoError::InvalidRevision(format!(
+            "change '{}' has no earlier versions",
+            change
+        )));
+    };
+    Ok(DiffSource::Evolog {
+        change: change.to_string(),
+        from: from.commit_id.clone(),
+        to: to.commit_id.clone(),
+    })
 }
 
 /// Result of a base comparison.
@@ -1446,11 +1870,17 @@ pub struct BaseComparison {
 pub fn list_changed_files_from_base_with_merge_base(
     root: &RepoRoot,
     base: &str,
+    renames: &RenameDetection,
 ) -> Result<BaseComparison, RepoError> {
     let merge_base = resolve_merge_base(root, base)?;
 
+    if !root.is_jj() {
+        let files = list_changed_files_since_git(root, &merge_base, renames)?;
+        return Ok(BaseComparison { merge_base, files });
+    }
+
     // Get files changed between merge-base and HEAD
-    let committed = list_changed_files_between(root, &merge_base, "HEAD")?;
+    let committed = list_changed_files_between(root, &merge_base, "HEAD", renames)?;
 
     // Also get working tree changes
     let working = list_changed_files(root)?;
@@ -1461,7 +1891,17 @@ pub fn list_changed_files_from_base_with_merge_base(
         .map(|f| (f.path.as_str().to_string(), f))
         .collect();
 
-    for f in working {
+    for mut f in working {
+        // Keep a committed rename/copy source when the file was only edited since.
+        if f.kind == FileChangeKind::Modified
+            && let Some(prev) = files.get(f.path.as_str())
+            && matches!(prev.kind, FileChangeKind::Renamed | FileChangeKind::Copied)
+        {
+            f.kind = prev.kind;
+            f.old_path = prev.old_path.clone();
+            f.old_mode = prev.old_mode;
+            f.similarity = prev.similarity;
+        }
         files.insert(f.path.as_str().to_string(), f);
     }
 
@@ -1474,13 +1914,43 @@ pub fn list_changed_files_from_base_with_merge_base(
     })
 }
 
+/// Git: diff the merge-base tree against the working tree (through the index) in one pass.
+fn list_changed_files_since_git(
+    root: &RepoRoot,
+    merge_base: &str,
+    renames: &RenameDetection,
+) -> Result<Vec<ChangedFile>, RepoError> {
+    let repo = Repository::open(root.path()).map_err(|_| RepoError::NotARepo("git"))?;
+    let base_tree = repo
+        .revparse_single(merge_base)
+        .and_then(|obj| obj.peel_to_tree())
+        .map_err(|_| RepoError::InvalidRevision(merge_base.to_string()))?;
+
+    let mut opts = DiffOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .ignore_submodules(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut opts))
+        .map_err(|e| RepoError::GitError(format!("failed to create diff: {}", e)))?;
+
+    let files = collect_git_deltas(&diff);
+    Ok(detect_renames(
+        files,
+        renames,
+        |path| tree_blob_content(&repo, &base_tree, path),
+        |path| load_working_content(root, path).ok(),
+    ))
+}
+
 /// List changed files between a base ref and HEAD (including working tree).
 #[must_use = "this returns a Result that should be checked"]
 pub fn list_changed_files_from_base(
     root: &RepoRoot,
     base: &str,
+    renames: &RenameDetection,
 ) -> Result<Vec<ChangedFile>, RepoError> {
-    Ok(list_changed_files_from_base_with_merge_base(root, base)?.files)
+    Ok(list_changed_files_from_base_with_merge_base(root, base, renames)?.files)
 }
 
 /// Parse `git diff --name-status -z` output.
@@ -1581,6 +2051,12 @@ pub fn diff_source_display(source: &DiffSource, root: &RepoRoot) -> String {
         DiffSource::Range { from, to } => {
             format!("{}..{}", take_chars(from, 7), take_chars(to, 7))
         }
+        DiffSource::Evolog { change, from, to } => format!(
+            "evolog {} {}→{}",
+            take_chars(change, 12),
+            take_chars(from, 7),
+            take_chars(to, 7)
+        ),
         DiffSource::Base(base) => format!("vs {}", base),
         DiffSource::FilePair {
             left,
@@ -1708,6 +2184,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mode_change_label_and_tag() {
+        let file = ChangedFile::new(RelPath::new("run.sh"), FileChangeKind::Modified);
+        assert_eq!(file.mode_change_label(), None);
+
+        let chmod = file
+            .clone()
+            .with_modes(Some(FileMode::Regular), Some(FileMode::Executable));
+        assert_eq!(
+            chmod.mode_change_label().as_deref(),
+            Some("100644 → 100755")
+        );
+        assert_eq!(chmod.mode_change_tag(), Some("+x"));
+
+        let link = file
+            .clone()
+            .with_modes(Some(FileMode::Executable), Some(FileMode::Symlink));
+        assert_eq!(link.mode_change_label().as_deref(), Some("file → symlink"));
+        assert_eq!(link.mode_change_tag(), Some("→link"));
+
+        let same = file.with_modes(Some(FileMode::Regular), Some(FileMode::Regular));
+        assert!(!same.has_mode_change());
+        assert_eq!(same.mode_change_tag(), None);
+    }
+
+    #[test]
+    fn file_mode_octal_roundtrip() {
+        for mode in [
+            FileMode::Regular,
+            FileMode::Executable,
+            FileMode::Symlink,
+            FileMode::Submodule,
+        ] {
+            assert_eq!(FileMode::from_octal(mode.octal()), Some(mode));
+        }
+        assert_eq!(FileMode::from_octal("040000"), None);
+    }
+
     #[test]
     fn truncate_chars_ascii() {
         assert_eq!(truncate_chars("short", 10), "short");
@@ -1850,4 +2364,35 @@ mod tests {
         assert!(!revision.is_empty());
         assert!(revision.chars().all(|c| c.is_ascii_hexdigit()));
     }
+
+    #[test]
+    #[cfg(feature = "jj")]
+    fn jj_evolog_lists_rewrites_newest_first() {
+        let Some(repo) = JjRepoGuard::new() else {
+            eprintln!("Skipping test: jj not available");
+            return;
+        };
+
+        fs::write(repo.path().join("README.md"), "hello again\n").unwrap();
+        jj(repo.path(), &["status"]);
+
+        let root =
+            RepoRoot::discover(repo.path(), VcsPreference::Auto).expect("should discover jj repo");
+        let entries = list_evolog(&root, "@").expect("list_evolog should succeed");
+        assert!(entries.len() >= 2);
+        assert_eq!(entries[0].commit_id, resolve_revision(&root, "@").unwrap());
+
+        let source = latest_evolog_source(&root, "@").unwrap();
+        let DiffSource::Evolog { from, to, .. } = &source else {
+            panic!("expected evolog source");
+        };
+        let files =
+            list_changed_files_between(&root, from, to, &RenameDetection::default()).unwrap();
+        assert!(files.iter().any(|f| f.path.as_str() == "README.md"));
+
+        let file = ChangedFile::new(RelPath::new("README.md"), FileChangeKind::Modified);
+        let (old, new) = load_diff_contents(&root, &source, &file, None).unwrap();
+        assert_eq!(old, b"hello\n");
+        assert_eq!(new, b"hello again\n");
+    }
 }
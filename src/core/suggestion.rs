@@ -0,0 +1,236 @@
+//! Suggested changes attached to comments.
+//!
+//! A suggestion is replacement text for the new-side lines a comment is anchored to,
+//! written in the message as a fenced block (like GitHub's):
+//!
+//! ````text
+//! ```suggestion
+//! let total = items.len();
+//! ```
+//! ````
+//!
+//! Applying it rewrites those lines in the working file, but only while they still
+//! read exactly as they did when the comment was made.
+
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::core::{Anchor, DiffSide, Selector, TextBuffer};
+
+/// Opening fence of a suggestion block.
+const SUGGESTION_FENCE: &str = "```suggestion";
+
+/// Errors from applying a suggestion.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SuggestionError {
+    /// The comment carries no suggestion.
+    #[error("comment has no suggestion")]
+    Missing,
+    /// The anchor does not point at new-side lines that can be checked.
+    #[error("suggestions need an anchor on new-side lines")]
+    NoTarget,
+    /// The target lines no longer read as they did when the comment was made.
+    #[error("target lines changed since the suggestion was made")]
+    TargetChanged,
+    /// I/O error reading or writing the file.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Split a ```` ```suggestion ```` block out of a comment message.
+///
+/// Returns the remaining message (trimmed) and the block's contents. An unterminated
+/// block runs to the end of the message. Messages without a block are returned as-is.
+pub fn split_suggestion(message: &str) -> (String, Option<String>) {
+    let mut rest = Vec::new();
+    let mut block: Option<Vec<&str>> = None;
+    let mut in_block = false;
+    for line in message.lines() {
+        if !in_block && block.is_none() && line.trim() == SUGGESTION_FENCE {
+            in_block = true;
+            block = Some(Vec::new());
+        } else if in_block && line.trim() == "```" {
+            in_block = false;
+        } else if in_block {
+            if let Some(block) = block.as_mut() {
+                block.push(line);
+            }
+        } else {
+            rest.push(line);
+        }
+    }
+    match block {
+        Some(block) => (rest.join("\n").trim().to_string(), Some(block.join("\n"))),
+        None => (message.to_string(), None),
+    }
+}
+
+/// New-side lines a suggestion replaces: `(start, end)` (0-indexed, inclusive).
+pub fn suggestion_target(anchor: &Anchor) -> Option<(usize, usize)> {
+    match anchor.recorded_span()? {
+        (DiffSide::New, start, end) => Some((start, end)),
+        (DiffSide::Old, ..) => None,
+    }
+}
+
+/// The target lines as recorded when the comment was made, if the anchor has a snapshot.
+pub fn suggestion_original(anchor: &Anchor) -> Option<&[String]> {
+    let (start, end) = suggestion_target(anchor)?;
+    anchor.selectors.iter().find_map(|s| match s {
+        Selector::ContextV1(ctx)
+            if ctx.side == DiffSide::New
+                && ctx.start_line == start
+                && ctx.lines.len() == end - start + 1 =>
+        {
+            Some(ctx.lines.as_slice())
+        }
+        _ => None,
+    })
+}
+
+/// Replace the anchored lines of `content` with `replacement`.
+///
+/// Refuses when the lines no longer match the anchor's snapshot (or, for anchors
+/// without one, its line fingerprint). Line endings of the target are kept.
+pub fn apply_suggestion(
+    content: &[u8],
+    anchor: &Anchor,
+    replacement: &str,
+) -> Result<Vec<u8>, SuggestionError> {
+    let (start, end) = suggestion_target(anchor).ok_or(SuggestionError::NoTarget)?;
+    let buffer = TextBuffer::new(content);
+    if end >= buffer.line_count() {
+        return Err(SuggestionError::TargetChanged);
+    }
+
+    let unchanged = if let Some(original) = suggestion_original(anchor) {
+        (start..=end)
+            .zip(original)
+            .all(|(line, text)| buffer.line_str(line).as_deref() == Some(text.as_str()))
+    } else {
+        let range = anchor.selectors.iter().find_map(|s| match s {
+            Selector::LineRangeV1(sel) if sel.side == DiffSide::New => Some(sel),
+            _ => None,
+        });
+        match range {
+            Some(sel) => sel.matches(&buffer),
+            None => return Err(SuggestionError::NoTarget),
+        }
+    };
+    if !unchanged {
+        return Err(SuggestionError::TargetChanged);
+    }
+
+    let lines: Vec<&[u8]> = content.split_inclusive(|&b| b == b'\n').collect();
+    let ending: &[u8] = match lines[end] {
+        l if l.ends_with(b"\r\n") => b"\r\n",
+        l if l.ends_with(b"\n") => b"\n",
+        // Last line without a trailing newline: keep it that way.
+        _ => b"",
+    };
+    let separator: &[u8] = if lines[start].ends_with(b"\r\n") {
+        b"\r\n"
+    } else {
+        b"\n"
+    };
+
+    let mut out = Vec::with_capacity(content.len() + replacement.len());
+    for line in &lines[..start] {
+        out.extend_from_slice(line);
+    }
+    let replacement = replacement.strip_suffix('\n').unwrap_or(replacement);
+    if !replacement.is_empty() {
+        for (i, line) in replacement.split('\n').enumerate() {
+            if i > 0 {
+                out.extend_from_slice(separator);
+            }
+            out.extend_from_slice(line.as_bytes());
+        }
+        out.extend_from_slice(ending);
+    }
+    for line in &lines[end + 1..] {
+        out.extend_from_slice(line);
+    }
+    Ok(out)
+}
+
+/// Apply a suggestion to the file at `path` in place.
+pub fn apply_suggestion_to_file(
+    path: &Path,
+    anchor: &Anchor,
+    replacement: &str,
+) -> Result<(), SuggestionError> {
+    let content = std::fs::read(path)?;
+    let patched = apply_suggestion(&content, anchor, replacement)?;
+    std::fs::write(path, patched)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{selector_from_context, selector_from_line_range};
+
+    fn anchor_for(content: &str, start: usize, end: usize) -> Anchor {
+        let buffer = TextBuffer::new(content.as_bytes());
+        Anchor {
+            selectors: vec![
+                Selector::LineRangeV1(
+                    selector_from_line_range(&buffer, DiffSide::New, start, end).unwrap(),
+                ),
+                Selector::ContextV1(
+                    selector_from_context(&buffer, DiffSide::New, start, end).unwrap(),
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn split_suggestion_extracts_block() {
+        let (message, suggestion) =
+            split_suggestion("Use a constant.\n```suggestion\nconst N: u32 = 4;\n```\n");
+        assert_eq!(message, "Use a constant.");
+        assert_eq!(suggestion.as_deref(), Some("const N: u32 = 4;"));
+
+        let (message, suggestion) = split_suggestion("no block here");
+        assert_eq!(message, "no block here");
+        assert!(suggestion.is_none());
+    }
+
+    #[test]
+    fn apply_replaces_target_lines() {
+        let content = "a\nb\nc\nd\n";
+        let anchor = anchor_for(content, 1, 2);
+        let patched = apply_suggestion(content.as_bytes(), &anchor, "B\nC2\nC3").unwrap();
+        assert_eq!(String::from_utf8(patched).unwrap(), "a\nB\nC2\nC3\nd\n");
+
+        // An empty suggestion deletes the lines.
+        let patched = apply_suggestion(content.as_bytes(), &anchor, "").unwrap();
+        assert_eq!(String::from_utf8(patched).unwrap(), "a\nd\n");
+    }
+
+    #[test]
+    fn apply_keeps_crlf_and_missing_final_newline() {
+        let content = "a\r\nb\r\nc";
+        let anchor = anchor_for(content, 1, 2);
+        let patched = apply_suggestion(content.as_bytes(), &anchor, "x\ny").unwrap();
+        assert_eq!(String::from_utf8(patched).unwrap(), "a\r\nx\r\ny");
+    }
+
+    #[test]
+    fn apply_refuses_changed_lines_and_old_side() {
+        let anchor = anchor_for("a\nb\nc\n", 1, 1);
+        let err = apply_suggestion(b"a\nB\nc\n", &anchor, "x").unwrap_err();
+        assert!(matches!(err, SuggestionError::TargetChanged));
+
+        let old = Anchor {
+            selectors: vec![Selector::LineRangeV1(
+                selector_from_line_range(&TextBuffer::new(b"a\n"), DiffSide::Old, 0, 0).unwrap(),
+            )],
+        };
+        let err = apply_suggestion(b"a\n", &old, "x").unwrap_err();
+        assert!(matches!(err, SuggestionError::NoTarget));
+    }
+}
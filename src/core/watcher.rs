@@ -1,13 +1,14 @@
 //! File system watching for live reload.
 
-use std::path::Path;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::time::Duration;
 
 use notify::RecursiveMode;
 use notify_debouncer_mini::{DebounceEventResult, new_debouncer};
 
-use super::RepoRoot;
+use super::{COMMENTS_REF, CommentBackend, FileCommentStore, RepoRoot};
 
 /// Events emitted by the repo watcher.
 #[derive(Debug, Clone)]
@@ -75,6 +76,100 @@ impl RepoWatcher {
     }
 }
 
+/// Watches the comment store so comments written by other processes show up live.
+///
+/// [`RepoWatcher`] skips `.quickdiff/`, and the store may live in the common git dir
+/// with shared worktree state, so the comments file gets its own watcher.
+pub struct CommentsWatcher {
+    rx: Receiver<WatchEvent>,
+    /// Directory holding the comments file.
+    dir: PathBuf,
+    /// Whether `dir` is being watched. Until it exists, its parent is watched instead.
+    watching_dir: bool,
+    watcher: notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>,
+}
+
+impl CommentsWatcher {
+    /// Create a watcher for the comments file of the given repository.
+    ///
+    /// With the git-ref backend, the loose ref file is watched instead.
+    pub fn new(root: &RepoRoot) -> Result<Self, notify::Error> {
+        let state_path = match root.comment_backend() {
+            CommentBackend::File => FileCommentStore::path_for(root),
+            CommentBackend::GitRef => git2::Repository::discover(root.path())
+                .map_err(|e| notify::Error::generic(e.message()))?
+                .commondir()
+                .join(COMMENTS_REF),
+        };
+        let dir = state_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| root.path().to_path_buf());
+        let file_name: OsString = state_path.file_name().unwrap_or_default().to_owned();
+        let (tx, rx) = mpsc::channel();
+
+        let watched_dir = dir.clone();
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(200),
+            move |res: DebounceEventResult| {
+                if let Ok(events) = res {
+                    // The file itself, or its directory appearing for the first time.
+                    let relevant = events.iter().any(|e| {
+                        e.path.file_name() == Some(file_name.as_os_str()) || e.path == watched_dir
+                    });
+                    if relevant {
+                        let _send_result = tx.send(WatchEvent::Changed);
+                    }
+                }
+            },
+        )?;
+
+        let watching_dir = dir.is_dir();
+        let target = match (watching_dir, dir.parent()) {
+            (false, Some(parent)) => parent,
+            _ => dir.as_path(),
+        };
+        debouncer
+            .watcher()
+            .watch(target, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            rx,
+            dir,
+            watching_dir,
+            watcher: debouncer,
+        })
+    }
+
+    /// Poll for changes to the comments file without blocking.
+    pub fn poll(&mut self) -> Option<WatchEvent> {
+        if !self.watching_dir
+            && self.dir.is_dir()
+            && self
+                .watcher
+                .watcher()
+                .watch(&self.dir, RecursiveMode::NonRecursive)
+                .is_ok()
+        {
+            self.watching_dir = true;
+            if let Some(parent) = self.dir.parent() {
+                let _unwatch_result = self.watcher.watcher().unwatch(parent);
+            }
+            // The file may have been written before the directory was watched.
+            while self.rx.try_recv().is_ok() {}
+            return Some(WatchEvent::Changed);
+        }
+
+        match self.rx.try_recv() {
+            Ok(event) => {
+                while self.rx.try_recv().is_ok() {}
+                Some(event)
+            }
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => None,
+        }
+    }
+}
+
 /// Check if a path should be ignored for watching.
 fn is_ignored_path(path: &Path, repo_root: &Path) -> bool {
     // Get path relative to repo root
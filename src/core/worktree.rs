@@ -0,0 +1,187 @@
+//! Git worktree discovery.
+
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+
+use crate::core::{RepoError, RepoRoot};
+
+/// A checkout that belongs to the same git repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeInfo {
+    /// Worktree name (`main` for the main checkout).
+    pub name: String,
+    /// Canonicalized checkout path.
+    pub path: PathBuf,
+    /// Checked-out branch name, or short commit id when detached.
+    pub head: Option<String>,
+    /// Whether this is the main checkout rather than a linked worktree.
+    pub is_main: bool,
+    /// Whether this is the checkout quickdiff is currently showing.
+    pub is_current: bool,
+}
+
+/// List the main checkout (absent for bare repos) and all valid linked worktrees.
+#[must_use = "this returns a Result that should be checked"]
+pub fn list_worktrees(root: &RepoRoot) -> Result<Vec<WorktreeInfo>, RepoError> {
+    if !root.is_git() {
+        return Err(RepoError::NotARepo("git"));
+    }
+    let repo = Repository::open(root.path()).map_err(|_| RepoError::NotARepo("git"))?;
+    let common = Repository::open(repo.commondir())
+        .map_err(|e| RepoError::GitError(format!("failed to open common dir: {}", e)))?;
+    Ok(worktrees_of(&common, Some(root.path())))
+}
+
+fn worktrees_of(common: &Repository, current: Option<&Path>) -> Vec<WorktreeInfo> {
+    let mut worktrees = Vec::new();
+
+    if let Some(main) = common.workdir().and_then(|p| p.canonicalize().ok()) {
+        worktrees.push(WorktreeInfo {
+            name: "main".to_string(),
+            head: head_label(common),
+            is_current: current == Some(main.as_path()),
+            path: main,
+            is_main: true,
+        });
+    }
+
+    let names = common.worktrees().map(|names| {
+        names
+            .iter()
+            .flatten()
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    });
+    for name in names.unwrap_or_default() {
+        let Ok(worktree) = common.find_worktree(&name) else {
+            continue;
+        };
+        if worktree.validate().is_err() {
+            continue; // pruned or moved checkout
+        }
+        let Ok(path) = worktree.path().canonicalize() else {
+            continue;
+        };
+        let head = Repository::open_from_worktree(&worktree)
+            .ok()
+            .and_then(|repo| head_label(&repo));
+        worktrees.push(WorktreeInfo {
+            name,
+            head,
+            is_current: current == Some(path.as_path()),
+            path,
+            is_main: false,
+        });
+    }
+
+    worktrees
+}
+
+fn head_label(repo: &Repository) -> Option<String> {
+    let head = repo.head().ok()?;
+    if head.is_branch() {
+        head.shorthand().map(str::to_string)
+    } else {
+        head.target()
+            .map(|oid| oid.to_string().chars().take(8).collect())
+    }
+}
+
+/// Build the error for a bare repo, listing its worktrees as suggestions.
+pub(crate) fn bare_repository_error(repo: &Repository) -> RepoError {
+    let paths: Vec<String> = worktrees_of(repo, None)
+        .into_iter()
+        .map(|w| w.path.display().to_string())
+        .collect();
+    if paths.is_empty() {
+        RepoError::BareRepository(String::new())
+    } else {
+        RepoError::BareRepository(format!(": {}", paths.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::VcsPreference;
+
+    fn init_with_commit(path: &Path) -> Repository {
+        let repo = Repository::init(path).unwrap();
+        std::fs::write(path.join("a.txt"), "a\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@test.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        drop(tree);
+        repo
+    }
+
+    #[test]
+    fn lists_main_and_linked_worktrees() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main");
+        std::fs::create_dir(&main).unwrap();
+        let repo = init_with_commit(&main);
+        let linked = dir.path().join("feature");
+        repo.worktree("feature", &linked, None).unwrap();
+
+        let root = RepoRoot::discover(&linked, VcsPreference::Git).unwrap();
+        assert!(root.is_linked_worktree());
+
+        let worktrees = list_worktrees(&root).unwrap();
+        assert_eq!(worktrees.len(), 2);
+        assert!(worktrees[0].is_main && !worktrees[0].is_current);
+        assert_eq!(worktrees[1].name, "feature");
+        assert!(worktrees[1].is_current);
+        assert_eq!(worktrees[1].head.as_deref(), Some("feature"));
+    }
+
+    #[test]
+    fn shared_state_is_keyed_by_common_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("main");
+        std::fs::create_dir(&main).unwrap();
+        let repo = init_with_commit(&main);
+        let linked = dir.path().join("feature");
+        repo.worktree("feature", &linked, None).unwrap();
+
+        let a = RepoRoot::discover(&main, VcsPreference::Git).unwrap();
+        let b = RepoRoot::discover(&linked, VcsPreference::Git).unwrap();
+        assert_ne!(a.state_dir(), b.state_dir());
+        assert_ne!(a.state_key(), b.state_key());
+
+        let a = a.with_shared_state(true);
+        let b = b.with_shared_state(true);
+        assert_eq!(a.state_dir(), b.state_dir());
+        assert_eq!(a.state_key(), b.state_key());
+        assert!(a.state_dir().ends_with(".git/quickdiff"));
+    }
+
+    #[test]
+    fn bare_repo_reports_worktrees() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("src");
+        std::fs::create_dir(&source).unwrap();
+        init_with_commit(&source);
+        let bare_path = dir.path().join("repo.git");
+        let bare = git2::build::RepoBuilder::new()
+            .bare(true)
+            .clone(source.to_str().unwrap(), &bare_path)
+            .unwrap();
+        let wt = dir.path().join("wt");
+        bare.worktree("wt", &wt, None).unwrap();
+
+        let err = RepoRoot::discover(&bare_path, VcsPreference::Git).unwrap_err();
+        assert!(matches!(err, RepoError::BareRepository(_)));
+        assert!(err.to_string().contains("wt"));
+
+        let root = RepoRoot::discover(&wt, VcsPreference::Git).unwrap();
+        let worktrees = list_worktrees(&root).unwrap();
+        assert_eq!(worktrees.len(), 1);
+        assert!(!worktrees[0].is_main);
+    }
+}
@@ -19,6 +19,7 @@ pub mod core;
 pub mod highlight;
 pub mod metrics;
 pub mod prelude;
+pub mod report;
 pub mod theme;
 pub mod ui;
 pub mod web;
@@ -14,9 +14,9 @@ use crossterm::{
 };
 use ratatui::prelude::*;
 
-use quickdiff::cli::run_comments_command;
+use quickdiff::cli::{run_comments_command, run_serve};
 use quickdiff::core::{
-    ConfigOverrides, DiffSource, RepoRoot, VcsPreference, load_preferences,
+    ConfigOverrides, DiffSource, PathFilter, RepoRoot, VcsPreference, load_preferences,
     looks_like_unified_diff, read_stdin_text,
 };
 use quickdiff::ui::{App, handle_input, render};
@@ -37,9 +37,12 @@ struct Cli {
     #[arg(value_name = "REV")]
     revision: Option<String>,
 
-    /// Filter to specific file(s)
-    #[arg(short = 'f', long = "file", value_name = "PATH")]
-    file: Option<String>,
+    /// Filter files by pathspec; repeatable, supports globs and `:!` excludes
+    ///
+    /// Literal paths match a file or directory from the repo root, not any
+    /// path containing the text: use `-f '**/foo*'` to find `src/foo.rs`.
+    #[arg(short = 'f', long = "file", value_name = "PATHSPEC")]
+    file: Vec<String>,
 
     /// Color theme (default, dracula, catppuccin, nord, gruvbox, tokyonight, rosepine, onedark, solarized)
     #[arg(short = 't', long = "theme", value_name = "THEME")]
@@ -49,10 +52,18 @@ struct Cli {
     #[arg(long = "pr", value_name = "NUMBER")]
     pr: Option<Option<u32>>,
 
+    /// Compare versions of a jj change from its evolog (default: @)
+    #[arg(long = "evolog", value_name = "CHANGE")]
+    evolog: Option<Option<String>>,
+
     /// Read unified diff from stdin and render in TUI (pager mode)
     #[arg(long = "stdin")]
     stdin: bool,
 
+    /// Accept remote-control commands on a Unix socket (default: .quickdiff/control.sock)
+    #[arg(long = "listen", value_name = "SOCKET")]
+    listen: Option<Option<std::path::PathBuf>>,
+
     /// Force VCS backend (default: auto-detect, prefers jj)
     #[arg(long = "vcs", value_name = "TYPE", value_parser = parse_vcs_preference)]
     vcs: Option<VcsPreference>,
@@ -145,9 +156,10 @@ fn open_html_in_browser(path: &str) {
 /// Find a subcommand in args, skipping flags and their values.
 /// Returns (index, subcommand) if found.
 fn find_subcommand(args: &[String]) -> Option<(usize, &str)> {
-    const SUBCOMMANDS: &[&str] = &["comments", "web", "pager", "difftool"];
+    const SUBCOMMANDS: &[&str] = &["comments", "web", "pager", "difftool", "serve", "ctl"];
     const FLAGS_WITH_VALUES: &[&str] = &[
         "-c", "--commit", "-b", "--base", "-f", "--file", "-t", "--theme", "--pr", "--vcs",
+        "--evolog", "--listen",
     ];
 
     let mut i = 1; // skip program name
@@ -180,6 +192,8 @@ fn main() -> ExitCode {
             "web" => return run_cli_web(&args[idx + 1..]),
             "pager" => return run_cli_pager(&args[idx + 1..]),
             "difftool" => return run_cli_difftool(&args[idx + 1..]),
+            "serve" => return run_cli_serve(&args[idx + 1..]),
+            "ctl" => return run_cli_ctl(&args[idx + 1..]),
             _ => {}
         }
     }
@@ -191,7 +205,7 @@ fn main() -> ExitCode {
     quickdiff::metrics::init();
 
     if cli.stdin {
-        return run_tui_patch(cli.theme, cli.vcs.unwrap_or(VcsPreference::Auto));
+        return run_tui_patch(cli.theme, cli.file, cli.vcs.unwrap_or(VcsPreference::Auto));
     }
 
     // Determine diff source
@@ -206,7 +220,7 @@ fn main() -> ExitCode {
 
     // Run TUI
     let vcs = cli.vcs.unwrap_or(VcsPreference::Auto);
-    match run_tui(source, cli.file, cli.theme, pr_number, vcs) {
+    match run_tui(source, cli.file, cli.theme, pr_number, vcs, cli.listen) {
         Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
             eprintln!("Error: {}", e);
@@ -218,6 +232,14 @@ fn main() -> ExitCode {
 /// Parse CLI arguments into a DiffSource.
 fn parse_diff_source(cli: &Cli) -> DiffSource {
     // Explicit flags take precedence.
+    if let Some(ref change) = cli.evolog {
+        // Versions are picked once the repository is known.
+        return DiffSource::Evolog {
+            change: change.clone().unwrap_or_else(|| "@".to_string()),
+            from: String::new(),
+            to: String::new(),
+        };
+    }
     if let Some(ref commit) = cli.commit {
         return DiffSource::Commit(commit.clone());
     }
@@ -302,9 +324,63 @@ fn run_cli_comments(args: &[String]) -> ExitCode {
         }
     };
 
+    let loaded = load_preferences(repo.path(), &ConfigOverrides::default());
+    let repo = repo
+        .with_shared_state(loaded.shared_state)
+        .with_comment_backend(loaded.comments_backend);
     run_comments_command(&repo, args)
 }
 
+/// Run the JSON-RPC review server.
+fn run_cli_serve(args: &[String]) -> ExitCode {
+    if !args.iter().any(|arg| arg == "--stdio") {
+        eprintln!("Usage: quickdiff serve --stdio");
+        return ExitCode::from(1);
+    }
+
+    let cwd = match std::env::current_dir() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Failed to get current directory: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let repo = match RepoRoot::discover(&cwd, VcsPreference::Auto) {
+        Ok(repo) => repo,
+        Err(quickdiff::core::RepoError::NotARepo(vcs)) => {
+            eprintln!("Error: Not inside a {} repository", vcs);
+            return ExitCode::from(1);
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let loaded = load_preferences(repo.path(), &ConfigOverrides::default());
+    let repo = repo
+        .with_shared_state(loaded.shared_state)
+        .with_comment_backend(loaded.comments_backend);
+    run_serve(&repo)
+}
+
+/// Run the remote-control client for a running TUI.
+#[cfg(unix)]
+fn run_cli_ctl(args: &[String]) -> ExitCode {
+    // The repository only provides the default socket path.
+    let repo = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| RepoRoot::discover(&cwd, VcsPreference::Auto).ok());
+    quickdiff::cli::run_ctl_command(repo.as_ref(), args)
+}
+
+#[cfg(not(unix))]
+fn run_cli_ctl(_args: &[String]) -> ExitCode {
+    eprintln!("Error: quickdiff ctl requires a Unix platform");
+    ExitCode::from(1)
+}
+
 /// Embedded web template (compiled into binary).
 const WEB_TEMPLATE: &str = include_str!("../web/template.html");
 
@@ -316,7 +392,7 @@ fn run_cli_web(args: &[String]) -> ExitCode {
     let mut stdin_mode = false;
     let mut open_browser = false;
     let mut output_path: Option<String> = None;
-    let mut file_filter: Option<String> = None;
+    let mut pathspecs: Vec<String> = Vec::new();
     let mut diffspec: Option<String> = None;
 
     let mut i = 0;
@@ -330,7 +406,7 @@ fn run_cli_web(args: &[String]) -> ExitCode {
             }
             "--file" | "-f" => {
                 i += 1;
-                file_filter = args.get(i).cloned();
+                pathspecs.extend(args.get(i).cloned());
             }
             arg if !arg.starts_with('-') => {
                 diffspec = Some(arg.to_string());
@@ -401,6 +477,12 @@ fn run_cli_web(args: &[String]) -> ExitCode {
         DiffSource::WorkingTree
     };
 
+    let loaded = load_preferences(repo.path(), &ConfigOverrides::default());
+    let file_filter = PathFilter::new(&pathspecs).with_excludes(&loaded.excludes);
+    let repo = repo
+        .with_shared_state(loaded.shared_state)
+        .with_comment_backend(loaded.comments_backend);
+
     // Build review data
     let input = quickdiff::web::WebInput {
         source,
@@ -458,9 +540,24 @@ fn run_cli_web(args: &[String]) -> ExitCode {
 }
 
 fn run_cli_pager(args: &[String]) -> ExitCode {
-    if !args.is_empty() {
-        eprintln!("Error: pager does not accept positional arguments");
-        return ExitCode::from(1);
+    let mut pathspecs: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--file" | "-f" => {
+                i += 1;
+                let Some(spec) = args.get(i) else {
+                    eprintln!("Error: {} requires a value", args[i - 1]);
+                    return ExitCode::from(1);
+                };
+                pathspecs.push(spec.clone());
+            }
+            _ => {
+                eprintln!("Error: pager does not accept positional arguments");
+                return ExitCode::from(1);
+            }
+        }
+        i += 1;
     }
 
     let input = match read_stdin_text() {
@@ -477,7 +574,7 @@ fn run_cli_pager(args: &[String]) -> ExitCode {
     }
 
     if looks_like_unified_diff(&input) {
-        run_tui_patch_from_text(input, None, VcsPreference::Auto, "stdin")
+        run_tui_patch_from_text(input, None, pathspecs, VcsPreference::Auto, "stdin")
     } else {
         print!("{}", input);
         ExitCode::SUCCESS
@@ -496,7 +593,7 @@ fn run_cli_difftool(args: &[String]) -> ExitCode {
         display_path: args.get(2).cloned().unwrap_or_else(|| args[1].clone()),
     };
 
-    match run_tui(source, None, None, None, VcsPreference::Auto) {
+    match run_tui(source, Vec::new(), None, None, VcsPreference::Auto, None) {
         Ok(()) => ExitCode::SUCCESS,
         Err(err) => {
             eprintln!("Error: {}", err);
@@ -506,7 +603,7 @@ fn run_cli_difftool(args: &[String]) -> ExitCode {
 }
 
 /// Run TUI in patch mode (stdin input).
-fn run_tui_patch(theme: Option<String>, vcs: VcsPreference) -> ExitCode {
+fn run_tui_patch(theme: Option<String>, pathspecs: Vec<String>, vcs: VcsPreference) -> ExitCode {
     let patch = match read_stdin_text() {
         Ok(text) => text,
         Err(err) => {
@@ -520,12 +617,13 @@ fn run_tui_patch(theme: Option<String>, vcs: VcsPreference) -> ExitCode {
         return ExitCode::from(1);
     }
 
-    run_tui_patch_from_text(patch, theme, vcs, "stdin")
+    run_tui_patch_from_text(patch, theme, pathspecs, vcs, "stdin")
 }
 
 fn run_tui_patch_from_text(
     patch: String,
     theme: Option<String>,
+    pathspecs: Vec<String>,
     vcs: VcsPreference,
     label: &str,
 ) -> ExitCode {
@@ -555,13 +653,24 @@ fn run_tui_patch_from_text(
             theme: theme.clone(),
         },
     );
-    let mut app = match App::new(repo, DiffSource::WorkingTree, None, loaded.prefs) {
+    let file_filter = PathFilter::new(&pathspecs).with_excludes(&loaded.excludes);
+    let repo = repo
+        .with_shared_state(loaded.shared_state)
+        .with_comment_backend(loaded.comments_backend);
+    let mut app = match App::new(
+        repo,
+        DiffSource::WorkingTree,
+        file_filter,
+        loaded.renames,
+        loaded.prefs,
+    ) {
         Ok(app) => app,
         Err(e) => {
             eprintln!("Error: {}", e);
             return ExitCode::from(1);
         }
     };
+    app.editor = loaded.editor;
     if let Some(warning) = loaded.warnings.into_iter().next() {
         app.ui.status = Some(warning);
     }
@@ -634,10 +743,11 @@ fn run_tui_loop_tty(app: &mut App) -> Result<()> {
 /// Run the TUI application.
 fn run_tui(
     source: DiffSource,
-    file_filter: Option<String>,
+    pathspecs: Vec<String>,
     theme: Option<String>,
     pr_number: Option<u32>,
     vcs: VcsPreference,
+    listen: Option<Option<std::path::PathBuf>>,
 ) -> Result<()> {
     // Set panic hook to ensure terminal cleanup
     let default_hook = panic::take_hook();
@@ -670,6 +780,14 @@ fn run_tui(
         std::process::exit(1);
     }
 
+    if let DiffSource::Evolog { change, .. } = &source {
+        if !repo.is_jj() {
+            eprintln!("Error: --evolog requires a jj repository");
+            std::process::exit(1);
+        }
+        source = quickdiff::core::latest_evolog_source(&repo, change)?;
+    }
+
     let loaded = load_preferences(
         repo.path(),
         &ConfigOverrides {
@@ -678,11 +796,36 @@ fn run_tui(
     );
 
     // Create app with diff source and file filter
-    let mut app = App::new(repo.clone(), source, file_filter, loaded.prefs)?;
+    let file_filter = PathFilter::new(&pathspecs).with_excludes(&loaded.excludes);
+    let repo = repo
+        .with_shared_state(loaded.shared_state)
+        .with_comment_backend(loaded.comments_backend);
+    let mut app = App::new(
+        repo.clone(),
+        source,
+        file_filter,
+        loaded.renames,
+        loaded.prefs,
+    )?;
+    app.editor = loaded.editor;
     if let Some(warning) = loaded.warnings.into_iter().next() {
         app.ui.status = Some(warning);
     }
 
+    if let Some(path) = listen {
+        #[cfg(unix)]
+        {
+            let path = path.unwrap_or_else(|| quickdiff::core::default_control_socket(&repo));
+            app.listen(&path)
+                .with_context(|| format!("Failed to listen on {}", path.display()))?;
+        }
+        #[cfg(not(unix))]
+        {
+            let _unused = path;
+            anyhow::bail!("--listen requires a Unix platform");
+        }
+    }
+
     // Handle PR mode initialization
     if let Some(n) = pr_number {
         if n == 0 {
@@ -694,7 +837,7 @@ fn run_tui(
                 eprintln!("Error: GitHub CLI not available. Run 'gh auth login'");
                 std::process::exit(1);
             }
-            match quickdiff::core::list_prs(repo.path(), quickdiff::core::PRFilter::All) {
+            match quickdiff::core::list_prs(repo.path(), &quickdiff::core::PRQuery::default()) {
                 Ok(prs) => {
                     if let Some(pr) = prs.into_iter().find(|p| p.number == n) {
                         app.load_pr(pr);
@@ -712,7 +855,11 @@ fn run_tui(
     }
 
     // Check for empty changeset (skip in PR mode - files come from PR)
-    if app.files.is_empty() && !app.pr.active && app.ui.mode != quickdiff::ui::Mode::PRPicker {
+    if app.files.is_empty()
+        && !app.pr.active
+        && app.ui.mode != quickdiff::ui::Mode::PRPicker
+        && !matches!(app.source, DiffSource::Evolog { .. })
+    {
         println!("No changes detected");
         return Ok(());
     }
@@ -743,6 +890,9 @@ fn run_event_loop<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resu
         app.poll_worker();
         app.poll_pr_worker();
         app.poll_watcher();
+        app.poll_comments_watcher();
+        #[cfg(unix)]
+        app.poll_control();
 
         // Only redraw if dirty or on resize
         if app.ui.dirty {
@@ -0,0 +1,493 @@
+//! Markdown and plain-text review reports.
+//!
+//! A report lists the open comments of a changeset grouped by file, each with a
+//! short excerpt of the diff around it and the enclosing scope, for pasting
+//! into chat or a PR description.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::core::{
+    AnchorMatch, ChangeKind, ChangedFile, Comment, CommentContext, DiffResult, DiffSide,
+    DiffSource, RelPath, RepoRoot, TextBuffer, ViewedStore, diff_source_display,
+    load_diff_contents, open_comment_store, relocate_anchor,
+};
+use crate::highlight::{LanguageId, ScopeInfo, find_enclosing_scope, query_scopes};
+
+/// Rows of context shown around the commented lines.
+const EXCERPT_CONTEXT: usize = 2;
+/// Longest excerpt, in diff rows.
+const EXCERPT_MAX_ROWS: usize = 12;
+
+/// Output format for a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// GitHub-flavoured markdown.
+    #[default]
+    Markdown,
+    /// Plain text.
+    Text,
+}
+
+impl ReportFormat {
+    /// Parse a format name (`markdown`/`md` or `text`/`txt`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Some(Self::Markdown),
+            "text" | "txt" | "plain" => Some(Self::Text),
+            _ => None,
+        }
+    }
+}
+
+/// What to report on.
+pub struct ReportInput<'a> {
+    /// Diff source the files come from.
+    pub source: &'a DiffSource,
+    /// Files in the changeset.
+    pub files: &'a [ChangedFile],
+    /// Merge base for `DiffSource::Base`, to avoid recomputing it per file.
+    pub merge_base: Option<&'a str>,
+    /// Viewed state for the counts.
+    pub viewed: &'a dyn ViewedStore,
+}
+
+/// A review report, ready to render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewReport {
+    /// Diff source summary.
+    pub source: String,
+    /// Files in the changeset.
+    pub total_files: usize,
+    /// Files marked viewed.
+    pub viewed_files: usize,
+    /// Files with open comments, in changeset order.
+    pub files: Vec<ReportFile>,
+}
+
+/// Open comments on one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportFile {
+    /// Repo-relative path.
+    pub path: String,
+    /// Comments, by position.
+    pub comments: Vec<ReportComment>,
+}
+
+/// One comment in a report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportComment {
+    /// Comment id.
+    pub id: u64,
+    /// Author name, without the email address.
+    pub author: Option<String>,
+    /// Kind name, if set.
+    pub kind: Option<&'static str>,
+    /// Comment text.
+    pub message: String,
+    /// Where the comment sits now: side and 1-based inclusive lines.
+    pub location: Option<(DiffSide, usize, usize)>,
+    /// Enclosing scope, e.g. `fn parse`.
+    pub scope: Option<String>,
+    /// Diff excerpt in unified form (`+`, `-` or space, then the line).
+    pub excerpt: Vec<String>,
+    /// Replies as (author name, message).
+    pub replies: Vec<(String, String)>,
+}
+
+impl ReviewReport {
+    /// Number of open comments in the report.
+    pub fn comment_count(&self) -> usize {
+        self.files.iter().map(|f| f.comments.len()).sum()
+    }
+
+    /// Render the report.
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.render_markdown(),
+            ReportFormat::Text => self.render_text(),
+        }
+    }
+
+    fn summary_line(&self) -> String {
+        format!(
+            "{} of {} files viewed ({} unviewed), {} open comment{}",
+            self.viewed_files,
+            self.total_files,
+            self.total_files.saturating_sub(self.viewed_files),
+            self.comment_count(),
+            if self.comment_count() == 1 { "" } else { "s" }
+        )
+    }
+
+    fn render_markdown(&self) -> String {
+        let mut lines = vec![
+            format!("# Review: {}", self.source),
+            String::new(),
+            format!("{}.", self.summary_line()),
+        ];
+        for file in &self.files {
+            lines.push(String::new());
+            lines.push(format!("## `{}`", file.path));
+            for c in &file.comments {
+                let mut header = format!("**#{}**", c.id);
+                header.push_str(&c.describe(|scope| format!("`{}`", scope)));
+                if let Some(author) = &c.author {
+                    header.push_str(" · ");
+                    header.push_str(author);
+                }
+                lines.extend([String::new(), header, String::new()]);
+                lines.push(c.message.trim_end().to_string());
+                if !c.excerpt.is_empty() {
+                    let fence = code_fence(&c.excerpt);
+                    lines.push(String::new());
+                    lines.push(format!("{}diff", fence));
+                    lines.extend(c.excerpt.iter().cloned());
+                    lines.push(fence);
+                }
+                for (author, message) in &c.replies {
+                    lines.push(String::new());
+                    lines.push(format!("> **{}:** {}", author, message.trim_end()));
+                }
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+
+    fn render_text(&self) -> String {
+        let mut lines = vec![format!("Review: {}", self.source), self.summary_line()];
+        for file in &self.files {
+            lines.push(String::new());
+            lines.push(file.path.clone());
+            for c in &file.comments {
+                let mut header = format!("  #{}", c.id);
+                header.push_str(&c.describe(str::to_string));
+                if let Some(author) = &c.author {
+                    header.push_str(&format!(" ({})", author));
+                }
+                lines.push(header);
+                for line in c.message.trim_end().lines() {
+                    lines.push(format!("    {}", line));
+                }
+                for line in &c.excerpt {
+                    lines.push(format!("      | {}", line));
+                }
+                for (author, message) in &c.replies {
+                    lines.push(format!("    ↳ {}: {}", author, message.trim_end()));
+                }
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+}
+
+impl ReportComment {
+    /// Kind, location and scope, each preceded by a space.
+    fn describe(&self, quote_scope: impl Fn(&str) -> String) -> String {
+        let mut out = String::new();
+        if let Some(kind) = self.kind {
+            out.push_str(&format!(" [{}]", kind));
+        }
+        if let Some(location) = self.location {
+            out.push(' ');
+            out.push_str(&format_location(location));
+        }
+        if let Some(scope) = &self.scope {
+            out.push_str(" in ");
+            out.push_str(&quote_scope(scope));
+        }
+        out
+    }
+}
+
+fn format_location((side, start, end): (DiffSide, usize, usize)) -> String {
+    if start == end {
+        format!("{} L{}", side.as_str(), start)
+    } else {
+        format!("{} L{}-{}", side.as_str(), start, end)
+    }
+}
+
+/// A backtick fence longer than any run of backticks in the excerpt.
+fn code_fence(lines: &[String]) -> String {
+    let longest = lines
+        .iter()
+        .flat_map(|line| line.split(|c| c != '`'))
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat((longest + 1).max(3))
+}
+
+/// `Name <email>` → `Name`.
+fn author_name(author: &str) -> String {
+    author.split(" <").next().unwrap_or(author).to_string()
+}
+
+/// Loaded diff and lazily computed scopes for one file.
+struct FileDiff {
+    old: TextBuffer,
+    new: TextBuffer,
+    diff: DiffResult,
+    lang: LanguageId,
+    scopes: HashMap<DiffSide, Vec<ScopeInfo>>,
+}
+
+impl FileDiff {
+    fn scope_at(&mut self, side: DiffSide, line: usize) -> Option<String> {
+        let (lang, old, new) = (self.lang, &self.old, &self.new);
+        let scopes = self.scopes.entry(side).or_insert_with(|| {
+            let buffer = match side {
+                DiffSide::Old => old,
+                DiffSide::New => new,
+            };
+            query_scopes(lang, &String::from_utf8_lossy(buffer.as_bytes()))
+        });
+        find_enclosing_scope(scopes, line).map(|s| format!("{} {}", s.kind, s.name))
+    }
+}
+
+/// Build a report of the open comments on `input.source`.
+pub fn build_review_report(repo: &RepoRoot, input: ReportInput<'_>) -> Result<ReviewReport> {
+    let context = CommentContext::for_source(input.source);
+    let store = open_comment_store(repo)?;
+    let mut by_path: HashMap<&RelPath, Vec<&Comment>> = HashMap::new();
+    for comment in store.list(false) {
+        if comment.context.matches(&context) {
+            by_path.entry(&comment.path).or_default().push(comment);
+        }
+    }
+
+    // Changeset order first, then comments on files that left the changeset.
+    let mut paths: Vec<&RelPath> = input
+        .files
+        .iter()
+        .map(|f| &f.path)
+        .filter(|p| by_path.contains_key(p))
+        .collect();
+    let mut rest: Vec<&RelPath> = by_path
+        .keys()
+        .copied()
+        .filter(|p| !input.files.iter().any(|f| &f.path == *p))
+        .collect();
+    rest.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    paths.extend(rest);
+
+    let mut files = Vec::new();
+    for path in paths {
+        let mut loaded = input
+            .files
+            .iter()
+            .find(|f| &f.path == path)
+            .and_then(|file| load_file_diff(repo, input.source, file, input.merge_base));
+
+        let mut comments: Vec<(Option<AnchorMatch>, ReportComment)> = by_path[path]
+            .iter()
+            .map(|c| {
+                let found = loaded
+                    .as_ref()
+                    .and_then(|f| relocate_anchor(&c.anchor, &f.diff, &f.old, &f.new));
+                let report = report_comment(c, found.as_ref(), loaded.as_mut());
+                (found, report)
+            })
+            .collect();
+        comments.sort_by_key(|(found, c)| {
+            (
+                found
+                    .as_ref()
+                    .map_or(usize::MAX, |m| m.row.unwrap_or(m.start_line)),
+                c.id,
+            )
+        });
+
+        files.push(ReportFile {
+            path: path.as_str().to_string(),
+            comments: comments.into_iter().map(|(_, c)| c).collect(),
+        });
+    }
+
+    let viewed_files = input
+        .files
+        .iter()
+        .filter(|f| input.viewed.is_viewed(&f.path))
+        .count();
+
+    Ok(ReviewReport {
+        source: diff_source_display(input.source, repo),
+        total_files: input.files.len(),
+        viewed_files,
+        files,
+    })
+}
+
+fn load_file_diff(
+    repo: &RepoRoot,
+    source: &DiffSource,
+    file: &ChangedFile,
+    merge_base: Option<&str>,
+) -> Option<FileDiff> {
+    let (old_bytes, new_bytes) = load_diff_contents(repo, source, file, merge_base).ok()?;
+    let old = TextBuffer::new(&old_bytes);
+    let new = TextBuffer::new(&new_bytes);
+    let diff = DiffResult::compute(&old, &new);
+    let lang = Path::new(file.path.as_str())
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(LanguageId::from_extension)
+        .unwrap_or(LanguageId::Plain);
+    Some(FileDiff {
+        old,
+        new,
+        diff,
+        lang,
+        scopes: HashMap::new(),
+    })
+}
+
+fn report_comment(
+    comment: &Comment,
+    found: Option<&AnchorMatch>,
+    loaded: Option<&mut FileDiff>,
+) -> ReportComment {
+    let (excerpt, scope) = match (found, loaded) {
+        (Some(found), Some(loaded)) => (
+            diff_excerpt(&loaded.diff, found),
+            loaded.scope_at(found.side, found.start_line),
+        ),
+        _ => (Vec::new(), None),
+    };
+    ReportComment {
+        id: comment.id,
+        author: comment.author.as_deref().map(author_name),
+        kind: comment.kind.map(|k| k.as_str()),
+        message: comment.message.clone(),
+        location: found.map(|m| (m.side, m.start_line + 1, m.end_line + 1)),
+        scope,
+        excerpt,
+        replies: comment
+            .replies
+            .iter()
+            .map(|r| {
+                (
+                    r.author
+                        .as_deref()
+                        .map_or_else(|| "anonymous".to_string(), author_name),
+                    r.message.clone(),
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Unified-diff lines around a match, clipped to its hunk.
+fn diff_excerpt(diff: &DiffResult, found: &AnchorMatch) -> Vec<String> {
+    let rows = diff.rows();
+    let on_side = |i: usize| {
+        let line = match found.side {
+            DiffSide::Old => rows[i].old.as_ref(),
+            DiffSide::New => rows[i].new.as_ref(),
+        };
+        line.is_some_and(|l| (found.start_line..=found.end_line).contains(&l.line_num))
+    };
+    let Some(first) = (0..rows.len()).find(|&i| on_side(i)) else {
+        return Vec::new();
+    };
+    let last = (first..rows.len())
+        .rev()
+        .find(|&i| on_side(i))
+        .unwrap_or(first);
+
+    let (lo, hi) = match diff.hunk_at_row(first).and_then(|h| diff.hunks().get(h)) {
+        Some(h) => (h.start_row, h.start_row + h.row_count - 1),
+        None => (0, rows.len() - 1),
+    };
+    let start = first.saturating_sub(EXCERPT_CONTEXT).max(lo);
+    let end = (last + EXCERPT_CONTEXT).min(hi).max(last);
+
+    let mut lines = Vec::new();
+    for row in &rows[start..=end] {
+        match row.kind {
+            ChangeKind::Equal => {
+                if let Some(l) = &row.new {
+                    lines.push(format!(" {}", l.content));
+                }
+            }
+            ChangeKind::Delete => {
+                if let Some(l) = &row.old {
+                    lines.push(format!("-{}", l.content));
+                }
+            }
+            ChangeKind::Insert => {
+                if let Some(l) = &row.new {
+                    lines.push(format!("+{}", l.content));
+                }
+            }
+            ChangeKind::Replace => {
+                if let Some(l) = &row.old {
+                    lines.push(format!("-{}", l.content));
+                }
+                if let Some(l) = &row.new {
+                    lines.push(format!("+{}", l.content));
+                }
+            }
+        }
+    }
+    if lines.len() > EXCERPT_MAX_ROWS {
+        lines.truncate(EXCERPT_MAX_ROWS);
+        lines.push(" …".to_string());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> ReviewReport {
+        ReviewReport {
+            source: "worktree".to_string(),
+            total_files: 3,
+            viewed_files: 1,
+            files: vec![ReportFile {
+                path: "src/lib.rs".to_string(),
+                comments: vec![ReportComment {
+                    id: 4,
+                    author: Some("Alice".to_string()),
+                    kind: Some("bug"),
+                    message: "off by one".to_string(),
+                    location: Some((DiffSide::New, 2, 3)),
+                    scope: Some("fn parse".to_string()),
+                    excerpt: vec![" fn parse() {".to_string(), "+    let n = 1;".to_string()],
+                    replies: vec![("Bob".to_string(), "fixed".to_string())],
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn markdown_groups_comments_under_files() {
+        let md = report().render(ReportFormat::Markdown);
+        assert!(md.starts_with("# Review: worktree\n"));
+        assert!(md.contains("1 of 3 files viewed (2 unviewed), 1 open comment."));
+        assert!(md.contains("## `src/lib.rs`"));
+        assert!(md.contains("**#4** [bug] new L2-3 in `fn parse` · Alice"));
+        assert!(md.contains("```diff\n fn parse() {\n+    let n = 1;\n```"));
+        assert!(md.contains("> **Bob:** fixed"));
+    }
+
+    #[test]
+    fn text_indents_messages_and_excerpts() {
+        let text = report().render(ReportFormat::Text);
+        assert!(text.contains("\nsrc/lib.rs\n  #4 [bug] new L2-3 in fn parse (Alice)\n"));
+        assert!(text.contains("    off by one\n      |  fn parse() {\n"));
+        assert!(text.contains("    ↳ Bob: fixed"));
+    }
+
+    #[test]
+    fn fence_outgrows_backticks_in_excerpt() {
+        assert_eq!(code_fence(&["+let s = \"```\";".to_string()]), "````");
+        assert_eq!(code_fence(&["+plain".to_string()]), "```");
+    }
+}
@@ -1,49 +1,165 @@
-use std::collections::HashMap;
-
-use super::{App, CommentViewItem, Focus, Mode};
+use super::pr::review_thread_summary;
+use super::{App, CommentViewItem, DiffPaneMode, Focus, Mode, load_open_comment_counts};
 use crate::core::{
-    Anchor, CommentStatus, CommentStore, FileCommentStore, Selector, format_anchor_summary,
-    selector_from_hunk,
+    Anchor, AnchorMatch, AnchorStatus, CommentId, CommentKind, CommentReply, CommentStatus,
+    DiffSide, NewComment, Selector, apply_suggestion_to_file, comment_author, context_from_hunk,
+    format_anchor_summary, hunk_line_span, open_comment_store, relocate_anchor, row_for_line,
+    selector_from_context, selector_from_hunk, selector_from_line_range, split_suggestion,
+    suggestion_original,
 };
 
 impl App {
-    /// Start adding a comment on the current hunk.
-    pub fn start_add_comment(&mut self) {
-        let Some(diff) = &self.diff else {
-            self.ui.error = Some("No diff available".to_string());
-            self.ui.dirty = true;
-            return;
-        };
+    /// Place a comment anchor on the loaded diff.
+    pub(crate) fn relocate_comment(&self, anchor: &Anchor) -> Option<AnchorMatch> {
+        relocate_anchor(
+            anchor,
+            self.diff.as_ref()?,
+            self.old_buffer.as_ref()?,
+            self.new_buffer.as_ref()?,
+        )
+    }
 
-        if diff.hunks().is_empty() {
-            self.ui.error = Some("No hunks to comment on".to_string());
+    /// Start a visual line selection at the current row, or cancel the active one.
+    pub fn toggle_line_selection(&mut self) {
+        if self.viewer.selection_anchor.take().is_some() {
+            self.ui.status = None;
             self.ui.dirty = true;
             return;
         }
 
         let Some(row) = self.view_row_to_diff_row(self.viewer.scroll_y) else {
-            self.ui.error = Some("Not on a hunk - navigate to a hunk first".to_string());
+            self.ui.error = Some("No lines to select".to_string());
             self.ui.dirty = true;
             return;
         };
 
-        if diff.hunk_at_row(row).is_none() {
-            self.ui.error = Some("Not on a hunk - navigate to a hunk first".to_string());
+        self.viewer.selection_anchor = Some(row);
+        self.ui.status =
+            Some("Select lines: j/k to extend, c to comment, Esc to cancel".to_string());
+        self.ui.error = None;
+        self.ui.dirty = true;
+    }
+
+    /// Drop the visual line selection.
+    pub fn clear_line_selection(&mut self) {
+        self.viewer.selection_anchor = None;
+        self.ui.status = None;
+        self.ui.dirty = true;
+    }
+
+    /// Diff rows covered by the visual selection (inclusive).
+    pub fn selected_rows(&self) -> Option<(usize, usize)> {
+        let anchor = self.viewer.selection_anchor?;
+        let cursor = self.view_row_to_diff_row(self.viewer.scroll_y)?;
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
+    /// Side and 0-indexed inclusive line span of the visual selection.
+    ///
+    /// Prefers the new side unless only the old pane is shown or the
+    /// selection covers deleted lines only.
+    pub fn selected_line_range(&self) -> Option<(DiffSide, usize, usize)> {
+        let (first, last) = self.selected_rows()?;
+        let rows = self.diff.as_ref()?.rows().get(first..=last)?;
+        let sides = if self.viewer.pane_mode == DiffPaneMode::OldOnly {
+            [DiffSide::Old, DiffSide::New]
+        } else {
+            [DiffSide::New, DiffSide::Old]
+        };
+
+        sides.into_iter().find_map(|side| {
+            let lines = rows.iter().filter_map(|row| match side {
+                DiffSide::Old => row.old.as_ref().map(|l| l.line_num),
+                DiffSide::New => row.new.as_ref().map(|l| l.line_num),
+            });
+            let (start, end) = lines.fold(None, |acc: Option<(usize, usize)>, n| {
+                Some(acc.map_or((n, n), |(lo, hi)| (lo.min(n), hi.max(n))))
+            })?;
+            Some((side, start, end))
+        })
+    }
+
+    /// Start adding a comment on the current hunk (or the selected lines).
+    pub fn start_add_comment(&mut self) {
+        let Some(diff) = &self.diff else {
+            self.ui.error = Some("No diff available".to_string());
             self.ui.dirty = true;
             return;
+        };
+
+        if self.viewer.selection_anchor.is_some() {
+            if self.selected_line_range().is_none() {
+                self.ui.error = Some("Selection has no lines to comment on".to_string());
+                self.ui.dirty = true;
+                return;
+            }
+        } else if diff.hunks().is_empty() {
+            self.ui.error = Some("No hunks to comment on".to_string());
+            self.ui.dirty = true;
+            return;
+        } else {
+            let Some(row) = self.view_row_to_diff_row(self.viewer.scroll_y) else {
+                self.ui.error = Some("Not on a hunk - navigate to a hunk first".to_string());
+                self.ui.dirty = true;
+                return;
+            };
+
+            if diff.hunk_at_row(row).is_none() {
+                self.ui.error = Some("Not on a hunk - navigate to a hunk first".to_string());
+                self.ui.dirty = true;
+                return;
+            }
         }
 
         self.ui.mode = Mode::AddComment;
         self.comments.draft.clear();
         self.comments.draft_cursor = 0;
+        self.comments.draft_kind = None;
         self.ui.error = None;
         self.ui.status = None;
         self.ui.dirty = true;
     }
 
-    /// Cancel adding a comment.
+    /// Start replying to the selected comment's thread.
+    pub fn comments_start_reply(&mut self) {
+        if self.selected_comment_is_remote() {
+            return;
+        }
+        let Some(item) = self.comments.viewing.get(self.comments.selected) else {
+            return;
+        };
+        self.comments.reply_to = Some(item.id);
+        self.comments.draft.clear();
+        self.comments.draft_cursor = 0;
+        self.ui.mode = Mode::AddComment;
+        self.ui.dirty = true;
+    }
+
+    /// Start editing the selected comment's message.
+    pub fn comments_start_edit(&mut self) {
+        if self.selected_comment_is_remote() {
+            return;
+        }
+        let Some(item) = self.comments.viewing.get(self.comments.selected) else {
+            return;
+        };
+        self.comments.editing = Some(item.id);
+        self.comments.draft_kind = item.kind;
+        self.comments.draft = item.message.clone();
+        self.comments.draft_cursor = self.comments.draft.len();
+        self.ui.mode = Mode::AddComment;
+        self.ui.dirty = true;
+    }
+
+    /// Cancel adding a comment (or reply, or edit).
     pub fn cancel_add_comment(&mut self) {
-        self.ui.mode = Mode::Normal;
+        let from_overlay =
+            self.comments.reply_to.take().is_some() | self.comments.editing.take().is_some();
+        self.ui.mode = if from_overlay {
+            Mode::ViewComments
+        } else {
+            Mode::Normal
+        };
         self.comments.draft.clear();
         self.comments.draft_cursor = 0;
         self.ui.dirty = true;
@@ -57,6 +173,15 @@ impl App {
             return;
         }
 
+        if let Some(id) = self.comments.reply_to.take() {
+            self.save_reply(id);
+            return;
+        }
+        if let Some(id) = self.comments.editing.take() {
+            self.save_edit(id);
+            return;
+        }
+
         let Some(diff) = &self.diff else {
             self.ui.error = Some("No diff available".to_string());
             self.ui.mode = Mode::Normal;
@@ -64,20 +189,62 @@ impl App {
             return;
         };
 
-        let Some(row) = self.view_row_to_diff_row(self.viewer.scroll_y) else {
-            self.ui.error = Some("No hunk at current position".to_string());
-            self.ui.mode = Mode::Normal;
-            self.ui.dirty = true;
-            return;
-        };
+        let mut selectors = Vec::new();
+        let hunk_idx = if let Some((side, start, end)) = self.selected_line_range() {
+            let buffer = match side {
+                DiffSide::Old => self.old_buffer.as_ref(),
+                DiffSide::New => self.new_buffer.as_ref(),
+            };
+            let Some(range) = buffer.and_then(|b| selector_from_line_range(b, side, start, end))
+            else {
+                self.ui.error = Some("Failed to create comment anchor".to_string());
+                self.ui.mode = Mode::Normal;
+                self.ui.dirty = true;
+                return;
+            };
+            selectors.push(Selector::LineRangeV1(range));
+            if let Some(context) = buffer.and_then(|b| selector_from_context(b, side, start, end)) {
+                selectors.push(Selector::ContextV1(context));
+            }
 
-        let Some(hunk_idx) = diff.hunk_at_row(row) else {
-            self.ui.error = Some("No hunk at current position".to_string());
-            self.ui.mode = Mode::Normal;
-            self.ui.dirty = true;
-            return;
+            // Keep the enclosing hunk as a fallback when the selection stays inside one.
+            self.selected_rows().and_then(|(first, last)| {
+                let hunk = diff.hunk_at_row(first)?;
+                (diff.hunk_at_row(last) == Some(hunk)).then_some(hunk)
+            })
+        } else {
+            let Some(row) = self.view_row_to_diff_row(self.viewer.scroll_y) else {
+                self.ui.error = Some("No hunk at current position".to_string());
+                self.ui.mode = Mode::Normal;
+                self.ui.dirty = true;
+                return;
+            };
+
+            let Some(hunk_idx) = diff.hunk_at_row(row) else {
+                self.ui.error = Some("No hunk at current position".to_string());
+                self.ui.mode = Mode::Normal;
+                self.ui.dirty = true;
+                return;
+            };
+            Some(hunk_idx)
         };
 
+        if let Some(hunk_idx) = hunk_idx {
+            let Some(selector) = selector_from_hunk(diff, hunk_idx) else {
+                self.ui.error = Some("Failed to create comment anchor".to_string());
+                self.ui.mode = Mode::Normal;
+                self.ui.dirty = true;
+                return;
+            };
+            selectors.push(Selector::DiffHunkV1(selector));
+            if selectors.len() == 1
+                && let (Some(old), Some(new)) = (&self.old_buffer, &self.new_buffer)
+                && let Some(context) = context_from_hunk(diff, hunk_idx, old, new)
+            {
+                selectors.push(Selector::ContextV1(context));
+            }
+        }
+
         let Some(file) = self.selected_file() else {
             self.ui.error = Some("No file selected".to_string());
             self.ui.mode = Mode::Normal;
@@ -86,19 +253,22 @@ impl App {
         };
 
         let path = file.path.clone();
+        let anchor = Anchor { selectors };
 
-        let Some(selector) = selector_from_hunk(diff, hunk_idx) else {
-            self.ui.error = Some("Failed to create comment anchor".to_string());
-            self.ui.mode = Mode::Normal;
+        let (message, suggestion) = split_suggestion(&self.comments.draft);
+        if suggestion.is_some() && suggestion_original(&anchor).is_none() {
+            // Keep the editor open so the draft is not lost.
+            self.ui.error = Some("A suggestion needs new-side lines to replace".to_string());
             self.ui.dirty = true;
             return;
+        }
+        let message = if message.is_empty() {
+            "Suggested change".to_string()
+        } else {
+            message
         };
 
-        let anchor = Anchor {
-            selectors: vec![Selector::DiffHunkV1(selector)],
-        };
-
-        let mut store = match FileCommentStore::open(&self.repo) {
+        let mut store = match open_comment_store(&self.repo) {
             Ok(s) => s,
             Err(e) => {
                 self.ui.error = Some(format!("Failed to open comment store: {}", e));
@@ -108,17 +278,26 @@ impl App {
             }
         };
 
-        match store.add(
-            path.clone(),
-            self.comment_context.clone(),
-            self.comments.draft.clone(),
+        match store.add(NewComment {
+            path: path.clone(),
+            context: self.comment_context.clone(),
+            author: comment_author(&self.repo),
+            message,
+            kind: self.comments.draft_kind,
+            suggestion,
             anchor,
-        ) {
+        }) {
             Ok(id) => {
                 self.ui.status = Some(format!("Comment {} saved", id));
                 self.ui.error = None;
+                #[cfg(unix)]
+                self.emit_control(crate::core::ControlEvent::CommentAdded {
+                    id,
+                    path: path.as_str().to_string(),
+                });
                 *self.open_comment_counts.entry(path).or_insert(0) += 1;
-                self.commented_hunks.insert(hunk_idx);
+                self.viewer.selection_anchor = None;
+                self.refresh_current_file_comment_markers();
             }
             Err(e) => {
                 self.ui.error = Some(format!("Failed to save comment: {}", e));
@@ -131,6 +310,109 @@ impl App {
         self.ui.dirty = true;
     }
 
+    fn save_reply(&mut self, id: CommentId) {
+        let result = open_comment_store(&self.repo).and_then(|mut store| {
+            store.reply(id, comment_author(&self.repo), self.comments.draft.clone())
+        });
+        match result {
+            Ok(true) => {
+                self.ui.status = Some(format!("Replied to comment {}", id));
+                self.ui.error = None;
+            }
+            Ok(false) => self.ui.error = Some(format!("Comment {} not found", id)),
+            Err(e) => self.ui.error = Some(format!("Failed to save reply: {}", e)),
+        }
+
+        self.ui.mode = Mode::ViewComments;
+        self.comments.draft.clear();
+        self.comments.draft_cursor = 0;
+        self.refresh_viewing_comments();
+        self.ui.dirty = true;
+    }
+
+    fn save_edit(&mut self, id: CommentId) {
+        let result = open_comment_store(&self.repo).and_then(|mut store| {
+            store.edit(id, self.comments.draft.clone())?;
+            store.set_kind(id, self.comments.draft_kind)
+        });
+        match result {
+            Ok(true) => {
+                self.ui.status = Some(format!("Edited comment {}", id));
+                self.ui.error = None;
+                self.refresh_current_file_comment_markers();
+            }
+            Ok(false) => self.ui.error = Some(format!("Comment {} not found", id)),
+            Err(e) => self.ui.error = Some(format!("Failed to edit comment: {}", e)),
+        }
+
+        self.ui.mode = Mode::ViewComments;
+        self.comments.draft.clear();
+        self.comments.draft_cursor = 0;
+        self.refresh_viewing_comments();
+        self.ui.dirty = true;
+    }
+
+    /// Insert a suggestion block prefilled with the lines the new comment will target:
+    /// the new-side line selection, or else the current hunk's new-side lines.
+    pub fn comment_insert_suggestion(&mut self) {
+        if self.comments.reply_to.is_some() || self.comments.editing.is_some() {
+            return;
+        }
+        let span = match self.selected_line_range() {
+            Some((DiffSide::New, start, end)) => Some((start, end)),
+            Some((DiffSide::Old, ..)) => None,
+            None => self
+                .diff
+                .as_ref()
+                .zip(self.view_row_to_diff_row(self.viewer.scroll_y))
+                .and_then(|(diff, row)| diff.hunks().get(diff.hunk_at_row(row)?))
+                .and_then(|hunk| match hunk_line_span(hunk) {
+                    (DiffSide::New, start, end) => Some((start, end)),
+                    (DiffSide::Old, ..) => None,
+                }),
+        };
+        let lines = span.and_then(|(start, end)| {
+            let buffer = self.new_buffer.as_ref()?;
+            (start..=end)
+                .map(|i| buffer.line_str(i))
+                .collect::<Option<Vec<_>>>()
+        });
+        let Some(lines) = lines else {
+            self.ui.error = Some("A suggestion needs new-side lines to replace".to_string());
+            self.mark_dirty();
+            return;
+        };
+
+        let mut block = String::new();
+        if self.comments.draft_cursor > 0
+            && !self.comments.draft[..self.comments.draft_cursor].ends_with('\n')
+        {
+            block.push('\n');
+        }
+        block.push_str("```suggestion\n");
+        for line in &lines {
+            block.push_str(line);
+            block.push('\n');
+        }
+        block.push_str("```");
+        self.comments
+            .draft
+            .insert_str(self.comments.draft_cursor, &block);
+        // Leave the cursor on the last suggested line, ready to edit.
+        self.comments.draft_cursor += block.len() - "\n```".len();
+        self.ui.error = None;
+        self.mark_dirty();
+    }
+
+    /// Cycle the draft's severity. Replies have no severity of their own.
+    pub fn comment_cycle_kind(&mut self) {
+        if self.comments.reply_to.is_some() {
+            return;
+        }
+        self.comments.draft_kind = CommentKind::cycle(self.comments.draft_kind);
+        self.mark_dirty();
+    }
+
     /// Insert a character at the comment draft cursor.
     pub fn comment_insert_char(&mut self, ch: char) {
         self.comments.draft.insert(self.comments.draft_cursor, ch);
@@ -216,7 +498,7 @@ impl App {
 
         let include_resolved = self.comments.include_resolved;
 
-        let Ok(store) = FileCommentStore::open(&self.repo) else {
+        let Ok(store) = open_comment_store(&self.repo) else {
             self.comments.viewing.clear();
             self.comments.selected = 0;
             self.comments.scroll = 0;
@@ -229,14 +511,7 @@ impl App {
             .comments
             .viewing
             .get(self.comments.selected)
-            .map(|c| c.id);
-
-        let mut digest_to_start_row: HashMap<String, usize> = HashMap::new();
-        if let Some(diff) = &self.diff {
-            for h in diff.hunks() {
-                digest_to_start_row.insert(h.digest_hex.clone(), h.start_row);
-            }
-        }
+            .map(|c| (c.remote, c.id));
 
         let mut items: Vec<CommentViewItem> = Vec::new();
         for c in comments {
@@ -244,28 +519,73 @@ impl App {
                 continue;
             }
 
-            let mut hunk_start_row = None;
-            let mut stale = false;
-
-            if let Some(Selector::DiffHunkV1(h)) = c.anchor.selectors.first() {
-                if let Some(row) = digest_to_start_row.get(&h.digest_hex) {
-                    hunk_start_row = Some(*row);
-                } else if !digest_to_start_row.is_empty() {
-                    stale = true;
-                }
-            }
+            let found = self.relocate_comment(&c.anchor);
 
             items.push(CommentViewItem {
                 id: c.id,
                 status: c.status,
+                author: c.author.clone(),
                 message: c.message.clone(),
                 anchor_summary: format_anchor_summary(&c.anchor),
-                hunk_start_row,
-                stale,
+                hunk_start_row: found.as_ref().and_then(|m| m.row),
+                anchor_status: AnchorStatus::of(found.as_ref()),
+                confidence: found.map(|m| m.confidence),
+                replies: c.replies.clone(),
+                edited: !c.edits.is_empty(),
+                kind: c.kind,
+                suggestion: c.suggestion.clone().map(|text| {
+                    let original = suggestion_original(&c.anchor)
+                        .map(<[String]>::to_vec)
+                        .unwrap_or_default();
+                    (original, text)
+                }),
+                remote: false,
+                outdated: false,
             });
         }
 
-        items.sort_by_key(|c| (c.status != CommentStatus::Open, c.id));
+        let path = file.path.clone();
+        for (idx, thread, span) in self.review_threads_for(&path) {
+            if thread.is_resolved && !include_resolved {
+                continue;
+            }
+            let diff_row =
+                span.and_then(|(side, start, _)| row_for_line(self.diff.as_ref()?, side, start));
+            let mut comments = thread.comments.iter();
+            let first = comments.next();
+            items.push(CommentViewItem {
+                id: idx as CommentId,
+                status: if thread.is_resolved {
+                    CommentStatus::Resolved
+                } else {
+                    CommentStatus::Open
+                },
+                author: first.and_then(|c| c.author.clone()),
+                message: first.map(|c| c.body.clone()).unwrap_or_default(),
+                anchor_summary: review_thread_summary(thread),
+                hunk_start_row: diff_row,
+                anchor_status: if diff_row.is_some() {
+                    AnchorStatus::Anchored
+                } else {
+                    AnchorStatus::Stale
+                },
+                confidence: None,
+                replies: comments
+                    .map(|c| CommentReply {
+                        author: c.author.clone(),
+                        message: c.body.clone(),
+                        created_at_ms: None,
+                    })
+                    .collect(),
+                edited: false,
+                kind: None,
+                suggestion: None,
+                remote: true,
+                outdated: thread.is_outdated,
+            });
+        }
+
+        items.sort_by_key(|c| (c.status != CommentStatus::Open, c.remote, c.id));
 
         self.comments.viewing = items;
 
@@ -274,7 +594,7 @@ impl App {
                 .comments
                 .viewing
                 .iter()
-                .position(|c| c.id == id)
+                .position(|c| (c.remote, c.id) == id)
                 .unwrap_or(0),
             None => 0,
         };
@@ -288,6 +608,20 @@ impl App {
         }
     }
 
+    /// GitHub review threads are read-only: report it if one is selected.
+    fn selected_comment_is_remote(&mut self) -> bool {
+        let remote = self
+            .comments
+            .viewing
+            .get(self.comments.selected)
+            .is_some_and(|c| c.remote);
+        if remote {
+            self.ui.status = Some("GitHub review threads are read-only".to_string());
+            self.ui.dirty = true;
+        }
+        remote
+    }
+
     /// Show comments overlay for current file.
     pub fn show_comments(&mut self) {
         self.comments.include_resolved = false;
@@ -360,7 +694,7 @@ impl App {
 
     /// Resolve the currently selected comment.
     pub fn comments_resolve_selected(&mut self) {
-        if self.comments.viewing.is_empty() {
+        if self.comments.viewing.is_empty() || self.selected_comment_is_remote() {
             return;
         }
 
@@ -374,7 +708,7 @@ impl App {
             return;
         }
 
-        let mut store = match FileCommentStore::open(&self.repo) {
+        let mut store = match open_comment_store(&self.repo) {
             Ok(s) => s,
             Err(e) => {
                 self.ui.error = Some(format!("Failed to open comment store: {}", e));
@@ -411,4 +745,138 @@ impl App {
 
         self.ui.dirty = true;
     }
+
+    /// Resolve the selected comment, or reopen it if it is already resolved.
+    pub fn comments_toggle_resolved_selected(&mut self) {
+        match self.comments.viewing.get(self.comments.selected) {
+            Some(item) if item.status == CommentStatus::Resolved => self.comments_reopen_selected(),
+            Some(_) => self.comments_resolve_selected(),
+            None => {}
+        }
+    }
+
+    /// Reopen the currently selected comment.
+    pub fn comments_reopen_selected(&mut self) {
+        if self.selected_comment_is_remote() {
+            return;
+        }
+        let Some(id) = self
+            .comments
+            .viewing
+            .get(self.comments.selected)
+            .map(|c| c.id)
+        else {
+            return;
+        };
+
+        let result = open_comment_store(&self.repo).and_then(|mut store| store.reopen(id));
+        match result {
+            Ok(true) => {
+                self.reload_comment_state();
+                self.ui.status = Some(format!("Reopened comment {}", id));
+            }
+            Ok(false) => self.ui.error = Some(format!("Comment {} not found", id)),
+            Err(e) => self.ui.error = Some(format!("Failed to reopen comment: {}", e)),
+        }
+        self.ui.dirty = true;
+    }
+
+    /// Delete the currently selected comment. The first call only arms the
+    /// deletion; a second call on the same comment carries it out.
+    pub fn comments_delete_selected(&mut self) {
+        if self.selected_comment_is_remote() {
+            return;
+        }
+        let Some(id) = self
+            .comments
+            .viewing
+            .get(self.comments.selected)
+            .map(|c| c.id)
+        else {
+            return;
+        };
+
+        if self.comments.confirm_delete.take() != Some(id) {
+            self.comments.confirm_delete = Some(id);
+            self.ui.status = Some(format!("Press d again to delete comment {}", id));
+            self.ui.dirty = true;
+            return;
+        }
+
+        let result = open_comment_store(&self.repo).and_then(|mut store| store.delete(id));
+        match result {
+            Ok(true) => {
+                self.reload_comment_state();
+                self.ui.status = Some(format!("Deleted comment {}", id));
+            }
+            Ok(false) => self.ui.error = Some(format!("Comment {} not found", id)),
+            Err(e) => self.ui.error = Some(format!("Failed to delete comment: {}", e)),
+        }
+        self.ui.dirty = true;
+    }
+
+    /// Reload counts, markers and the overlay list after the store changed.
+    pub(crate) fn reload_comment_state(&mut self) {
+        self.open_comment_counts = load_open_comment_counts(&self.repo, &self.comment_context);
+        self.refresh_current_file_comment_markers();
+        self.refresh_viewing_comments();
+    }
+
+    /// Apply the selected comment's suggestion to the working file and resolve it.
+    pub fn comments_apply_suggestion_selected(&mut self) {
+        if self.selected_comment_is_remote() {
+            return;
+        }
+        let Some(id) = self
+            .comments
+            .viewing
+            .get(self.comments.selected)
+            .map(|c| c.id)
+        else {
+            return;
+        };
+
+        let mut store = match open_comment_store(&self.repo) {
+            Ok(s) => s,
+            Err(e) => {
+                self.ui.error = Some(format!("Failed to open comment store: {}", e));
+                self.ui.dirty = true;
+                return;
+            }
+        };
+        let Some(comment) = store.get(id).cloned() else {
+            self.ui.error = Some(format!("Comment {} not found", id));
+            self.ui.dirty = true;
+            return;
+        };
+        let Some(suggestion) = comment.suggestion.as_deref() else {
+            self.ui.error = Some(format!("Comment {} has no suggestion", id));
+            self.ui.dirty = true;
+            return;
+        };
+
+        if self.pr.active {
+            self.ui.error = Some("Suggestions can only be applied to a local checkout".to_string());
+            self.ui.dirty = true;
+            return;
+        }
+
+        let path = self.repo.path().join(comment.path.as_str());
+        if let Err(e) = apply_suggestion_to_file(&path, &comment.anchor, suggestion) {
+            self.ui.error = Some(format!("Cannot apply suggestion: {}", e));
+            self.ui.dirty = true;
+            return;
+        }
+        if let Err(e) = store.resolve(id) {
+            self.ui.error = Some(format!("Applied suggestion but failed to resolve: {}", e));
+            self.ui.dirty = true;
+            return;
+        }
+
+        self.close_comments();
+        self.open_comment_counts = load_open_comment_counts(&self.repo, &self.comment_context);
+        self.request_current_diff();
+        self.ui.status = Some(format!("Applied suggestion from comment {}", id));
+        self.ui.dirty = true;
+    }
 }
@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use super::App;
+use crate::core::{
+    ControlCommand, ControlEvent, ControlReply, ControlServer, DiffSource, RelPath,
+    diff_source_display,
+};
+
+impl App {
+    /// Accept remote-control commands on a Unix socket at `path`.
+    pub fn listen(&mut self, path: &Path) -> std::io::Result<()> {
+        self.worker.control = Some(ControlServer::bind(path)?);
+        Ok(())
+    }
+
+    /// Socket the app is listening on, if any.
+    pub fn control_socket(&self) -> Option<&Path> {
+        self.worker.control.as_ref().map(ControlServer::path)
+    }
+
+    /// Apply commands from control clients and answer them.
+    pub fn poll_control(&mut self) -> bool {
+        let Some(server) = self.worker.control.as_mut() else {
+            return false;
+        };
+        let commands = server.poll();
+        if commands.is_empty() {
+            return false;
+        }
+
+        for (client, command) in commands {
+            let result = self.apply_control_command(command);
+            if let Some(server) = self.worker.control.as_mut() {
+                server.reply(client, &ControlReply::from_result(result));
+            }
+        }
+        self.ui.dirty = true;
+        true
+    }
+
+    /// Apply one remote-control command.
+    pub fn apply_control_command(&mut self, command: ControlCommand) -> Result<(), String> {
+        match command {
+            ControlCommand::Open { path, line } => self.open_path(&path, line),
+            ControlCommand::Reload => {
+                self.manual_reload();
+                Ok(())
+            }
+            ControlCommand::Worktree => self.switch_source_from_control(DiffSource::WorkingTree),
+            ControlCommand::Base { base } => {
+                self.switch_source_from_control(DiffSource::Base(base))
+            }
+            ControlCommand::Commit { commit } => {
+                self.switch_source_from_control(DiffSource::Commit(commit))
+            }
+            ControlCommand::Range { from, to } => {
+                self.switch_source_from_control(DiffSource::Range { from, to })
+            }
+            ControlCommand::Quit => {
+                self.should_quit = true;
+                Ok(())
+            }
+        }
+    }
+
+    /// Select `path` and scroll to a 1-based new-side line once its diff is loaded.
+    fn open_path(&mut self, path: &str, line: Option<usize>) -> Result<(), String> {
+        let rel_path = RelPath::try_new(path)
+            .map_err(|_| format!("path must be relative to the repository: {}", path))?;
+        let Some(idx) = self.files.iter().position(|f| f.path == rel_path) else {
+            return Err(format!("{} is not in the current diff", path));
+        };
+        if !self.sidebar.filtered_indices.is_empty() && !self.is_file_visible(idx) {
+            self.clear_filter();
+        }
+
+        let line = line.map(|n| n.saturating_sub(1));
+        let loaded = idx == self.sidebar.selected_idx && self.diff.is_some();
+        if loaded {
+            if let Some(line) = line {
+                self.scroll_to_new_line(line);
+            }
+        } else {
+            self.sidebar.selected_idx = idx;
+            self.request_current_diff();
+            self.viewer.pending_line = line;
+        }
+        self.focus = super::Focus::Diff;
+        self.ui.dirty = true;
+        Ok(())
+    }
+
+    fn switch_source_from_control(&mut self, source: DiffSource) -> Result<(), String> {
+        self.switch_source(source).map_err(|e| e.to_string())?;
+        let source = diff_source_display(&self.source, &self.repo);
+        self.emit_control(ControlEvent::SourceChanged { source });
+        Ok(())
+    }
+
+    /// Tell control clients the selected file changed, once per file.
+    pub(super) fn announce_selected_file(&mut self) {
+        if self.worker.control.is_none() {
+            return;
+        }
+        let Some(path) = self.selected_file().map(|f| f.path.clone()) else {
+            return;
+        };
+        if self.worker.announced_path.as_ref() == Some(&path) {
+            return;
+        }
+        self.emit_control(ControlEvent::FileSelected {
+            path: path.as_str().to_string(),
+        });
+        self.worker.announced_path = Some(path);
+    }
+
+    /// Send an event to every control client.
+    pub(crate) fn emit_control(&mut self, event: ControlEvent) {
+        if let Some(server) = self.worker.control.as_mut() {
+            server.emit(&event);
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::mpsc::TrySendError;
 
 use super::super::worker::{DiffLoadRequest, DiffLoadResponse};
 use super::{App, DiffPaneMode, DiffSource, DiffViewMode};
-use crate::core::{CommentStore, DiffResult, FileCommentStore, RenderRow, Selector};
+use crate::core::{DiffResult, RenderRow, open_comment_store};
 use crate::highlight::{LanguageId, query_scopes};
 use crate::ui::windowing::visible_range;
 
@@ -11,11 +11,15 @@ impl App {
     ///
     /// Work is performed on a background thread. Call `poll_worker()` to apply results.
     pub fn request_current_diff(&mut self) {
+        self.viewer.selection_anchor = None;
+        self.viewer.pending_line = None;
+        #[cfg(unix)]
+        self.announce_selected_file();
         if self.patch.active {
             self.request_current_patch_diff();
             return;
         }
-        if self.pr.active {
+        if self.pr.active && self.pr_content_source().is_none() {
             self.request_current_pr_diff();
             return;
         }
@@ -78,6 +82,7 @@ impl App {
         self.new_scopes.clear();
         self.old_highlights.clear();
         self.new_highlights.clear();
+        self.pr.patch_lines = None;
         self.viewer.scroll_y = 0;
         self.viewer.scroll_x = 0;
 
@@ -89,7 +94,9 @@ impl App {
 
         let req = DiffLoadRequest {
             id,
-            source: self.source.clone(),
+            source: self
+                .pr_content_source()
+                .unwrap_or_else(|| self.source.clone()),
             cached_merge_base: self.cached_merge_base.clone(),
             file: file.clone(),
         };
@@ -174,6 +181,9 @@ impl App {
                     {
                         self.viewer.scroll_y = view_row;
                     }
+                    if let Some(line) = self.viewer.pending_line.take() {
+                        self.scroll_to_new_line(line);
+                    }
 
                     if !is_binary {
                         let lang = self.current_lang;
@@ -218,10 +228,37 @@ impl App {
         self.flush_queued_diff_request();
     }
 
+    /// Scroll so a new-side line (0-indexed) is at the top, showing the full file
+    /// when the line is outside the hunks.
+    pub(crate) fn scroll_to_new_line(&mut self, line: usize) {
+        let Some(diff) = self.diff.as_ref() else {
+            return;
+        };
+        // First row at or after the line; deleted rows carry no new-side line.
+        let Some(row) = diff
+            .rows()
+            .iter()
+            .position(|row| row.new.as_ref().is_some_and(|l| l.line_num >= line))
+        else {
+            return;
+        };
+        if self.viewer.view_mode == DiffViewMode::HunksOnly
+            && self.viewer.hunk_view_rows.binary_search(&row).is_err()
+        {
+            self.viewer.view_mode = DiffViewMode::FullFile;
+            self.rebuild_view_rows();
+        }
+        self.viewer.scroll_y = self.diff_row_to_view_row(row).unwrap_or(0);
+        self.ui.dirty = true;
+    }
+
     pub(crate) fn refresh_current_file_comment_markers(&mut self) {
         self.commented_hunks.clear();
         self.comment_index.by_hunk.clear();
         self.comment_index.by_digest.clear();
+        self.comment_index.line_ranges.clear();
+        self.comment_index.kinds.clear();
+        self.comment_index.review_threads.clear();
 
         let Some(diff) = &self.diff else {
             return;
@@ -230,7 +267,14 @@ impl App {
             return;
         };
 
-        let Ok(store) = FileCommentStore::open(&self.repo) else {
+        self.comment_index.review_threads = self
+            .review_threads_for(&path)
+            .into_iter()
+            .filter(|(_, thread, _)| !thread.is_resolved)
+            .filter_map(|(_, _, span)| span)
+            .collect();
+
+        let Ok(store) = open_comment_store(&self.repo) else {
             return;
         };
 
@@ -250,19 +294,23 @@ impl App {
                 continue;
             }
 
-            for sel in &c.anchor.selectors {
-                match sel {
-                    Selector::DiffHunkV1(h) => {
-                        if let Some(&idx) = self.comment_index.by_digest.get(&h.digest_hex) {
-                            self.commented_hunks.insert(idx);
-                            self.comment_index
-                                .by_hunk
-                                .entry(idx)
-                                .or_default()
-                                .push(c.id);
-                        }
-                    }
+            let Some(found) = self.relocate_comment(&c.anchor) else {
+                continue;
+            };
+            if let Some(kind) = c.kind {
+                self.comment_index.kinds.insert(c.id, kind);
+            }
+            // Whole-hunk anchors mark the hunk; everything else marks its lines.
+            match found.hunk {
+                Some(idx) => {
+                    self.commented_hunks.insert(idx);
+                    self.comment_index
+                        .by_hunk
+                        .entry(idx)
+                        .or_default()
+                        .push(c.id);
                 }
+                None => self.comment_index.line_ranges.push((c.id, found)),
             }
         }
     }
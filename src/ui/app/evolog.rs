@@ -0,0 +1,154 @@
+use super::{App, Mode};
+use crate::core::{DiffSource, RepoError, ViewedStore, list_changed_files_between, list_evolog};
+
+impl App {
+    /// Load the evolution log of `change` into the picker state.
+    pub(super) fn load_evolog(&mut self, change: &str) -> Result<(), RepoError> {
+        let entries = list_evolog(&self.repo, change)?;
+        self.evolog.change = change.to_string();
+        self.evolog.entries = entries;
+        self.evolog.selected = 0;
+        self.evolog.from_idx = 1.min(self.evolog.entries.len().saturating_sub(1));
+        self.evolog.to_idx = 0;
+        Ok(())
+    }
+
+    /// Position of a version in the loaded evolog.
+    pub(super) fn evolog_index(&self, commit_id: &str) -> Option<usize> {
+        self.evolog
+            .entries
+            .iter()
+            .position(|e| e.commit_id == commit_id)
+    }
+
+    /// Open the evolog picker for the shown change (`@` outside commit views).
+    pub fn open_evolog_picker(&mut self) {
+        if self.pr.active || self.patch.active || !self.repo.is_jj() {
+            self.ui.error = Some("Evolog is only available in jj repositories".to_string());
+            self.ui.dirty = true;
+            return;
+        }
+
+        if !matches!(self.source, DiffSource::Evolog { .. }) {
+            let change = match &self.source {
+                DiffSource::Commit(commit) => commit.clone(),
+                _ => "@".to_string(),
+            };
+            if let Err(e) = self.load_evolog(&change) {
+                self.ui.error = Some(format!("Failed to load evolog: {}", e));
+                self.ui.dirty = true;
+                return;
+            }
+        }
+
+        if self.evolog.entries.len() < 2 {
+            self.ui.status = Some("Change has no earlier versions".to_string());
+            self.ui.dirty = true;
+            return;
+        }
+
+        self.evolog.selected = self.evolog.from_idx;
+        self.ui.mode = Mode::EvologPicker;
+        self.ui.dirty = true;
+    }
+
+    /// Close the evolog picker without changing the comparison.
+    pub fn close_evolog_picker(&mut self) {
+        self.ui.mode = Mode::Normal;
+        self.ui.dirty = true;
+    }
+
+    /// Move the evolog cursor up (newer).
+    pub fn evolog_select_prev(&mut self) {
+        if self.evolog.selected > 0 {
+            self.evolog.selected -= 1;
+            self.ui.dirty = true;
+        }
+    }
+
+    /// Move the evolog cursor down (older).
+    pub fn evolog_select_next(&mut self) {
+        if self.evolog.selected + 1 < self.evolog.entries.len() {
+            self.evolog.selected += 1;
+            self.ui.dirty = true;
+        }
+    }
+
+    /// Use the highlighted version as the old side.
+    pub fn evolog_mark_old(&mut self) {
+        self.evolog.from_idx = self.evolog.selected;
+        self.ui.dirty = true;
+    }
+
+    /// Use the highlighted version as the new side.
+    pub fn evolog_mark_new(&mut self) {
+        self.evolog.to_idx = self.evolog.selected;
+        self.ui.dirty = true;
+    }
+
+    /// Diff the two marked versions.
+    pub fn evolog_apply(&mut self) {
+        if self.evolog.from_idx == self.evolog.to_idx {
+            self.ui.error = Some("Mark two different versions with [ and ]".to_string());
+            self.ui.dirty = true;
+            return;
+        }
+        self.ui.mode = Mode::Normal;
+        self.show_evolog_pair();
+    }
+
+    fn show_evolog_pair(&mut self) {
+        let (Some(from), Some(to)) = (
+            self.evolog.entries.get(self.evolog.from_idx),
+            self.evolog.entries.get(self.evolog.to_idx),
+        ) else {
+            return;
+        };
+        let source = DiffSource::Evolog {
+            change: self.evolog.change.clone(),
+            from: from.commit_id.clone(),
+            to: to.commit_id.clone(),
+        };
+
+        let mut files = match list_changed_files_between(
+            &self.repo,
+            &from.commit_id,
+            &to.commit_id,
+            &self.rename_detection,
+        ) {
+            Ok(files) => files,
+            Err(e) => {
+                self.ui.error = Some(format!("Failed to diff versions: {}", e));
+                self.ui.dirty = true;
+                return;
+            }
+        };
+        files.retain(|f| self.file_filter.matches(f.path.as_str()));
+
+        // Old versions never change on disk, so there is nothing to watch.
+        self.worker.watcher = None;
+        self.source = source;
+        self.files = files;
+        self.rebuild_path_cache();
+        self.sidebar.filtered_indices.clear();
+        self.sidebar.filter.clear();
+        self.sidebar.selected_idx = 0;
+        self.sidebar.scroll = 0;
+        self.viewed_in_changeset = self
+            .files
+            .iter()
+            .filter(|f| self.viewed.is_viewed(&f.path))
+            .count();
+        self.reset_comment_context();
+        self.request_current_diff();
+
+        let count = self.evolog.entries.len();
+        self.ui.status = Some(format!(
+            "Comparing version {} → {} of {}",
+            count - self.evolog.from_idx,
+            count - self.evolog.to_idx,
+            count
+        ));
+        self.ui.dirty = true;
+    }
+}
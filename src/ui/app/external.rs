@@ -1,4 +1,6 @@
 use super::App;
+use crate::core::{DiffSide, EditorTarget, editor_invocation};
+use crate::report::{ReportFormat, ReportInput, build_review_report};
 use arboard::Clipboard;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
@@ -7,26 +9,68 @@ use crossterm::{
 };
 use shell_words::split;
 use std::{
-    env,
+    env, fs,
     io::{self, Write},
+    path::PathBuf,
     process::Command,
 };
 
 impl App {
-    /// Open the currently selected file in the external editor.
+    /// Open the selected file in the external editor at the line under the cursor.
+    ///
+    /// On a deleted line the first press asks for confirmation; the second
+    /// opens the old version as a read-only temp file.
     pub fn open_selected_in_editor(&mut self) {
         let Some(file) = self.selected_file() else {
             self.ui.error = Some("No file selected to open".to_string());
             self.ui.dirty = true;
             return;
         };
+        let display_path = file.path.as_str().to_string();
 
-        let Some(path) = self.selected_absolute_path() else {
-            self.ui.error = Some("No file selected to open".to_string());
-            self.ui.dirty = true;
-            return;
+        let cursor = self.cursor_line();
+        let row = self.view_row_to_diff_row(self.viewer.scroll_y);
+        let (path, line) = match cursor {
+            Some((DiffSide::Old, line)) => {
+                if row.is_none() || self.viewer.confirm_open_old.take() != row {
+                    self.viewer.confirm_open_old = row;
+                    self.ui.status = Some(format!(
+                        "Line {} was deleted - press o again to open the old version read-only",
+                        line + 1
+                    ));
+                    self.ui.error = None;
+                    self.ui.dirty = true;
+                    return;
+                }
+                match self.write_old_version() {
+                    Ok(path) => (path, line),
+                    Err(e) => {
+                        self.ui.error = Some(format!("Failed to write old version: {}", e));
+                        self.ui.dirty = true;
+                        return;
+                    }
+                }
+            }
+            Some((DiffSide::New, line)) => match self.selected_absolute_path() {
+                Some(path) => (path, line),
+                None => {
+                    self.ui.error = Some("No file selected to open".to_string());
+                    self.ui.dirty = true;
+                    return;
+                }
+            },
+            None => match self.selected_absolute_path() {
+                Some(path) => (path, 0),
+                None => {
+                    self.ui.error = Some("No file selected to open".to_string());
+                    self.ui.dirty = true;
+                    return;
+                }
+            },
         };
-        let command_parts = match Self::editor_command() {
+        let read_only = matches!(cursor, Some((DiffSide::Old, _)));
+
+        let command_parts = match self.editor_command() {
             Ok(parts) => parts,
             Err(msg) => {
                 self.ui.error = Some(msg);
@@ -34,15 +78,23 @@ impl App {
                 return;
             }
         };
+        let invocation = editor_invocation(
+            &command_parts,
+            &EditorTarget {
+                path: &path,
+                line: line + 1,
+                // The viewer tracks lines, not a cursor column.
+                column: 1,
+            },
+        );
 
-        let Some((program, args)) = command_parts.split_first() else {
+        let Some((program, args)) = invocation.split_first() else {
             self.ui.error = Some("Editor command is empty".to_string());
             self.ui.dirty = true;
             return;
         };
         let mut cmd = Command::new(program);
         cmd.args(args);
-        cmd.arg(&path);
 
         if let Err(e) = Self::suspend_terminal_for_external() {
             self.ui.error = Some(format!("Failed to release terminal: {}", e));
@@ -61,7 +113,11 @@ impl App {
         match status {
             Ok(status) => {
                 if status.success() {
-                    self.ui.status = Some(format!("Editor closed for {}", file.path.as_str()));
+                    self.ui.status = Some(if read_only {
+                        format!("Editor closed for old version of {}", display_path)
+                    } else {
+                        format!("Editor closed for {}", display_path)
+                    });
                     self.ui.error = None;
                 } else {
                     self.ui.error = Some(format!("Editor exited with code {:?}", status.code()));
@@ -75,23 +131,79 @@ impl App {
         self.ui.dirty = true;
     }
 
-    fn editor_command() -> Result<Vec<String>, String> {
-        for key in ["QUICKDIFF_EDITOR", "VISUAL", "EDITOR"] {
-            if let Ok(value) = env::var(key) {
-                let trimmed = value.trim();
-                if trimmed.is_empty() {
-                    continue;
-                }
-                match split(trimmed) {
-                    Ok(parts) if !parts.is_empty() => return Ok(parts),
-                    Ok(_) => continue,
-                    Err(e) => {
-                        return Err(format!("Failed to parse ${}: {}", key, e));
-                    }
+    /// Side and 0-indexed line under the cursor, preferring the new side.
+    ///
+    /// Deleted lines report the old side; `None` without a loaded diff row.
+    pub fn cursor_line(&self) -> Option<(DiffSide, usize)> {
+        let row = self.view_row_to_diff_row(self.viewer.scroll_y)?;
+        let row = self.diff.as_ref()?.rows().get(row)?;
+        match (&row.new, &row.old) {
+            (Some(new), _) => Some((DiffSide::New, new.line_num)),
+            (None, Some(old)) => Some((DiffSide::Old, old.line_num)),
+            (None, None) => None,
+        }
+    }
+
+    /// Write the old side of the selected file to a read-only temp file.
+    ///
+    /// The file keeps its name (for editor syntax detection) under a fresh
+    /// private directory with a random name, so nothing another user placed
+    /// in the temp dir is followed. It is left behind for GUI editors that
+    /// return before the file is read.
+    fn write_old_version(&self) -> io::Result<PathBuf> {
+        let (Some(file), Some(old)) = (self.selected_file(), self.old_buffer.as_ref()) else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "old version is not loaded",
+            ));
+        };
+        let dir = tempfile::Builder::new()
+            .prefix("quickdiff-old-")
+            .tempdir()?
+            .keep();
+        let path = dir.join(file.path.as_str());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?
+            .write_all(old.as_bytes())?;
+        let mut perms = fs::metadata(&path)?.permissions();
+        perms.set_readonly(true);
+        fs::set_permissions(&path, perms)?;
+        Ok(path)
+    }
+
+    fn editor_command(&self) -> Result<Vec<String>, String> {
+        let configured = self
+            .editor
+            .as_ref()
+            .map(|value| ("`editor`".to_string(), value.clone()));
+        let from_env = |key: &str| env::var(key).ok().map(|value| (format!("${}", key), value));
+        let candidates = from_env("QUICKDIFF_EDITOR")
+            .into_iter()
+            .chain(configured)
+            .chain(from_env("VISUAL"))
+            .chain(from_env("EDITOR"));
+        for (key, value) in candidates {
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            match split(trimmed) {
+                Ok(parts) if !parts.is_empty() => return Ok(parts),
+                Ok(_) => continue,
+                Err(e) => {
+                    return Err(format!("Failed to parse {}: {}", key, e));
                 }
             }
         }
-        Err("Set $QUICKDIFF_EDITOR, $VISUAL, or $EDITOR to open files externally".to_string())
+        Err(
+            "Set $QUICKDIFF_EDITOR, `editor` in the config, $VISUAL, or $EDITOR to open files externally"
+                .to_string(),
+        )
     }
 
     fn suspend_terminal_for_external() -> io::Result<()> {
@@ -133,4 +245,43 @@ impl App {
 
         self.ui.dirty = true;
     }
+
+    /// Export the open comments as a markdown report.
+    ///
+    /// The report is written to `review.md` in the state directory and copied
+    /// to the clipboard when one is available.
+    pub fn export_review_report(&mut self) {
+        self.ui.dirty = true;
+        let input = ReportInput {
+            source: &self.source,
+            files: &self.files,
+            merge_base: self.cached_merge_base.as_deref(),
+            viewed: &self.viewed,
+        };
+        let report = match build_review_report(&self.repo, input) {
+            Ok(report) => report,
+            Err(e) => {
+                self.ui.error = Some(format!("Failed to build report: {:#}", e));
+                return;
+            }
+        };
+        let text = report.render(ReportFormat::Markdown);
+
+        let dir = self.repo.state_dir();
+        let path = dir.join("review.md");
+        if let Err(e) = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, &text)) {
+            self.ui.error = Some(format!("Failed to write {}: {}", path.display(), e));
+            return;
+        }
+        let copied = Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text));
+        let count = report.comment_count();
+        self.ui.status = Some(match copied {
+            Ok(()) => format!(
+                "Exported {} comment(s) to {} and the clipboard",
+                count,
+                path.display()
+            ),
+            Err(_) => format!("Exported {} comment(s) to {}", count, path.display()),
+        });
+    }
 }
@@ -3,11 +3,11 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::core::{
-    ChangedFile, CommentContext, CommentStore, DiffResult, DiffSource, FileCommentStore,
-    FileViewedStore, FuzzyMatcher, RelPath, RepoRoot, RepoWatcher, TextBuffer, ViewPreferences,
-    ViewedStore, diff_source_display, list_changed_files, list_changed_files_between,
-    list_changed_files_from_base_with_merge_base, list_commit_files, resolve_revision,
-    save_global_preferences,
+    ChangedFile, CommentContext, CommentsWatcher, DiffResult, DiffSource, FileViewedStore,
+    FuzzyMatcher, PathFilter, RelPath, RenameDetection, RepoError, RepoRoot, RepoWatcher,
+    TextBuffer, ViewPreferences, ViewedStore, diff_source_display, list_changed_files,
+    list_changed_files_between, list_changed_files_from_base_with_merge_base, list_commit_files,
+    open_comment_store, resolve_revision, save_global_preferences, split_patch,
 };
 use crate::highlight::{FileHighlightCache, HighlighterCache, LanguageId, ScopeInfo};
 use crate::theme::Theme;
@@ -15,7 +15,10 @@ use crate::theme::Theme;
 use super::render::{ThemeStyles, build_path_cache};
 
 mod comments;
+#[cfg(unix)]
+mod control;
 mod diff;
+mod evolog;
 mod external;
 mod filter;
 mod navigation;
@@ -25,10 +28,11 @@ mod state;
 mod theme;
 mod watcher;
 mod worker_state;
+mod worktree;
 
 pub use state::{
-    CommentIndex, CommentViewItem, CommentsState, DiffPaneMode, DiffViewMode, Focus, Mode,
-    PRActionType, PatchState, PrState, SidebarState, UiState, ViewerState,
+    CommentIndex, CommentViewItem, CommentsState, DiffPaneMode, DiffViewMode, EvologState, Focus,
+    Mode, PRActionType, PatchState, PrState, SidebarState, UiState, ViewerState, WorktreeState,
 };
 use worker_state::WorkerState;
 
@@ -44,8 +48,10 @@ pub struct App {
     pub comment_context: CommentContext,
     /// List of changed files.
     pub files: Vec<ChangedFile>,
-    /// File path filter (only show this file if set).
-    pub file_filter: Option<String>,
+    /// Pathspec filter applied to every file list (CLI `-f` plus config excludes).
+    pub file_filter: PathFilter,
+    /// Rename/copy detection settings for commit, range and base file lists.
+    pub rename_detection: RenameDetection,
     /// Sidebar state.
     pub sidebar: SidebarState,
     /// Current focus.
@@ -62,6 +68,8 @@ pub struct App {
     pub comment_index: CommentIndex,
     /// Should the app quit?
     pub should_quit: bool,
+    /// Editor command from the global config (`editor`), used by `o`.
+    pub editor: Option<String>,
 
     /// Background worker state.
     worker: WorkerState,
@@ -119,29 +127,14 @@ pub struct App {
     pub pr: PrState,
     /// Patch mode state.
     pub patch: PatchState,
-}
-
-fn comment_context_for_source(source: &DiffSource) -> CommentContext {
-    match source {
-        DiffSource::WorkingTree => CommentContext::Worktree,
-        DiffSource::Base(base) => CommentContext::Base { base: base.clone() },
-        DiffSource::Commit(commit) => CommentContext::Commit {
-            commit: commit.clone(),
-        },
-        DiffSource::Range { from, to } => CommentContext::Range {
-            from: from.clone(),
-            to: to.clone(),
-        },
-        DiffSource::FilePair { .. } | DiffSource::DiffTool { .. } => CommentContext::Unscoped,
-        DiffSource::PullRequest { number, .. } => CommentContext::Commit {
-            // Use PR number as pseudo-commit context
-            commit: format!("pr-{}", number),
-        },
-    }
+    /// Worktree picker state.
+    pub worktree: WorktreeState,
+    /// jj evolog picker state.
+    pub evolog: EvologState,
 }
 
 fn load_open_comment_counts(repo: &RepoRoot, context: &CommentContext) -> HashMap<RelPath, usize> {
-    let Ok(store) = FileCommentStore::open(repo) else {
+    let Ok(store) = open_comment_store(repo) else {
         return HashMap::new();
     };
 
@@ -155,12 +148,39 @@ fn load_open_comment_counts(repo: &RepoRoot, context: &CommentContext) -> HashMa
     counts
 }
 
+/// Changed files of a repo-backed source, with the merge base for `Base`.
+fn list_source_files(
+    repo: &RepoRoot,
+    source: &DiffSource,
+    rename_detection: &RenameDetection,
+) -> Result<(Vec<ChangedFile>, Option<String>), RepoError> {
+    match source {
+        DiffSource::WorkingTree => Ok((list_changed_files(repo)?, None)),
+        DiffSource::Commit(commit) => {
+            Ok((list_commit_files(repo, commit, rename_detection)?, None))
+        }
+        DiffSource::Range { from, to } | DiffSource::Evolog { from, to, .. } => Ok((
+            list_changed_files_between(repo, from, to, rename_detection)?,
+            None,
+        )),
+        DiffSource::Base(base) => {
+            let result =
+                list_changed_files_from_base_with_merge_base(repo, base, rename_detection)?;
+            Ok((result.files, Some(result.merge_base)))
+        }
+        DiffSource::FilePair { .. }
+        | DiffSource::DiffTool { .. }
+        | DiffSource::PullRequest { .. } => Ok((Vec::new(), None)),
+    }
+}
+
 impl App {
-    /// Create a new App from a repository root with optional diff source and file filter.
+    /// Create a new App from a repository root with a diff source and pathspec filter.
     pub fn new(
         repo: RepoRoot,
         source: DiffSource,
-        file_filter: Option<String>,
+        file_filter: PathFilter,
+        rename_detection: RenameDetection,
         prefs: ViewPreferences,
     ) -> anyhow::Result<Self> {
         let theme = Theme::load(&prefs.theme);
@@ -177,17 +197,15 @@ impl App {
             other => other,
         };
 
-        let comment_context = comment_context_for_source(&source);
+        let comment_context = CommentContext::for_source(&source);
 
         // Load files based on diff source
         let (mut files, cached_merge_base) = match &source {
-            DiffSource::WorkingTree => (list_changed_files(&repo)?, None),
-            DiffSource::Commit(commit) => (list_commit_files(&repo, commit)?, None),
-            DiffSource::Range { from, to } => (list_changed_files_between(&repo, from, to)?, None),
-            DiffSource::Base(base) => {
-                let result = list_changed_files_from_base_with_merge_base(&repo, base)?;
-                (result.files, Some(result.merge_base))
-            }
+            DiffSource::WorkingTree
+            | DiffSource::Commit(_)
+            | DiffSource::Range { .. }
+            | DiffSource::Evolog { .. }
+            | DiffSource::Base(_) => list_source_files(&repo, &source, &rename_detection)?,
             DiffSource::FilePair {
                 right,
                 display_path,
@@ -222,14 +240,12 @@ impl App {
         let ignored_filter = matches!(
             source,
             DiffSource::FilePair { .. } | DiffSource::DiffTool { .. }
-        ) && file_filter.is_some();
-        if source.is_repo_backed()
-            && let Some(ref filter) = file_filter
-        {
-            files.retain(|f| f.path.as_str().contains(filter));
+        ) && file_filter.has_pathspecs();
+        if source.is_repo_backed() {
+            files.retain(|f| file_filter.matches(f.path.as_str()));
         }
 
-        let viewed = FileViewedStore::new(repo.as_str())?;
+        let viewed = FileViewedStore::new(repo.state_key())?;
         let viewed_in_changeset = files.iter().filter(|f| viewed.is_viewed(&f.path)).count();
 
         let open_comment_counts = load_open_comment_counts(&repo, &comment_context);
@@ -243,6 +259,7 @@ impl App {
             comment_context,
             files,
             file_filter,
+            rename_detection,
             sidebar: SidebarState {
                 visible: true,
                 ..Default::default()
@@ -254,6 +271,7 @@ impl App {
             commented_hunks: HashSet::new(),
             comment_index: CommentIndex::default(),
             should_quit: false,
+            editor: None,
             worker,
             diff: None,
             old_buffer: None,
@@ -285,6 +303,8 @@ impl App {
             theme_original: prefs.theme.clone(),
             pr: PrState::default(),
             patch: PatchState::default(),
+            worktree: WorktreeState::default(),
+            evolog: EvologState::default(),
         };
 
         // Build path cache for sidebar
@@ -305,6 +325,11 @@ impl App {
             }
         }
 
+        // Pick up comments written by other processes (agents, CI) while running.
+        if app.comments_enabled() {
+            app.worker.comments_watcher = CommentsWatcher::new(&app.repo).ok();
+        }
+
         // Restore last selected file if available (only for working tree mode)
         if matches!(app.source, DiffSource::WorkingTree)
             && let Some(last) = app.viewed.last_selected()
@@ -313,6 +338,13 @@ impl App {
             app.sidebar.selected_idx = idx;
         }
 
+        if let DiffSource::Evolog { change, from, to } = &app.source {
+            let (change, from, to) = (change.clone(), from.clone(), to.clone());
+            app.load_evolog(&change)?;
+            app.evolog.from_idx = app.evolog_index(&from).unwrap_or(0);
+            app.evolog.to_idx = app.evolog_index(&to).unwrap_or(0);
+        }
+
         // Load initial diff if there are files
         if !app.files.is_empty() {
             app.request_current_diff();
@@ -351,6 +383,7 @@ impl App {
             }
             DiffSource::Commit(_)
             | DiffSource::Range { .. }
+            | DiffSource::Evolog { .. }
             | DiffSource::FilePair { .. }
             | DiffSource::DiffTool { .. } => {
                 if self.files.is_empty() {
@@ -370,6 +403,61 @@ impl App {
         }
     }
 
+    /// Show another worktree, base, commit or range diff in place.
+    ///
+    /// Leaves PR mode first. Commit and range revisions are resolved so comment
+    /// contexts match those made from the command line.
+    pub fn switch_source(&mut self, source: DiffSource) -> anyhow::Result<()> {
+        let source = match source {
+            DiffSource::WorkingTree => DiffSource::WorkingTree,
+            DiffSource::Base(base) => DiffSource::Base(base),
+            DiffSource::Commit(commit) => {
+                DiffSource::Commit(resolve_revision(&self.repo, &commit)?.to_string())
+            }
+            DiffSource::Range { from, to } => DiffSource::Range {
+                from: resolve_revision(&self.repo, &from)?.to_string(),
+                to: resolve_revision(&self.repo, &to)?.to_string(),
+            },
+            other => anyhow::bail!(
+                "cannot switch to {}",
+                diff_source_display(&other, &self.repo)
+            ),
+        };
+        if self.patch.active {
+            anyhow::bail!("cannot switch sources while reading a patch");
+        }
+
+        let (mut files, merge_base) =
+            list_source_files(&self.repo, &source, &self.rename_detection)?;
+        files.retain(|f| self.file_filter.matches(f.path.as_str()));
+
+        self.exit_pr_mode();
+        self.worker.watcher = if matches!(source, DiffSource::WorkingTree | DiffSource::Base(_)) {
+            RepoWatcher::new(&self.repo).ok()
+        } else {
+            None
+        };
+        self.source = source;
+        self.cached_merge_base = merge_base;
+        self.files = files;
+        self.rebuild_path_cache();
+        self.sidebar.filtered_indices.clear();
+        self.sidebar.filter.clear();
+        self.sidebar.selected_idx = 0;
+        self.sidebar.scroll = 0;
+        self.viewed_in_changeset = self
+            .files
+            .iter()
+            .filter(|f| self.viewed.is_viewed(&f.path))
+            .count();
+        self.reset_comment_context();
+        self.request_current_diff();
+
+        self.ui.status = Some(format!("Showing {}", self.source_display()));
+        self.ui.dirty = true;
+        Ok(())
+    }
+
     /// Open the in-app help overlay.
     pub fn open_help(&mut self) {
         self.ui.mode = Mode::Help;
@@ -424,10 +512,17 @@ impl App {
         format!("{}/{}", self.viewed_in_changeset, self.files.len())
     }
 
-    /// Check if we're in working tree mode (uncommitted changes).
-    /// Comments are only available in this mode.
-    pub fn is_worktree_mode(&self) -> bool {
-        matches!(self.source, DiffSource::WorkingTree) && !self.patch.active
+    /// Check if comments can be made on the current view.
+    /// Any repo-backed source (worktree, base, commit, range, PR) supports them;
+    /// file pairs and stdin patches have no stable context to attach them to.
+    pub fn comments_enabled(&self) -> bool {
+        self.source.is_repo_backed() && !self.patch.active
+    }
+
+    /// Point comments at the current source and reload their counts.
+    pub(crate) fn reset_comment_context(&mut self) {
+        self.comment_context = CommentContext::for_source(&self.source);
+        self.open_comment_counts = load_open_comment_counts(&self.repo, &self.comment_context);
     }
 
     /// Mark dirty for redraw.
@@ -445,35 +540,8 @@ impl App {
 ///
 /// Reconstructs file content from patch hunks for display in the diff viewer.
 fn extract_content_from_patch(patch: &str) -> (String, String) {
-    let mut old_lines: Vec<&str> = Vec::new();
-    let mut new_lines: Vec<&str> = Vec::new();
-    let mut in_hunk = false;
-
-    for line in patch.lines() {
-        if line.starts_with("@@") {
-            in_hunk = true;
-            continue;
-        }
-
-        if !in_hunk {
-            continue;
-        }
-
-        if line.starts_with('-') && !line.starts_with("---") {
-            // Deleted line - only in old
-            old_lines.push(&line[1..]);
-        } else if line.starts_with('+') && !line.starts_with("+++") {
-            // Added line - only in new
-            new_lines.push(&line[1..]);
-        } else if line.starts_with(' ') || line.is_empty() {
-            // Context line - in both
-            let content = line.strip_prefix(' ').unwrap_or(line);
-            old_lines.push(content);
-            new_lines.push(content);
-        }
-    }
-
-    (old_lines.join("\n"), new_lines.join("\n"))
+    let sides = split_patch(patch);
+    (sides.old, sides.new)
 }
 
 #[cfg(test)]
@@ -609,5 +677,6 @@ rename to new.rs
         assert!(worker.pending_pr.is_none());
         assert!(worker.pr_worker.request_tx.is_some());
         assert!(worker.watcher.is_none());
+        assert!(worker.comments_watcher.is_none());
     }
 }
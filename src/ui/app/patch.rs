@@ -7,7 +7,8 @@ use crate::highlight::{LanguageId, query_scopes};
 impl App {
     /// Load a unified diff patch and enter patch mode.
     pub fn load_patch(&mut self, patch: String, label: String) {
-        let patch_files = parse_unified_diff(&patch);
+        let mut patch_files = parse_unified_diff(&patch);
+        patch_files.retain(|pf| self.file_filter.matches(pf.path.as_str()));
         self.patch.active = true;
         self.patch.label = label;
         self.patch.files = patch_files.clone();
@@ -17,6 +18,9 @@ impl App {
                 path: pf.path.clone(),
                 kind: pf.kind,
                 old_path: pf.old_path.clone(),
+                old_mode: pf.old_mode,
+                new_mode: pf.new_mode,
+                similarity: pf.similarity,
             })
             .collect();
         self.rebuild_path_cache();
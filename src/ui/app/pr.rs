@@ -1,12 +1,23 @@
+use std::time::{Duration, Instant};
+
 use super::super::worker::{PrRequest, PrResponse};
 use super::{App, Mode, PRActionType};
 use crate::core::DiffResult;
 use crate::core::{
-    ChangedFile, DiffSource, PullRequest, TextBuffer, approve_pr, comment_pr, list_changed_files,
-    open_pr_in_browser, parse_unified_diff, request_changes_pr,
+    ChangedFile, Comment, DiffSide, DiffSource, PR_PAGE_SIZE, PullRequest, PullRequestReview,
+    RelPath, ReviewEvent, ReviewSide, ReviewThread, TextBuffer, UnmappedComment, approve_pr,
+    comment_pr, list_changed_files, load_diff_contents, map_review_comments, open_comment_store,
+    open_pr_in_browser, parse_unified_diff, place_review_thread, request_changes_pr,
+    split_patch_with_gaps, submit_pr_review,
 };
 use crate::highlight::{LanguageId, query_scopes};
 
+/// How often the checks of the open PR are refreshed in the background.
+const CHECKS_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A review thread with its index in the PR's threads and the lines it covers.
+pub(crate) type PlacedThread<'a> = (usize, &'a ReviewThread, Option<(DiffSide, usize, usize)>);
+
 impl App {
     pub(crate) fn send_pr_request(&mut self, req: PrRequest) -> bool {
         let Some(tx) = self.worker.pr_worker.request_tx.as_ref() else {
@@ -44,7 +55,12 @@ impl App {
                     }
 
                     self.worker.pending_pr_list_id = None;
+                    self.pr.has_more = prs.len() >= self.pr.query.limit;
                     self.pr.list = prs;
+                    self.pr.picker_selected = self
+                        .pr
+                        .picker_selected
+                        .min(self.pr.list.len().saturating_sub(1));
                     self.pr.loading = false;
                     self.ui.error = None;
 
@@ -61,11 +77,17 @@ impl App {
 
                     self.worker.pending_pr_list_id = None;
                     self.pr.list.clear();
+                    self.pr.has_more = false;
                     self.pr.loading = false;
                     self.ui.error = Some(format!("Failed to fetch PRs: {}", message));
                     self.ui.dirty = true;
                 }
-                PrResponse::Diff { id, diff } => {
+                PrResponse::Diff {
+                    id,
+                    diff,
+                    threads,
+                    revisions,
+                } => {
                     if self.worker.pending_pr_load_id != Some(id) {
                         continue;
                     }
@@ -89,7 +111,8 @@ impl App {
                         }
                     };
 
-                    let pr_files = parse_unified_diff(&diff);
+                    let mut pr_files = parse_unified_diff(&diff);
+                    pr_files.retain(|pf| self.file_filter.matches(pf.path.as_str()));
 
                     self.files = pr_files
                         .iter()
@@ -97,11 +120,17 @@ impl App {
                             path: pf.path.clone(),
                             kind: pf.kind,
                             old_path: pf.old_path.clone(),
+                            old_mode: pf.old_mode,
+                            new_mode: pf.new_mode,
+                            similarity: pf.similarity,
                         })
                         .collect();
 
                     self.rebuild_path_cache();
                     self.pr.files = pr_files;
+                    let threads_error = threads.as_ref().err().cloned();
+                    self.pr.threads = threads.unwrap_or_default();
+                    self.pr.revisions = revisions;
                     self.pr.active = true;
                     self.pr.current = Some(pr.clone());
 
@@ -110,12 +139,13 @@ impl App {
                         head: pr.head_ref_name.clone(),
                         base: pr.base_ref_name.clone(),
                     };
+                    self.reset_comment_context();
 
                     self.sidebar.selected_idx = 0;
                     self.sidebar.scroll = 0;
 
                     if !self.files.is_empty() {
-                        self.request_current_pr_diff();
+                        self.request_current_diff();
                     } else {
                         self.diff = None;
                         self.viewer.hunk_view_rows.clear();
@@ -123,7 +153,42 @@ impl App {
                         self.new_buffer = None;
                         self.ui.status = Some("PR has no changed files".to_string());
                     }
+                    if let Some(e) = threads_error {
+                        self.ui.status = Some(format!("Could not load review threads: {}", e));
+                    }
+                    self.refresh_pr_checks();
+                    self.request_pr_details();
+
+                    self.ui.dirty = true;
+                }
+                PrResponse::Checks { id, checks } => {
+                    if self.worker.pending_pr_checks_id != Some(id) {
+                        continue;
+                    }
 
+                    self.worker.pending_pr_checks_id = None;
+                    match checks {
+                        Ok(checks) => {
+                            self.pr.checks = Some(checks);
+                            self.pr.checks_error = None;
+                        }
+                        Err(e) => self.pr.checks_error = Some(e),
+                    }
+                    self.ui.dirty = true;
+                }
+                PrResponse::Details { id, details } => {
+                    if self.worker.pending_pr_details_id != Some(id) {
+                        continue;
+                    }
+
+                    self.worker.pending_pr_details_id = None;
+                    match details {
+                        Ok(details) => {
+                            self.pr.details = Some(details);
+                            self.pr.details_error = None;
+                        }
+                        Err(e) => self.pr.details_error = Some(e),
+                    }
                     self.ui.dirty = true;
                 }
                 PrResponse::DiffError { id, message } => {
@@ -140,6 +205,86 @@ impl App {
                 }
             }
         }
+
+        if self.pr.active
+            && self
+                .pr
+                .checks_requested_at
+                .is_some_and(|at| at.elapsed() >= CHECKS_REFRESH_INTERVAL)
+        {
+            self.refresh_pr_checks();
+        }
+    }
+
+    /// Ask the PR worker for the current PR's CI checks.
+    pub fn refresh_pr_checks(&mut self) {
+        let Some(pr_number) = self.active_pr_number() else {
+            return;
+        };
+        if self.worker.pending_pr_checks_id.is_some() {
+            return;
+        }
+
+        let id = self.worker.next_pr_request_id;
+        self.worker.next_pr_request_id = self.worker.next_pr_request_id.wrapping_add(1);
+        self.pr.checks_requested_at = Some(Instant::now());
+        if self.send_pr_request(PrRequest::Checks { id, pr_number }) {
+            self.worker.pending_pr_checks_id = Some(id);
+        }
+    }
+
+    /// Ask the PR worker for the current PR's description and conversation.
+    fn request_pr_details(&mut self) {
+        let Some(pr_number) = self.active_pr_number() else {
+            return;
+        };
+        if self.worker.pending_pr_details_id.is_some() {
+            return;
+        }
+
+        let id = self.worker.next_pr_request_id;
+        self.worker.next_pr_request_id = self.worker.next_pr_request_id.wrapping_add(1);
+        if self.send_pr_request(PrRequest::Details { id, pr_number }) {
+            self.worker.pending_pr_details_id = Some(id);
+        }
+    }
+
+    fn active_pr_number(&self) -> Option<u32> {
+        self.pr
+            .current
+            .as_ref()
+            .filter(|_| self.pr.active)
+            .map(|pr| pr.number)
+    }
+
+    /// Open the overlay with the PR's checks, description and conversation,
+    /// refreshing them in the background.
+    pub fn open_pr_info(&mut self) {
+        if self.active_pr_number().is_none() {
+            return;
+        }
+        self.ui.mode = Mode::PRInfo;
+        self.pr.info_scroll = 0;
+        self.refresh_pr_info();
+    }
+
+    /// Fetch the PR's checks, description and conversation again.
+    pub fn refresh_pr_info(&mut self) {
+        self.request_pr_details();
+        self.refresh_pr_checks();
+        self.ui.dirty = true;
+    }
+
+    /// Close the PR info overlay.
+    pub fn close_pr_info(&mut self) {
+        self.ui.mode = Mode::Normal;
+        self.ui.dirty = true;
+    }
+
+    /// Scroll the PR info overlay by `delta` lines.
+    pub fn scroll_pr_info(&mut self, delta: isize) {
+        self.pr.info_scroll = self.pr.info_scroll.saturating_add_signed(delta);
+        self.ui.dirty = true;
     }
 
     /// Open PR picker mode and begin loading PRs from GitHub.
@@ -151,10 +296,30 @@ impl App {
         }
 
         self.ui.mode = Mode::PRPicker;
+        self.pr.search_input = None;
+        self.restart_pr_list();
+    }
+
+    /// Fetch the first page of PRs for a changed query, from the top.
+    fn restart_pr_list(&mut self) {
+        self.pr.query.limit = PR_PAGE_SIZE;
+        self.pr.list.clear();
+        self.pr.has_more = false;
         self.pr.picker_selected = 0;
         self.pr.picker_scroll = 0;
-        self.pr.loading = true;
-        self.ui.dirty = true;
+        self.fetch_pr_list();
+    }
+
+    /// Fetch another page of PRs once the selection reaches the end of the
+    /// list and the last fetch filled its limit.
+    fn load_more_prs(&mut self) {
+        if !self.pr.has_more
+            || self.worker.pending_pr_list_id.is_some()
+            || self.pr.picker_selected + 1 < self.pr.list.len()
+        {
+            return;
+        }
+        self.pr.query.limit += PR_PAGE_SIZE;
         self.fetch_pr_list();
     }
 
@@ -169,7 +334,7 @@ impl App {
 
         if !self.send_pr_request(PrRequest::List {
             id,
-            filter: self.pr.filter,
+            query: self.pr.query.clone(),
         }) {
             self.worker.pending_pr_list_id = None;
             self.pr.loading = false;
@@ -182,6 +347,8 @@ impl App {
     pub fn close_pr_picker(&mut self) {
         self.ui.mode = Mode::Normal;
         self.pr.list.clear();
+        self.pr.has_more = false;
+        self.pr.search_input = None;
         self.pr.picker_selected = 0;
         self.pr.picker_scroll = 0;
         self.pr.loading = false;
@@ -194,6 +361,7 @@ impl App {
         if !self.pr.list.is_empty() {
             self.pr.picker_selected = (self.pr.picker_selected + 1).min(self.pr.list.len() - 1);
             self.ui.dirty = true;
+            self.load_more_prs();
         }
     }
 
@@ -205,24 +373,54 @@ impl App {
 
     /// Cycle the picker to the next filter (all → mine → review requested).
     pub fn pr_picker_next_filter(&mut self) {
-        self.pr.filter = match self.pr.filter {
+        self.pr.query.filter = match self.pr.query.filter {
             crate::core::PRFilter::All => crate::core::PRFilter::Mine,
             crate::core::PRFilter::Mine => crate::core::PRFilter::ReviewRequested,
             crate::core::PRFilter::ReviewRequested => crate::core::PRFilter::All,
         };
-        self.pr.picker_selected = 0;
-        self.fetch_pr_list();
+        self.restart_pr_list();
     }
 
     /// Cycle the picker to the previous filter (reverse order).
     pub fn pr_picker_prev_filter(&mut self) {
-        self.pr.filter = match self.pr.filter {
+        self.pr.query.filter = match self.pr.query.filter {
             crate::core::PRFilter::All => crate::core::PRFilter::ReviewRequested,
             crate::core::PRFilter::Mine => crate::core::PRFilter::All,
             crate::core::PRFilter::ReviewRequested => crate::core::PRFilter::Mine,
         };
-        self.pr.picker_selected = 0;
-        self.fetch_pr_list();
+        self.restart_pr_list();
+    }
+
+    /// Cycle the picker's state filter (open → draft → merged → closed).
+    pub fn pr_picker_next_state(&mut self) {
+        self.pr.query.state = self.pr.query.state.next();
+        self.restart_pr_list();
+    }
+
+    /// Cycle the picker's sort order.
+    pub fn pr_picker_next_sort(&mut self) {
+        self.pr.query.sort = self.pr.query.sort.next();
+        self.restart_pr_list();
+    }
+
+    /// Start editing the picker's search line, seeded with the current search.
+    pub fn pr_picker_start_search(&mut self) {
+        self.pr.search_input = Some(self.pr.query.search_input());
+        self.ui.dirty = true;
+    }
+
+    /// Apply the edited search line and refetch.
+    pub fn pr_picker_apply_search(&mut self) {
+        if let Some(input) = self.pr.search_input.take() {
+            self.pr.query.set_search_input(&input);
+            self.restart_pr_list();
+        }
+    }
+
+    /// Stop editing the search line without changing the query.
+    pub fn pr_picker_cancel_search(&mut self) {
+        self.pr.search_input = None;
+        self.ui.dirty = true;
     }
 
     /// Load the currently highlighted PR from the picker.
@@ -251,6 +449,16 @@ impl App {
         self.pr.active = true;
         self.pr.current = Some(pr.clone());
         self.pr.files.clear();
+        self.pr.threads.clear();
+        self.pr.revisions = None;
+        self.pr.patch_lines = None;
+        self.pr.checks = None;
+        self.pr.checks_error = None;
+        self.pr.checks_requested_at = None;
+        self.pr.details = None;
+        self.pr.details_error = None;
+        self.worker.pending_pr_checks_id = None;
+        self.worker.pending_pr_details_id = None;
         self.files.clear();
         self.diff = None;
         self.viewer.hunk_view_rows.clear();
@@ -269,6 +477,7 @@ impl App {
             head: pr.head_ref_name.clone(),
             base: pr.base_ref_name.clone(),
         };
+        self.reset_comment_context();
 
         if !self.send_pr_request(PrRequest::LoadDiff {
             id,
@@ -290,14 +499,26 @@ impl App {
         self.pr.active = false;
         self.pr.current = None;
         self.pr.files.clear();
+        self.pr.threads.clear();
+        self.pr.revisions = None;
+        self.pr.patch_lines = None;
+        self.pr.checks = None;
+        self.pr.checks_error = None;
+        self.pr.checks_requested_at = None;
+        self.pr.details = None;
+        self.pr.details_error = None;
+        self.worker.pending_pr_checks_id = None;
+        self.worker.pending_pr_details_id = None;
         self.worker.pending_pr_load_id = None;
         self.worker.pending_pr = None;
         self.pr.loading = false;
         self.worker.loading = false;
         self.source = DiffSource::WorkingTree;
+        self.reset_comment_context();
 
         match list_changed_files(&self.repo) {
-            Ok(files) => {
+            Ok(mut files) => {
+                files.retain(|f| self.file_filter.matches(f.path.as_str()));
                 self.files = files;
                 self.rebuild_path_cache();
                 self.sidebar.selected_idx = 0;
@@ -386,6 +607,96 @@ impl App {
         self.ui.dirty = true;
     }
 
+    /// Begin submitting the local comments as a review with inline comments.
+    pub fn start_pr_submit_review(&mut self) {
+        if !self.pr.active || self.pr.current.is_none() {
+            self.ui.error = Some("Not in PR mode".to_string());
+            self.ui.dirty = true;
+            return;
+        }
+
+        self.pr.action_type = Some(PRActionType::SubmitReview);
+        self.pr.action_text.clear();
+        self.pr.review_event = ReviewEvent::Comment;
+        self.pr.review_comment_count = self.pr_review_comments().len();
+        self.ui.mode = Mode::PRAction;
+        self.ui.dirty = true;
+    }
+
+    /// Cycle the verdict of the review being submitted.
+    pub fn cycle_pr_review_event(&mut self) {
+        self.pr.review_event = match self.pr.review_event {
+            ReviewEvent::Comment => ReviewEvent::Approve,
+            ReviewEvent::Approve => ReviewEvent::RequestChanges,
+            ReviewEvent::RequestChanges => ReviewEvent::Comment,
+        };
+        self.ui.dirty = true;
+    }
+
+    /// Open local comments on the active PR.
+    fn pr_review_comments(&self) -> Vec<Comment> {
+        let Ok(store) = open_comment_store(&self.repo) else {
+            return Vec::new();
+        };
+        store
+            .list(false)
+            .into_iter()
+            .filter(|c| c.context.matches(&self.comment_context))
+            .cloned()
+            .collect()
+    }
+
+    /// Map local comments onto the PR diff and send them as one review.
+    fn send_pr_review(&mut self, pr_number: u32) -> Result<String, String> {
+        let body = self.pr.action_text.trim().to_string();
+        let event = self.pr.review_event;
+        if event == ReviewEvent::RequestChanges && body.is_empty() {
+            return Err("Message cannot be empty".to_string());
+        }
+
+        let comments = self.pr_review_comments();
+        let refs: Vec<&Comment> = comments.iter().collect();
+        let source = self.pr_content_source();
+        let mapping = map_review_comments(&refs, &self.pr.files, |pr_file| {
+            let file = self.files.iter().find(|f| f.path == pr_file.path)?;
+            let (old, new) = load_diff_contents(&self.repo, source.as_ref()?, file, None).ok()?;
+            Some((
+                String::from_utf8_lossy(&old).into_owned(),
+                String::from_utf8_lossy(&new).into_owned(),
+            ))
+        });
+        if mapping.comments.is_empty() && body.is_empty() && event == ReviewEvent::Comment {
+            return Err(if mapping.unmapped.is_empty() {
+                "No comments to submit".to_string()
+            } else {
+                format!(
+                    "No comments could be placed in the PR diff: {}",
+                    describe_unmapped(&mapping.unmapped)
+                )
+            });
+        }
+
+        let review = PullRequestReview {
+            event,
+            body,
+            comments: mapping.comments,
+        };
+        submit_pr_review(self.repo.path(), pr_number, &review).map_err(|e| e.to_string())?;
+
+        let mut status = format!(
+            "Submitted review on PR #{} with {} inline comment(s)",
+            pr_number,
+            review.comments.len()
+        );
+        if !mapping.unmapped.is_empty() {
+            status.push_str(&format!(
+                "; not placed: {}",
+                describe_unmapped(&mapping.unmapped)
+            ));
+        }
+        Ok(status)
+    }
+
     /// Cancel any in-progress PR review action.
     pub fn cancel_pr_action(&mut self) {
         self.ui.mode = Mode::Normal;
@@ -430,6 +741,20 @@ impl App {
                 }
                 request_changes_pr(&repo_path, pr_number, &self.pr.action_text)
             }
+            Some(PRActionType::SubmitReview) => {
+                match self.send_pr_review(pr_number) {
+                    Ok(status) => {
+                        self.ui.status = Some(status);
+                        self.ui.error = None;
+                        self.cancel_pr_action();
+                    }
+                    Err(e) => {
+                        self.ui.error = Some(format!("Failed: {}", e));
+                        self.ui.dirty = true;
+                    }
+                }
+                return;
+            }
             None => {
                 self.cancel_pr_action();
                 return;
@@ -442,7 +767,7 @@ impl App {
                     Some(PRActionType::Approve) => "approved",
                     Some(PRActionType::Comment) => "commented on",
                     Some(PRActionType::RequestChanges) => "requested changes on",
-                    None => "reviewed",
+                    Some(PRActionType::SubmitReview) | None => "reviewed",
                 };
                 self.ui.status = Some(format!("PR #{} {}", pr_number, action_name));
                 self.ui.error = None;
@@ -455,7 +780,56 @@ impl App {
         self.cancel_pr_action();
     }
 
-    /// Render the diff for the currently selected PR file from its patch.
+    /// GitHub review threads on `path`, with their index in the PR's threads
+    /// and the lines they cover in the current view (if shown).
+    pub(crate) fn review_threads_for(&self, path: &RelPath) -> Vec<PlacedThread<'_>> {
+        if !self.pr.active {
+            return Vec::new();
+        }
+        let sides = self
+            .pr
+            .files
+            .iter()
+            .find(|f| &f.path == path)
+            .map(|f| split_patch_with_gaps(&f.patch));
+        let full_files = self.pr.revisions.is_some();
+        self.pr
+            .threads
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| &t.path == path)
+            .map(|(idx, t)| {
+                let span = if full_files {
+                    place_review_thread(t, None)
+                } else {
+                    sides.as_ref().and_then(|s| place_review_thread(t, Some(s)))
+                };
+                (idx, t, span)
+            })
+            .collect()
+    }
+
+    /// Source to load whole PR files from, when the PR's commits are local.
+    pub(super) fn pr_content_source(&self) -> Option<DiffSource> {
+        let revisions = self.pr.revisions.as_ref().filter(|_| self.pr.active)?;
+        Some(DiffSource::Range {
+            from: revisions.base.clone(),
+            to: revisions.head.clone(),
+        })
+    }
+
+    /// Line number to show in the gutter for a 0-based line of the current
+    /// view; patch-only PR files show their real line numbers, and nothing
+    /// for gap separators.
+    pub fn gutter_line_number(&self, side: DiffSide, line: usize) -> Option<usize> {
+        match &self.pr.patch_lines {
+            Some(sides) if self.pr.active => sides.file_line(side, line).map(|n| n as usize),
+            _ => Some(line + 1),
+        }
+    }
+
+    /// Render the diff for the currently selected PR file from its patch,
+    /// when its whole files are not available.
     pub(super) fn request_current_pr_diff(&mut self) {
         if !self.pr.active || self.pr.files.is_empty() {
             return;
@@ -476,10 +850,9 @@ impl App {
         self.old_highlights.clear();
         self.new_highlights.clear();
 
-        let (old_content, new_content) = super::extract_content_from_patch(&pr_file.patch);
-
-        let old_buffer = TextBuffer::new(old_content.as_bytes());
-        let new_buffer = TextBuffer::new(new_content.as_bytes());
+        let sides = split_patch_with_gaps(&pr_file.patch);
+        let old_buffer = TextBuffer::new(sides.old.as_bytes());
+        let new_buffer = TextBuffer::new(sides.new.as_bytes());
 
         let is_binary = old_buffer.is_binary() || new_buffer.is_binary();
         let diff = if is_binary {
@@ -509,7 +882,9 @@ impl App {
         self.old_buffer = Some(old_buffer);
         self.new_buffer = Some(new_buffer);
         self.diff = diff;
+        self.pr.patch_lines = Some(sides);
         self.rebuild_view_rows();
+        self.refresh_current_file_comment_markers();
         self.viewer.scroll_y = 0;
 
         if let Some(diff) = self.diff.as_ref()
@@ -525,3 +900,25 @@ impl App {
         self.ui.dirty = true;
     }
 }
+
+/// `#3 src/lib.rs (lines span several hunks), …` for a status line.
+fn describe_unmapped(unmapped: &[UnmappedComment]) -> String {
+    unmapped
+        .iter()
+        .map(|u| format!("#{} {} ({})", u.id, u.path, u.reason))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `GitHub new L10-12` for a thread's header in the comments overlay.
+pub(super) fn review_thread_summary(thread: &ReviewThread) -> String {
+    let side = match thread.side {
+        ReviewSide::Left => "old",
+        ReviewSide::Right => "new",
+    };
+    match (thread.start_line, thread.line) {
+        (_, None) => "GitHub (outdated)".to_string(),
+        (Some(start), Some(end)) if start != end => format!("GitHub {} L{}-{}", side, start, end),
+        (_, Some(line)) => format!("GitHub {} L{}", side, line),
+    }
+}
@@ -1,6 +1,11 @@
 use std::collections::HashMap;
+use std::time::Instant;
 
-use crate::core::{CommentId, CommentStatus, PRChangedFile, PRFilter, PullRequest};
+use crate::core::{
+    AnchorConfidence, AnchorMatch, AnchorStatus, CommentId, CommentKind, CommentReply,
+    CommentStatus, DiffSide, EvologEntry, PRChangedFile, PRQuery, PatchSides, PrCheck, PrDetails,
+    PrRevisions, PullRequest, ReviewEvent, ReviewThread, WorktreeInfo,
+};
 
 /// Focus state for the UI.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -31,6 +36,12 @@ pub enum Mode {
     PRPicker,
     /// Composing PR review action.
     PRAction,
+    /// Viewing the PR's checks, description and conversation.
+    PRInfo,
+    /// Choosing another git worktree to view.
+    WorktreePicker,
+    /// Choosing two versions of a jj change to compare.
+    EvologPicker,
 }
 
 /// Type of PR review action.
@@ -42,6 +53,8 @@ pub enum PRActionType {
     Comment,
     /// Request changes on the PR.
     RequestChanges,
+    /// Submit a review carrying the local comments inline.
+    SubmitReview,
 }
 
 /// Layout mode for diff panes.
@@ -70,10 +83,21 @@ pub enum DiffViewMode {
 pub struct CommentViewItem {
     pub id: CommentId,
     pub status: CommentStatus,
+    pub author: Option<String>,
     pub message: String,
     pub anchor_summary: String,
     pub hunk_start_row: Option<usize>,
-    pub stale: bool,
+    pub anchor_status: AnchorStatus,
+    pub confidence: Option<AnchorConfidence>,
+    pub replies: Vec<CommentReply>,
+    pub edited: bool,
+    pub kind: Option<CommentKind>,
+    /// Suggested change as (original lines, replacement text).
+    pub suggestion: Option<(Vec<String>, String)>,
+    /// GitHub review thread (read-only); `id` is its index in the PR's threads.
+    pub remote: bool,
+    /// GitHub marked the thread outdated.
+    pub outdated: bool,
 }
 
 /// Per-file projection of comments onto current diff hunks.
@@ -83,6 +107,12 @@ pub struct CommentIndex {
     pub by_hunk: HashMap<usize, Vec<CommentId>>,
     /// Current hunk index by hunk digest.
     pub by_digest: HashMap<String, usize>,
+    /// Open comments placed on specific lines rather than a whole hunk.
+    pub line_ranges: Vec<(CommentId, AnchorMatch)>,
+    /// Severity of open comments that have one.
+    pub kinds: HashMap<CommentId, CommentKind>,
+    /// Lines covered by unresolved GitHub review threads, in PR mode.
+    pub review_threads: Vec<(DiffSide, usize, usize)>,
 }
 
 impl CommentIndex {
@@ -92,6 +122,42 @@ impl CommentIndex {
             .get(&hunk_idx)
             .is_some_and(|comments| !comments.is_empty())
     }
+
+    /// Whether a line on one side is covered by an open line-range comment.
+    pub fn has_line_comment(&self, side: DiffSide, line: usize) -> bool {
+        self.line_ranges
+            .iter()
+            .any(|(_, found)| found.contains(side, line))
+    }
+
+    /// Whether a line on one side is covered by an unresolved GitHub review thread.
+    pub fn has_review_thread(&self, side: DiffSide, line: usize) -> bool {
+        self.review_threads
+            .iter()
+            .any(|&(s, start, end)| s == side && (start..=end).contains(&line))
+    }
+
+    /// Most severe kind among the open comments on a hunk or covering a line.
+    pub fn severity_at(
+        &self,
+        hunk_idx: Option<usize>,
+        side: DiffSide,
+        line: Option<usize>,
+    ) -> Option<CommentKind> {
+        let on_hunk = hunk_idx
+            .and_then(|idx| self.by_hunk.get(&idx))
+            .into_iter()
+            .flatten();
+        let on_line = self
+            .line_ranges
+            .iter()
+            .filter(|(_, found)| line.is_some_and(|n| found.contains(side, n)))
+            .map(|(id, _)| id);
+        on_hunk
+            .chain(on_line)
+            .filter_map(|id| self.kinds.get(id).copied())
+            .min()
+    }
 }
 
 /// Sidebar navigation and filter state.
@@ -128,6 +194,12 @@ pub struct ViewerState {
     pub show_line_numbers: bool,
     /// Precomputed hunk view rows.
     pub hunk_view_rows: Vec<usize>,
+    /// Diff row where a visual line selection started.
+    pub selection_anchor: Option<usize>,
+    /// New-side line (0-indexed) to scroll to once the current diff has loaded.
+    pub pending_line: Option<usize>,
+    /// Diff row of a deleted line awaiting a second `o` to open the old version.
+    pub confirm_open_old: Option<usize>,
 }
 
 /// Comment viewing/editing state.
@@ -137,6 +209,8 @@ pub struct CommentsState {
     pub draft: String,
     /// Cursor byte position within the draft.
     pub draft_cursor: usize,
+    /// Severity picked for the draft (cycled with Tab).
+    pub draft_kind: Option<CommentKind>,
     /// Comments being viewed.
     pub viewing: Vec<CommentViewItem>,
     /// Selected index in view.
@@ -145,6 +219,12 @@ pub struct CommentsState {
     pub scroll: usize,
     /// Include resolved comments.
     pub include_resolved: bool,
+    /// Comment whose thread the draft replies to (`None` = new comment).
+    pub reply_to: Option<CommentId>,
+    /// Comment whose message the draft replaces.
+    pub editing: Option<CommentId>,
+    /// Comment awaiting a second `d` to confirm deletion.
+    pub confirm_delete: Option<CommentId>,
 }
 
 /// UI mode and message state.
@@ -173,8 +253,12 @@ pub struct PrState {
     pub list: Vec<PullRequest>,
     /// Loading state.
     pub loading: bool,
-    /// Filter for PR list.
-    pub filter: PRFilter,
+    /// Query the PR list was fetched with.
+    pub query: PRQuery,
+    /// Whether the last fetch filled its limit, so more PRs may exist.
+    pub has_more: bool,
+    /// Search line being edited in the picker, if any.
+    pub search_input: Option<String>,
     /// Picker selection.
     pub picker_selected: usize,
     /// Picker scroll.
@@ -183,6 +267,29 @@ pub struct PrState {
     pub action_text: String,
     /// Action type.
     pub action_type: Option<PRActionType>,
+    /// Verdict for [`PRActionType::SubmitReview`].
+    pub review_event: ReviewEvent,
+    /// Open local comments on the PR when the review was started.
+    pub review_comment_count: usize,
+    /// Review threads already on the PR.
+    pub threads: Vec<ReviewThread>,
+    /// Local commits holding the PR's whole files, when they could be fetched.
+    pub revisions: Option<PrRevisions>,
+    /// Where the selected file's lines sit in the real file, when it is shown
+    /// from its patch alone.
+    pub patch_lines: Option<PatchSides>,
+    /// CI checks of the current PR, once loaded.
+    pub checks: Option<Vec<PrCheck>>,
+    /// Why the last checks refresh failed.
+    pub checks_error: Option<String>,
+    /// When checks were last requested, for the background refresh.
+    pub checks_requested_at: Option<Instant>,
+    /// Description and conversation of the current PR, once loaded.
+    pub details: Option<PrDetails>,
+    /// Why the description could not be loaded.
+    pub details_error: Option<String>,
+    /// Scroll offset of the PR info overlay.
+    pub info_scroll: usize,
 }
 
 /// Patch mode state (stdin or external patch input).
@@ -195,3 +302,27 @@ pub struct PatchState {
     /// Display label (e.g., "stdin").
     pub label: String,
 }
+
+/// Worktree picker state.
+#[derive(Debug, Default)]
+pub struct WorktreeState {
+    /// Worktrees of the current repository.
+    pub list: Vec<WorktreeInfo>,
+    /// Picker selection.
+    pub selected: usize,
+}
+
+/// jj evolog picker state.
+#[derive(Debug, Default)]
+pub struct EvologState {
+    /// Change whose evolution is listed.
+    pub change: String,
+    /// Versions of the change, newest first.
+    pub entries: Vec<EvologEntry>,
+    /// Picker cursor.
+    pub selected: usize,
+    /// Version shown on the old side.
+    pub from_idx: usize,
+    /// Version shown on the new side.
+    pub to_idx: usize,
+}
@@ -1,4 +1,4 @@
-use super::App;
+use super::{App, Mode};
 use crate::core::ViewedStore;
 use crate::core::{DiffSource, list_changed_files, list_changed_files_from_base_with_merge_base};
 
@@ -17,21 +17,42 @@ impl App {
         }
     }
 
+    /// Poll the comments file watcher and reload comments written elsewhere.
+    pub fn poll_comments_watcher(&mut self) -> bool {
+        let Some(ref mut watcher) = self.worker.comments_watcher else {
+            return false;
+        };
+
+        if watcher.poll().is_none() {
+            return false;
+        }
+
+        self.reload_comment_state();
+        if self.ui.mode == Mode::ViewComments && self.comments.viewing.is_empty() {
+            self.close_comments();
+        }
+        self.ui.dirty = true;
+        true
+    }
+
     pub(crate) fn refresh_file_list(&mut self) {
         let current_path = self.selected_file().map(|f| f.path.clone());
 
         let new_files = match &self.source {
             DiffSource::WorkingTree => list_changed_files(&self.repo).ok(),
-            DiffSource::Base(base) => {
-                list_changed_files_from_base_with_merge_base(&self.repo, base)
-                    .ok()
-                    .map(|r| {
-                        self.cached_merge_base = Some(r.merge_base);
-                        r.files
-                    })
-            }
+            DiffSource::Base(base) => list_changed_files_from_base_with_merge_base(
+                &self.repo,
+                base,
+                &self.rename_detection,
+            )
+            .ok()
+            .map(|r| {
+                self.cached_merge_base = Some(r.merge_base);
+                r.files
+            }),
             DiffSource::Commit(_)
             | DiffSource::Range { .. }
+            | DiffSource::Evolog { .. }
             | DiffSource::FilePair { .. }
             | DiffSource::DiffTool { .. }
             | DiffSource::PullRequest { .. } => return,
@@ -41,9 +62,7 @@ impl App {
             return;
         };
 
-        if let Some(ref filter) = self.file_filter {
-            files.retain(|f| f.path.as_str().contains(filter));
-        }
+        files.retain(|f| self.file_filter.matches(f.path.as_str()));
 
         self.files = files;
         self.rebuild_path_cache();
@@ -1,7 +1,9 @@
 use super::super::worker::{
     DiffLoadRequest, DiffWorker, PrWorker, spawn_diff_worker, spawn_pr_worker,
 };
-use crate::core::{PullRequest, RepoRoot, RepoWatcher};
+use crate::core::{CommentsWatcher, PullRequest, RepoRoot, RepoWatcher};
+#[cfg(unix)]
+use crate::core::{ControlServer, RelPath};
 
 pub(super) struct WorkerState {
     pub(super) diff: DiffWorker,
@@ -13,8 +15,16 @@ pub(super) struct WorkerState {
     pub(super) next_pr_request_id: u64,
     pub(super) pending_pr_list_id: Option<u64>,
     pub(super) pending_pr_load_id: Option<u64>,
+    pub(super) pending_pr_checks_id: Option<u64>,
+    pub(super) pending_pr_details_id: Option<u64>,
     pub(super) pending_pr: Option<PullRequest>,
     pub(super) watcher: Option<RepoWatcher>,
+    pub(super) comments_watcher: Option<CommentsWatcher>,
+    #[cfg(unix)]
+    pub(super) control: Option<ControlServer>,
+    /// Last file reported to control clients, so reloads are not re-announced.
+    #[cfg(unix)]
+    pub(super) announced_path: Option<RelPath>,
 }
 
 impl WorkerState {
@@ -29,8 +39,15 @@ impl WorkerState {
             next_pr_request_id: 1,
             pending_pr_list_id: None,
             pending_pr_load_id: None,
+            pending_pr_checks_id: None,
+            pending_pr_details_id: None,
             pending_pr: None,
             watcher: None,
+            comments_watcher: None,
+            #[cfg(unix)]
+            control: None,
+            #[cfg(unix)]
+            announced_path: None,
         }
     }
 }
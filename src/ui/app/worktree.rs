@@ -0,0 +1,114 @@
+use std::path::Path;
+
+use super::worker_state::WorkerState;
+use super::{App, Mode};
+use crate::core::{
+    CommentsWatcher, DiffSource, FileViewedStore, RepoRoot, RepoWatcher, VcsPreference,
+    list_worktrees,
+};
+
+impl App {
+    /// Open the worktree picker (git working-tree and base views only).
+    pub fn open_worktree_picker(&mut self) {
+        if self.pr.active
+            || self.patch.active
+            || !matches!(self.source, DiffSource::WorkingTree | DiffSource::Base(_))
+        {
+            self.ui.error =
+                Some("Worktrees can only be switched in working tree views".to_string());
+            self.ui.dirty = true;
+            return;
+        }
+
+        match list_worktrees(&self.repo) {
+            Ok(list) if list.len() > 1 => {
+                self.worktree.selected = list.iter().position(|w| w.is_current).unwrap_or(0);
+                self.worktree.list = list;
+                self.ui.mode = Mode::WorktreePicker;
+            }
+            Ok(_) => {
+                self.ui.status = Some("No other worktrees".to_string());
+            }
+            Err(e) => {
+                self.ui.error = Some(format!("Failed to list worktrees: {}", e));
+            }
+        }
+        self.ui.dirty = true;
+    }
+
+    /// Close the worktree picker without switching.
+    pub fn close_worktree_picker(&mut self) {
+        self.ui.mode = Mode::Normal;
+        self.ui.dirty = true;
+    }
+
+    /// Move the worktree picker selection up.
+    pub fn worktree_select_prev(&mut self) {
+        if self.worktree.selected > 0 {
+            self.worktree.selected -= 1;
+            self.ui.dirty = true;
+        }
+    }
+
+    /// Move the worktree picker selection down.
+    pub fn worktree_select_next(&mut self) {
+        if self.worktree.selected + 1 < self.worktree.list.len() {
+            self.worktree.selected += 1;
+            self.ui.dirty = true;
+        }
+    }
+
+    /// Switch to the highlighted worktree.
+    pub fn worktree_apply(&mut self) {
+        self.ui.mode = Mode::Normal;
+        let Some(target) = self.worktree.list.get(self.worktree.selected).cloned() else {
+            return;
+        };
+        if let Err(e) = self.switch_worktree(&target.path) {
+            self.ui.error = Some(format!("Failed to open worktree: {}", e));
+        }
+        self.ui.dirty = true;
+    }
+
+    /// Show the same diff source for another checkout of this repository.
+    ///
+    /// Viewed state and comments follow the new checkout (or stay shared when
+    /// shared worktree state is enabled).
+    pub fn switch_worktree(&mut self, path: &Path) -> anyhow::Result<()> {
+        let repo = RepoRoot::discover(path, VcsPreference::Git)?
+            .with_shared_state(self.repo.shares_state());
+        if repo == self.repo {
+            return Ok(());
+        }
+
+        self.viewed.save()?;
+        self.viewed = FileViewedStore::new(repo.state_key())?;
+        #[cfg(unix)]
+        let control = self.worker.control.take();
+        self.worker = WorkerState::new(&repo);
+        #[cfg(unix)]
+        {
+            self.worker.control = control;
+        }
+        self.repo = repo;
+        match RepoWatcher::new(&self.repo) {
+            Ok(w) => self.worker.watcher = Some(w),
+            Err(e) => self.ui.error = Some(format!("File watching disabled: {}", e)),
+        }
+        self.worker.comments_watcher = CommentsWatcher::new(&self.repo).ok();
+        self.reset_comment_context();
+
+        self.cached_merge_base = None;
+        self.diff = None;
+        self.old_buffer = None;
+        self.new_buffer = None;
+        self.viewer.hunk_view_rows.clear();
+        self.viewer.scroll_y = 0;
+        self.sidebar.scroll = 0;
+        self.refresh_file_list();
+
+        self.ui.status = Some(format!("Worktree: {}", self.repo.path().display()));
+        self.ui.dirty = true;
+        Ok(())
+    }
+}
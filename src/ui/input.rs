@@ -2,7 +2,7 @@
 
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 
-use super::app::{App, Focus, Mode};
+use super::app::{App, Focus, Mode, PRActionType};
 
 /// Handle a crossterm event.
 /// Returns true if the event was handled.
@@ -25,9 +25,16 @@ fn handle_key(app: &mut App, key: KeyEvent) -> bool {
         Mode::Help => return handle_help_key(app, key),
         Mode::PRPicker => return handle_pr_picker_key(app, key),
         Mode::PRAction => return handle_pr_action_key(app, key),
+        Mode::PRInfo => return handle_pr_info_key(app, key),
+        Mode::WorktreePicker => return handle_worktree_picker_key(app, key),
+        Mode::EvologPicker => return handle_evolog_picker_key(app, key),
         Mode::Normal => {}
     }
 
+    if key.code != KeyCode::Char('o') {
+        app.viewer.confirm_open_old = None;
+    }
+
     // Global keys (only in Normal mode)
     match key.code {
         KeyCode::Char('q') => {
@@ -66,6 +73,14 @@ fn handle_key(app: &mut App, key: KeyEvent) -> bool {
             app.open_help();
             return true;
         }
+        KeyCode::Char('W') => {
+            app.open_worktree_picker();
+            return true;
+        }
+        KeyCode::Char('E') => {
+            app.open_evolog_picker();
+            return true;
+        }
         KeyCode::Char('r') => {
             app.manual_reload();
             return true;
@@ -78,6 +93,10 @@ fn handle_key(app: &mut App, key: KeyEvent) -> bool {
             app.open_selected_in_editor();
             return true;
         }
+        KeyCode::Char('X') if app.comments_enabled() => {
+            app.export_review_report();
+            return true;
+        }
         KeyCode::Char('P') => {
             if !app.pr.active {
                 app.open_pr_picker();
@@ -140,6 +159,14 @@ fn handle_diff_key(app: &mut App, key: KeyEvent) -> bool {
             app.open_pr_in_browser();
             true
         }
+        KeyCode::Char('S') if app.pr.active && app.pr.current.is_some() => {
+            app.start_pr_submit_review();
+            true
+        }
+        KeyCode::Char('I') if app.pr.active && app.pr.current.is_some() => {
+            app.open_pr_info();
+            true
+        }
         KeyCode::Char('j') | KeyCode::Down => {
             app.scroll_diff(1, 0);
             true
@@ -195,11 +222,19 @@ fn handle_diff_key(app: &mut App, key: KeyEvent) -> bool {
             app.viewer.scroll_y = 0;
             true
         }
-        KeyCode::Char('c') if app.is_worktree_mode() => {
+        KeyCode::Char('v') if app.comments_enabled() => {
+            app.toggle_line_selection();
+            true
+        }
+        KeyCode::Esc if app.viewer.selection_anchor.is_some() => {
+            app.clear_line_selection();
+            true
+        }
+        KeyCode::Char('c') if app.comments_enabled() => {
             app.start_add_comment();
             true
         }
-        KeyCode::Char('C') if app.is_worktree_mode() => {
+        KeyCode::Char('C') if app.comments_enabled() => {
             app.show_comments();
             true
         }
@@ -230,6 +265,14 @@ fn handle_add_comment_key(app: &mut App, key: KeyEvent) -> bool {
             app.comment_insert_newline();
             true
         }
+        KeyCode::Tab => {
+            app.comment_cycle_kind();
+            true
+        }
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.comment_insert_suggestion();
+            true
+        }
         KeyCode::Left => {
             app.comment_move_left();
             true
@@ -268,6 +311,9 @@ fn handle_add_comment_key(app: &mut App, key: KeyEvent) -> bool {
 
 /// Handle keys when viewing comments overlay.
 fn handle_view_comments_key(app: &mut App, key: KeyEvent) -> bool {
+    if key.code != KeyCode::Char('d') {
+        app.comments.confirm_delete = None;
+    }
     match key.code {
         KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('C') => {
             app.close_comments();
@@ -286,7 +332,23 @@ fn handle_view_comments_key(app: &mut App, key: KeyEvent) -> bool {
             true
         }
         KeyCode::Char('r') => {
-            app.comments_resolve_selected();
+            app.comments_toggle_resolved_selected();
+            true
+        }
+        KeyCode::Char('c') => {
+            app.comments_start_reply();
+            true
+        }
+        KeyCode::Char('e') => {
+            app.comments_start_edit();
+            true
+        }
+        KeyCode::Char('d') => {
+            app.comments_delete_selected();
+            true
+        }
+        KeyCode::Char('s') => {
+            app.comments_apply_suggestion_selected();
             true
         }
         KeyCode::Char('a') => {
@@ -345,6 +407,91 @@ fn handle_theme_selector_key(app: &mut App, key: KeyEvent) -> bool {
     }
 }
 
+/// Handle keys in worktree picker mode.
+fn handle_worktree_picker_key(app: &mut App, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('W') => {
+            app.close_worktree_picker();
+            true
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.worktree_select_next();
+            true
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.worktree_select_prev();
+            true
+        }
+        KeyCode::Enter => {
+            app.worktree_apply();
+            true
+        }
+        _ => true, // consume all keys in overlay
+    }
+}
+
+/// Handle keys in the PR info overlay.
+fn handle_pr_info_key(app: &mut App, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('I') => {
+            app.close_pr_info();
+            true
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.scroll_pr_info(1);
+            true
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.scroll_pr_info(-1);
+            true
+        }
+        KeyCode::PageDown => {
+            app.scroll_pr_info(10);
+            true
+        }
+        KeyCode::PageUp => {
+            app.scroll_pr_info(-10);
+            true
+        }
+        KeyCode::Char('r') => {
+            app.refresh_pr_info();
+            true
+        }
+        _ => true, // consume all keys in overlay
+    }
+}
+
+/// Handle keys in evolog picker mode.
+fn handle_evolog_picker_key(app: &mut App, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('E') => {
+            app.close_evolog_picker();
+            true
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.evolog_select_next();
+            true
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.evolog_select_prev();
+            true
+        }
+        KeyCode::Char('[') => {
+            app.evolog_mark_old();
+            true
+        }
+        KeyCode::Char(']') => {
+            app.evolog_mark_new();
+            true
+        }
+        KeyCode::Enter => {
+            app.evolog_apply();
+            true
+        }
+        _ => true, // consume all keys in overlay
+    }
+}
+
 /// Handle keys when viewing help overlay.
 fn handle_help_key(app: &mut App, key: KeyEvent) -> bool {
     match key.code {
@@ -451,6 +598,23 @@ fn handle_click(app: &mut App, x: u16, y: u16) {
 
 /// Handle keys in PR picker mode.
 fn handle_pr_picker_key(app: &mut App, key: KeyEvent) -> bool {
+    if let Some(input) = app.pr.search_input.as_mut() {
+        match key.code {
+            KeyCode::Esc => app.pr_picker_cancel_search(),
+            KeyCode::Enter => app.pr_picker_apply_search(),
+            KeyCode::Backspace => {
+                input.pop();
+                app.ui.dirty = true;
+            }
+            KeyCode::Char(c) => {
+                input.push(c);
+                app.ui.dirty = true;
+            }
+            _ => return false,
+        }
+        return true;
+    }
+
     match key.code {
         KeyCode::Esc | KeyCode::Char('q') => {
             app.close_pr_picker();
@@ -476,6 +640,18 @@ fn handle_pr_picker_key(app: &mut App, key: KeyEvent) -> bool {
             app.pr_picker_prev_filter();
             true
         }
+        KeyCode::Char('s') => {
+            app.pr_picker_next_state();
+            true
+        }
+        KeyCode::Char('o') => {
+            app.pr_picker_next_sort();
+            true
+        }
+        KeyCode::Char('/') => {
+            app.pr_picker_start_search();
+            true
+        }
         KeyCode::Char('r') => {
             app.fetch_pr_list();
             true
@@ -495,6 +671,10 @@ fn handle_pr_action_key(app: &mut App, key: KeyEvent) -> bool {
             app.submit_pr_action();
             true
         }
+        KeyCode::Tab if app.pr.action_type == Some(PRActionType::SubmitReview) => {
+            app.cycle_pr_review_event();
+            true
+        }
         KeyCode::Char(c) => {
             app.pr.action_text.push(c);
             app.ui.dirty = true;
@@ -8,9 +8,11 @@ use ratatui::{
     widgets::Paragraph,
 };
 
-use crate::core::FileChangeKind;
+use crate::core::{ChecksSummary, FileChangeKind};
 use crate::ui::app::{App, Focus, Mode};
 
+use super::helpers::checks_badge;
+
 /// Render the top bar showing current file info.
 pub fn render_top_bar(frame: &mut Frame, app: &App, area: Rect) {
     let file = app.selected_file();
@@ -23,6 +25,7 @@ pub fn render_top_bar(frame: &mut Frame, app: &App, area: Rect) {
         FileChangeKind::Deleted => ("D", app.theme.error),
         FileChangeKind::Untracked => ("?", app.theme.text_muted),
         FileChangeKind::Renamed => ("R", app.theme.accent_dim),
+        FileChangeKind::Copied => ("C", app.theme.accent_dim),
     });
 
     let mut spans = vec![Span::styled(
@@ -43,6 +46,24 @@ pub fn render_top_bar(frame: &mut Frame, app: &App, area: Rect) {
             " ",
             Style::default().bg(app.theme.bg_elevated),
         ));
+        if let Some((badge, color)) = app
+            .pr
+            .checks
+            .as_deref()
+            .and_then(|checks| checks_badge(ChecksSummary::of(checks), &app.theme))
+        {
+            spans.push(Span::styled(
+                badge,
+                Style::default()
+                    .fg(color)
+                    .bg(app.theme.bg_elevated)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::styled(
+                "  ",
+                Style::default().bg(app.theme.bg_elevated),
+            ));
+        }
     }
 
     // Change kind badge
@@ -87,8 +108,8 @@ pub fn render_top_bar(frame: &mut Frame, app: &App, area: Rect) {
         ));
     }
 
-    // Open comments for this file (only in worktree mode)
-    if app.is_worktree_mode()
+    // Open comments for this file
+    if app.comments_enabled()
         && let Some(file) = file
         && let Some(count) = app.open_comment_counts.get(&file.path)
         && *count > 0
@@ -217,6 +238,69 @@ pub fn render_bottom_bar(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
+    // Worktree picker mode
+    if app.ui.mode == Mode::WorktreePicker {
+        let line = Line::from(vec![
+            Span::styled(
+                " Worktree ",
+                Style::default()
+                    .fg(app.theme.accent)
+                    .bg(app.theme.bg_elevated),
+            ),
+            Span::styled(
+                " j/k: move  Enter: open  Esc: cancel",
+                Style::default()
+                    .fg(app.theme.text_muted)
+                    .bg(app.theme.bg_elevated),
+            ),
+        ]);
+        let para = Paragraph::new(line).style(Style::default().bg(app.theme.bg_elevated));
+        frame.render_widget(para, area);
+        return;
+    }
+
+    // Evolog picker mode
+    if app.ui.mode == Mode::EvologPicker {
+        let line = Line::from(vec![
+            Span::styled(
+                " Evolog ",
+                Style::default()
+                    .fg(app.theme.accent)
+                    .bg(app.theme.bg_elevated),
+            ),
+            Span::styled(
+                " j/k: move  [: old  ]: new  Enter: compare  Esc: cancel",
+                Style::default()
+                    .fg(app.theme.text_muted)
+                    .bg(app.theme.bg_elevated),
+            ),
+        ]);
+        let para = Paragraph::new(line).style(Style::default().bg(app.theme.bg_elevated));
+        frame.render_widget(para, area);
+        return;
+    }
+
+    // PR info overlay mode
+    if app.ui.mode == Mode::PRInfo {
+        let line = Line::from(vec![
+            Span::styled(
+                " PR info ",
+                Style::default()
+                    .fg(app.theme.accent)
+                    .bg(app.theme.bg_elevated),
+            ),
+            Span::styled(
+                " j/k: scroll  r: refresh  Esc: close",
+                Style::default()
+                    .fg(app.theme.text_muted)
+                    .bg(app.theme.bg_elevated),
+            ),
+        ]);
+        let para = Paragraph::new(line).style(Style::default().bg(app.theme.bg_elevated));
+        frame.render_widget(para, area);
+        return;
+    }
+
     // Comments overlay mode
     if app.ui.mode == Mode::ViewComments {
         let scope = if app.comments.include_resolved {
@@ -231,12 +315,20 @@ pub fn render_bottom_bar(frame: &mut Frame, app: &App, area: Rect) {
                     .fg(app.theme.accent)
                     .bg(app.theme.bg_elevated),
             ),
-            Span::styled(
-                " j/k: move  Enter: jump  r: resolve  a: all/open  Esc: close",
-                Style::default()
-                    .fg(app.theme.text_muted)
-                    .bg(app.theme.bg_elevated),
-            ),
+            match app.comments.confirm_delete {
+                Some(id) => Span::styled(
+                    format!(" Press d again to delete comment {}", id),
+                    Style::default()
+                        .fg(app.theme.warning)
+                        .bg(app.theme.bg_elevated),
+                ),
+                None => Span::styled(
+                    " j/k: move  Enter: jump  c: reply  e: edit  d: delete  s: apply suggestion  r: resolve/reopen  a: all/open  Esc: close",
+                    Style::default()
+                        .fg(app.theme.text_muted)
+                        .bg(app.theme.bg_elevated),
+                ),
+            },
         ]);
         let para = Paragraph::new(line).style(Style::default().bg(app.theme.bg_elevated));
         frame.render_widget(para, area);
@@ -304,6 +396,7 @@ pub fn render_bottom_bar(frame: &mut Frame, app: &App, area: Rect) {
             ("w", "wrap"),
             ("n", "nums"),
             ("c", "comment"),
+            ("v", "select"),
             ("C", "threads"),
             (
                 "s",
@@ -8,13 +8,13 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::core::{ChangeKind, FileChangeKind, InlineSpan};
+use crate::core::{ChangeKind, DiffSide, FileChangeKind, InlineSpan};
 use crate::highlight::{ScopeInfo, StyleId, StyledSpan, find_enclosing_scope};
 use crate::ui::app::{App, DiffPaneMode, Focus};
 
 use super::helpers::{
-    SpanBuilder, ThemeStyles, boost_muted_fg, gutter_width, line_number_width, sanitize_char,
-    spaces, style_to_color, truncate_str, visible_tab_spaces,
+    SpanBuilder, ThemeStyles, boost_muted_fg, comment_kind_color, gutter_width, line_number_width,
+    sanitize_char, spaces, style_to_color, truncate_str, visible_tab_spaces,
 };
 
 /// Render the diff view.
@@ -89,6 +89,16 @@ pub fn render_diff(frame: &mut Frame, app: &App, area: Rect) {
     };
 
     if diff.rows().is_empty() || !diff.has_changes() {
+        if let Some(mode_change) = app.selected_file().and_then(|f| f.mode_change_label()) {
+            render_state_card(
+                frame,
+                app,
+                content,
+                "Mode changed",
+                &format!("{} (contents unchanged)", mode_change),
+            );
+            return;
+        }
         render_state_card(
             frame,
             app,
@@ -135,6 +145,15 @@ fn render_diff_header(frame: &mut Frame, app: &App, area: Rect) {
         crate::core::FileChangeKind::Deleted => ("D", app.theme.error),
         crate::core::FileChangeKind::Untracked => ("?", app.theme.text_muted),
         crate::core::FileChangeKind::Renamed => ("R", app.theme.accent_dim),
+        crate::core::FileChangeKind::Copied => ("C", app.theme.accent_dim),
+    });
+    let mode_change = file.and_then(|f| f.mode_change_label());
+    let origin = file.and_then(|f| {
+        let old = f.old_path.as_ref()?;
+        Some(match f.similarity {
+            Some(score) => format!("from {} {}%", old.as_str(), score),
+            None => format!("from {}", old.as_str()),
+        })
     });
     let hunk_text = app
         .current_hunk_info()
@@ -152,6 +171,8 @@ fn render_diff_header(frame: &mut Frame, app: &App, area: Rect) {
         crate::ui::app::DiffViewMode::FullFile => "full",
     };
     let right = [
+        origin.as_deref(),
+        mode_change.as_deref(),
         hunk_text.as_deref(),
         Some(view_text),
         Some(mode_text),
@@ -383,6 +404,12 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: Rect, is_old: bool) {
         .map(|buf| buf.line_count())
         .into_iter()
         .chain(app.new_buffer.as_ref().map(|buf| buf.line_count()))
+        .chain(
+            app.pr
+                .patch_lines
+                .as_ref()
+                .map(|sides| sides.max_file_line() as usize),
+        )
         .max()
         .unwrap_or(1);
     let line_num_width = line_number_width(max_line_num);
@@ -417,18 +444,17 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: Rect, is_old: bool) {
         bg_style: Style,
         code_spans: Vec<Span<'static>>,
         visible_len: usize,
-        has_comment: bool,
+        marker: Option<(&'static str, Color)>,
     }
 
+    let side = if is_old { DiffSide::Old } else { DiffSide::New };
+    let selected_rows = app.selected_rows();
+
     let visible_rows = app.visible_diff_rows(content_height.max(1));
     let mut rendered: Vec<RenderedLine> = Vec::with_capacity(visible_rows.len());
     let mut max_visible_len = 0usize;
 
     for (row_idx, row) in visible_rows {
-        let has_comment = app.is_worktree_mode()
-            && diff
-                .hunk_at_row(row_idx)
-                .is_some_and(|h| app.comment_index.has_open_comment(h));
         let (line_ref, bg_color, inline_bg, bg_style) = if is_old {
             match (&row.old, row.kind) {
                 (Some(line), ChangeKind::Equal) => (
@@ -486,8 +512,25 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: Rect, is_old: bool) {
         };
 
         let line_idx = line_ref.map(|l| l.line_num);
+        let is_selected =
+            selected_rows.is_some_and(|(first, last)| (first..=last).contains(&row_idx));
+        let hunk_idx = diff.hunk_at_row(row_idx);
+        let has_comment = app.comments_enabled()
+            && (hunk_idx.is_some_and(|h| app.comment_index.has_open_comment(h))
+                || line_idx.is_some_and(|n| app.comment_index.has_line_comment(side, n)));
+        let marker = if is_selected {
+            Some(("▌", app.theme.accent))
+        } else if has_comment {
+            let kind = app.comment_index.severity_at(hunk_idx, side, line_idx);
+            Some(("•", comment_kind_color(kind, &app.theme)))
+        } else if line_idx.is_some_and(|n| app.comment_index.has_review_thread(side, n)) {
+            Some(("◆", app.theme.accent))
+        } else {
+            None
+        };
         let line_num_str = line_idx
-            .map(|n| format!("{:>width$}", n + 1, width = line_num_width))
+            .and_then(|n| app.gutter_line_number(side, n))
+            .map(|n| format!("{:>width$}", n, width = line_num_width))
             .unwrap_or_else(|| spaces(line_num_width).to_string());
         let content = line_ref.map(|l| l.content.as_str()).unwrap_or("");
         let inline_spans = line_ref.and_then(|l| l.inline_spans.as_ref());
@@ -575,7 +618,7 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: Rect, is_old: bool) {
             bg_style,
             code_spans,
             visible_len,
-            has_comment,
+            marker,
         });
     }
 
@@ -623,7 +666,7 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: Rect, is_old: bool) {
             append_gutter(
                 &mut spans,
                 None,
-                false,
+                None,
                 sticky_bg,
                 sticky_bg_style,
                 is_old,
@@ -635,7 +678,7 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: Rect, is_old: bool) {
             append_gutter(
                 &mut spans,
                 None,
-                false,
+                None,
                 sticky_bg,
                 sticky_bg_style,
                 is_old,
@@ -670,7 +713,7 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: Rect, is_old: bool) {
         for (segment_idx, (segment_spans, segment_len)) in wrapped_segments.into_iter().enumerate()
         {
             let mut spans: Vec<Span> = Vec::new();
-            let show_marker = segment_idx == 0 && row.has_comment;
+            let marker = row.marker.filter(|_| segment_idx == 0);
             let line_num = if segment_idx == 0 {
                 Some(row.line_num_str.as_str())
             } else {
@@ -692,7 +735,7 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: Rect, is_old: bool) {
                 append_gutter(
                     &mut spans,
                     line_num,
-                    show_marker,
+                    marker,
                     row.bg_color,
                     row.bg_style,
                     is_old,
@@ -704,7 +747,7 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: Rect, is_old: bool) {
                 append_gutter(
                     &mut spans,
                     line_num,
-                    show_marker,
+                    marker,
                     row.bg_color,
                     row.bg_style,
                     is_old,
@@ -731,7 +774,7 @@ fn render_diff_pane(frame: &mut Frame, app: &App, area: Rect, is_old: bool) {
 fn append_gutter(
     spans: &mut Vec<Span<'static>>,
     line_num: Option<&str>,
-    has_marker: bool,
+    marker: Option<(&'static str, Color)>,
     bg_color: Color,
     bg_style: Style,
     is_old: bool,
@@ -739,11 +782,9 @@ fn append_gutter(
     line_num_width: usize,
     styles: &ThemeStyles,
 ) {
-    let marker_char = if has_marker { "•" } else { " " };
-    let marker_style = if has_marker {
-        styles.accent.bg(bg_color)
-    } else {
-        bg_style
+    let (marker_char, marker_style) = match marker {
+        Some((ch, color)) => (ch, Style::default().fg(color).bg(bg_color)),
+        None => (" ", bg_style),
     };
     let line_num = line_num.unwrap_or_else(|| spaces(line_num_width));
 
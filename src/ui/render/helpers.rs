@@ -3,6 +3,7 @@
 use ratatui::style::{Color, Style};
 use ratatui::text::Span;
 
+use crate::core::{CheckBucket, ChecksSummary, CommentKind, ReviewDecision};
 use crate::highlight::StyleId;
 use crate::theme::Theme;
 
@@ -28,6 +29,49 @@ pub fn gutter_width(show_line_numbers: bool, max_line_num: usize) -> usize {
     number_width + 2 // marker + separator
 }
 
+/// Color for a comment's severity (plain comments use the accent).
+pub fn comment_kind_color(kind: Option<CommentKind>, theme: &Theme) -> Color {
+    match kind {
+        Some(CommentKind::Bug) => theme.error,
+        Some(CommentKind::Warning) => theme.warning,
+        Some(CommentKind::Suggestion) | None => theme.accent,
+        Some(CommentKind::Good) => theme.success,
+    }
+}
+
+/// Icon and color for a CI check outcome.
+pub fn check_bucket_icon(bucket: CheckBucket, theme: &Theme) -> (&'static str, Color) {
+    match bucket {
+        CheckBucket::Pass => ("✓", theme.success),
+        CheckBucket::Fail | CheckBucket::Cancel => ("✗", theme.error),
+        CheckBucket::Pending => ("●", theme.warning),
+        CheckBucket::Skipping => ("-", theme.text_muted),
+    }
+}
+
+/// Short label and color for a PR's review decision.
+pub fn review_decision_label(decision: ReviewDecision, theme: &Theme) -> (&'static str, Color) {
+    match decision {
+        ReviewDecision::Approved => ("approved", theme.success),
+        ReviewDecision::ChangesRequested => ("changes", theme.error),
+        ReviewDecision::ReviewRequired => ("review", theme.warning),
+    }
+}
+
+/// Compact badge for a PR's checks: failures first, then pending, else passed.
+pub fn checks_badge(summary: ChecksSummary, theme: &Theme) -> Option<(String, Color)> {
+    let total = summary.passed + summary.failed + summary.pending + summary.skipped;
+    if total == 0 {
+        None
+    } else if summary.failed > 0 {
+        Some((format!("✗ {}/{}", summary.failed, total), theme.error))
+    } else if summary.pending > 0 {
+        Some((format!("● {}/{}", summary.pending, total), theme.warning))
+    } else {
+        Some((format!("✓ {}", summary.passed), theme.success))
+    }
+}
+
 /// Tab stop width for display alignment.
 pub const TAB_WIDTH: usize = 8;
 
@@ -54,6 +54,9 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         Mode::Help => overlays::render_help_overlay(frame, app),
         Mode::PRPicker => overlays::render_pr_picker_overlay(frame, app),
         Mode::PRAction => overlays::render_pr_action_overlay(frame, app),
+        Mode::PRInfo => overlays::render_pr_info_overlay(frame, app),
+        Mode::WorktreePicker => overlays::render_worktree_picker(frame, app),
+        Mode::EvologPicker => overlays::render_evolog_picker(frame, app),
         _ => {}
     }
 }
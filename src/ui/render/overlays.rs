@@ -1,18 +1,18 @@
-//! Modal overlay rendering (comments, theme selector, help, PR picker).
+//! Modal overlay rendering (comments, theme selector, help, PR, worktree and evolog pickers).
 
 use ratatui::{
     Frame,
     layout::Rect,
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 
-use crate::core::CommentStatus;
+use crate::core::{AnchorStatus, ChecksSummary, CommentStatus, ReviewEvent};
 use crate::theme::Theme;
-use crate::ui::app::{App, PRActionType};
+use crate::ui::app::{App, CommentViewItem, PRActionType};
 
-use super::helpers::truncate_str;
+use super::helpers::{check_bucket_icon, comment_kind_color, review_decision_label, truncate_str};
 
 /// Render the add-comment editor overlay.
 pub fn render_add_comment_overlay(frame: &mut Frame, app: &App) {
@@ -28,7 +28,13 @@ pub fn render_add_comment_overlay(frame: &mut Frame, app: &App) {
         .borders(Borders::ALL)
         .border_style(Style::default().fg(app.theme.accent))
         .title(Span::styled(
-            " Comment ",
+            if app.comments.reply_to.is_some() {
+                " Reply "
+            } else if app.comments.editing.is_some() {
+                " Edit comment "
+            } else {
+                " Comment "
+            },
             Style::default()
                 .fg(app.theme.accent)
                 .add_modifier(Modifier::BOLD),
@@ -40,24 +46,29 @@ pub fn render_add_comment_overlay(frame: &mut Frame, app: &App) {
         return;
     }
 
-    let hint = app
-        .diff
-        .as_ref()
-        .and_then(|diff| {
-            app.view_row_to_diff_row(app.viewer.scroll_y)
-                .and_then(|row| diff.hunk_at_row(row))
-        })
-        .and_then(|idx| app.diff.as_ref().and_then(|diff| diff.hunks().get(idx)))
-        .map(|hunk| {
-            format!(
-                "@@ -{},{} +{},{} @@",
-                hunk.old_range.0 + 1,
-                hunk.old_range.1,
-                hunk.new_range.0 + 1,
-                hunk.new_range.1
-            )
-        })
-        .unwrap_or_else(|| "Current hunk".to_string());
+    let hint = if let Some(id) = app.comments.reply_to {
+        format!("Reply to #{}", id)
+    } else if let Some(id) = app.comments.editing {
+        format!("Editing #{}", id)
+    } else {
+        app.diff
+            .as_ref()
+            .and_then(|diff| {
+                app.view_row_to_diff_row(app.viewer.scroll_y)
+                    .and_then(|row| diff.hunk_at_row(row))
+            })
+            .and_then(|idx| app.diff.as_ref().and_then(|diff| diff.hunks().get(idx)))
+            .map(|hunk| {
+                format!(
+                    "@@ -{},{} +{},{} @@",
+                    hunk.old_range.0 + 1,
+                    hunk.old_range.1,
+                    hunk.new_range.0 + 1,
+                    hunk.new_range.1
+                )
+            })
+            .unwrap_or_else(|| "Current hunk".to_string())
+    };
     frame.render_widget(
         Paragraph::new(Line::from(Span::styled(
             hint,
@@ -87,7 +98,7 @@ pub fn render_add_comment_overlay(frame: &mut Frame, app: &App) {
         editor_area,
     );
 
-    let footer = Line::from(vec![
+    let mut footer = vec![
         Span::styled(
             "Enter",
             Style::default()
@@ -124,7 +135,51 @@ pub fn render_add_comment_overlay(frame: &mut Frame, app: &App) {
                 .fg(app.theme.text_muted)
                 .bg(app.theme.bg_elevated),
         ),
-    ]);
+    ];
+    if app.comments.reply_to.is_none() {
+        footer.insert(
+            4,
+            Span::styled(
+                "Tab",
+                Style::default()
+                    .fg(app.theme.accent)
+                    .bg(app.theme.bg_elevated),
+            ),
+        );
+        footer.insert(
+            5,
+            Span::styled(
+                format!(
+                    " kind: {}  ",
+                    app.comments.draft_kind.map_or("none", |k| k.as_str())
+                ),
+                Style::default()
+                    .fg(comment_kind_color(app.comments.draft_kind, &app.theme))
+                    .bg(app.theme.bg_elevated),
+            ),
+        );
+    }
+    if app.comments.reply_to.is_none() && app.comments.editing.is_none() {
+        footer.insert(
+            6,
+            Span::styled(
+                "Ctrl-S",
+                Style::default()
+                    .fg(app.theme.accent)
+                    .bg(app.theme.bg_elevated),
+            ),
+        );
+        footer.insert(
+            7,
+            Span::styled(
+                " suggest  ",
+                Style::default()
+                    .fg(app.theme.text_muted)
+                    .bg(app.theme.bg_elevated),
+            ),
+        );
+    }
+    let footer = Line::from(footer);
     frame.render_widget(
         Paragraph::new(footer).style(Style::default().bg(app.theme.bg_elevated)),
         Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1),
@@ -208,8 +263,9 @@ pub fn render_comments_overlay(frame: &mut Frame, app: &App) {
     let desired_height = app
         .comments
         .viewing
-        .len()
-        .saturating_mul(2)
+        .iter()
+        .map(comment_item_height)
+        .sum::<usize>()
         .saturating_add(2) as u16;
     let height = desired_height.clamp(min_overlay_height, max_overlay_height);
 
@@ -255,11 +311,22 @@ pub fn render_comments_overlay(frame: &mut Frame, app: &App) {
     } else {
         let total = app.comments.viewing.len();
         let selected = app.comments.selected.min(total - 1);
-        let visible_items = ((inner.height as usize) / 2).max(1);
-        let scroll = (selected + 1).saturating_sub(visible_items);
-        let end = (scroll + visible_items).min(total);
+        // Scroll so the selected thread (and as many replies as fit) stays visible.
+        let mut scroll = selected;
+        let mut used = comment_item_height(&app.comments.viewing[selected]);
+        while scroll > 0 {
+            let prev = comment_item_height(&app.comments.viewing[scroll - 1]);
+            if used + prev > inner.height as usize {
+                break;
+            }
+            used += prev;
+            scroll -= 1;
+        }
 
-        for idx in scroll..end {
+        for idx in scroll..total {
+            if lines.len() >= inner.height as usize {
+                break;
+            }
             let item = &app.comments.viewing[idx];
             let is_selected = idx == selected;
 
@@ -286,7 +353,11 @@ pub fn render_comments_overlay(frame: &mut Frame, app: &App) {
                 Style::default().fg(app.theme.accent).bg(row_bg),
             ));
             spans.push(Span::styled(
-                format!("#{} ", item.id),
+                if item.remote {
+                    "◆ ".to_string()
+                } else {
+                    format!("#{} ", item.id)
+                },
                 Style::default()
                     .fg(app.theme.accent)
                     .bg(row_bg)
@@ -297,15 +368,46 @@ pub fn render_comments_overlay(frame: &mut Frame, app: &App) {
                 Style::default().fg(app.theme.success).bg(row_bg),
             ));
             spans.push(Span::styled(" ", Style::default().bg(row_bg)));
+            if let Some(kind) = item.kind {
+                spans.push(Span::styled(
+                    format!("[{}] ", kind.as_str()),
+                    Style::default()
+                        .fg(comment_kind_color(Some(kind), &app.theme))
+                        .bg(row_bg),
+                ));
+            }
             spans.push(Span::styled(
                 item.anchor_summary.as_str(),
                 Style::default().fg(app.theme.text_muted).bg(row_bg),
             ));
-            if item.stale {
+            if let Some(author) = &item.author {
+                // Name only; the email is noise in a one-line header.
+                let name = author.split(" <").next().unwrap_or(author);
+                spans.push(Span::styled(
+                    format!(" · {}", name),
+                    Style::default().fg(app.theme.text_dim).bg(row_bg),
+                ));
+            }
+            if item.edited {
                 spans.push(Span::styled(
+                    " (edited)",
+                    Style::default().fg(app.theme.text_dim).bg(row_bg),
+                ));
+            }
+            match (item.anchor_status, item.confidence) {
+                _ if item.outdated => spans.push(Span::styled(
+                    " [outdated]",
+                    Style::default().fg(app.theme.warning).bg(row_bg),
+                )),
+                (AnchorStatus::Stale, _) => spans.push(Span::styled(
                     " [stale]",
                     Style::default().fg(app.theme.warning).bg(row_bg),
-                ));
+                )),
+                (AnchorStatus::Relocated, Some(confidence)) => spans.push(Span::styled(
+                    format!(" [relocated, {} confidence]", confidence.as_str()),
+                    Style::default().fg(app.theme.accent).bg(row_bg),
+                )),
+                _ => {}
             }
             lines.push(Line::from(spans));
             if lines.len() < inner.height as usize {
@@ -324,6 +426,43 @@ pub fn render_comments_overlay(frame: &mut Frame, app: &App) {
                     ),
                 ]));
             }
+            for (removed, text) in suggestion_lines(item) {
+                if lines.len() >= inner.height as usize {
+                    break;
+                }
+                let body_bg = app.theme.bg_elevated;
+                let (sign, color) = if removed {
+                    ("  - ", app.theme.error)
+                } else {
+                    ("  + ", app.theme.success)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(sign, Style::default().fg(color).bg(body_bg)),
+                    Span::styled(
+                        truncate_str(text, inner.width.saturating_sub(4) as usize),
+                        Style::default().fg(color).bg(body_bg),
+                    ),
+                ]));
+            }
+            for reply in &item.replies {
+                if lines.len() >= inner.height as usize {
+                    break;
+                }
+                let body_bg = app.theme.bg_elevated;
+                let author = reply.author.as_deref().unwrap_or("anonymous");
+                let prefix = format!("    ↳ {}: ", author);
+                let room = (inner.width as usize).saturating_sub(prefix.chars().count());
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        prefix,
+                        Style::default().fg(app.theme.text_muted).bg(body_bg),
+                    ),
+                    Span::styled(
+                        truncate_str(reply.message.as_str(), room),
+                        Style::default().fg(msg_color).bg(body_bg),
+                    ),
+                ]));
+            }
         }
     }
 
@@ -338,6 +477,26 @@ pub fn render_comments_overlay(frame: &mut Frame, app: &App) {
     frame.render_widget(para, inner);
 }
 
+/// Rows used by one thread in the comments overlay: header, message, replies.
+fn comment_item_height(item: &CommentViewItem) -> usize {
+    2 + suggestion_lines(item).count() + item.replies.len()
+}
+
+/// Mini diff of a comment's suggestion: removed lines (`true`) then added lines.
+fn suggestion_lines(item: &CommentViewItem) -> impl Iterator<Item = (bool, &str)> {
+    item.suggestion.iter().flat_map(|(original, replacement)| {
+        let added = replacement
+            .strip_suffix('\n')
+            .unwrap_or(replacement)
+            .split('\n')
+            .filter(|_| !replacement.is_empty());
+        original
+            .iter()
+            .map(|line| (true, line.as_str()))
+            .chain(added.map(|line| (false, line)))
+    })
+}
+
 /// Render the theme selector overlay.
 pub fn render_theme_selector(frame: &mut Frame, app: &App) {
     let area = frame.area();
@@ -420,6 +579,207 @@ pub fn render_theme_selector(frame: &mut Frame, app: &App) {
     frame.render_widget(para, inner);
 }
 
+/// Render the worktree picker overlay.
+pub fn render_worktree_picker(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+
+    let width = 72.min(area.width.saturating_sub(4));
+    let height = (app.worktree.list.len() as u16 + 2).min(area.height.saturating_sub(4));
+
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let overlay_area = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .title(Span::styled(
+            " Worktrees ",
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::default().bg(app.theme.bg_elevated));
+
+    let inner = block.inner(overlay_area);
+    frame.render_widget(block, overlay_area);
+
+    let visible_height = inner.height as usize;
+    if visible_height == 0 {
+        return;
+    }
+
+    let scroll = app
+        .worktree
+        .selected
+        .saturating_sub(visible_height.saturating_sub(1));
+    let label_width = 24usize.min(inner.width as usize / 2);
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    for (i, wt) in app
+        .worktree
+        .list
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible_height)
+    {
+        let is_selected = i == app.worktree.selected;
+        let row_bg = if is_selected {
+            app.theme.bg_selected
+        } else {
+            app.theme.bg_elevated
+        };
+        let text_color = if is_selected {
+            app.theme.text_bright
+        } else {
+            app.theme.text_normal
+        };
+
+        let indicator = if is_selected { "▌" } else { " " };
+        let current = if wt.is_current { "*" } else { " " };
+        let label = match &wt.head {
+            Some(head) => format!("{} [{}]", wt.name, head),
+            None => wt.name.clone(),
+        };
+        let label = truncate_str(&label, label_width);
+        let path_width = (inner.width as usize).saturating_sub(label_width + 5);
+        let path = truncate_str(&wt.path.display().to_string(), path_width);
+
+        lines.push(Line::from(vec![
+            Span::styled(indicator, Style::default().fg(app.theme.accent).bg(row_bg)),
+            Span::styled(
+                format!("{} ", current),
+                Style::default().fg(app.theme.accent).bg(row_bg),
+            ),
+            Span::styled(
+                format!("{:<width$} ", label, width = label_width),
+                Style::default().fg(text_color).bg(row_bg),
+            ),
+            Span::styled(path, Style::default().fg(app.theme.text_muted).bg(row_bg)),
+        ]));
+    }
+
+    while lines.len() < visible_height {
+        lines.push(Line::from(Span::styled(
+            "",
+            Style::default().bg(app.theme.bg_elevated),
+        )));
+    }
+
+    let para = Paragraph::new(lines).style(Style::default().bg(app.theme.bg_elevated));
+    frame.render_widget(para, inner);
+}
+
+/// Render the jj evolog picker overlay.
+pub fn render_evolog_picker(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+
+    let width = 76.min(area.width.saturating_sub(4));
+    let height = (app.evolog.entries.len() as u16 + 2).min(area.height.saturating_sub(4));
+
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let overlay_area = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .title(Span::styled(
+            format!(" Evolog {} ", truncate_str(&app.evolog.change, 24)),
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::default().bg(app.theme.bg_elevated));
+
+    let inner = block.inner(overlay_area);
+    frame.render_widget(block, overlay_area);
+
+    let visible_height = inner.height as usize;
+    if visible_height == 0 {
+        return;
+    }
+
+    let scroll = app
+        .evolog
+        .selected
+        .saturating_sub(visible_height.saturating_sub(1));
+    let count = app.evolog.entries.len();
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    for (i, entry) in app
+        .evolog
+        .entries
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible_height)
+    {
+        let is_selected = i == app.evolog.selected;
+        let row_bg = if is_selected {
+            app.theme.bg_selected
+        } else {
+            app.theme.bg_elevated
+        };
+        let text_color = if is_selected {
+            app.theme.text_bright
+        } else {
+            app.theme.text_normal
+        };
+
+        let indicator = if is_selected { "▌" } else { " " };
+        let (marker, marker_color) = if i == app.evolog.from_idx {
+            ("old", app.theme.error)
+        } else if i == app.evolog.to_idx {
+            ("new", app.theme.success)
+        } else {
+            ("", app.theme.text_muted)
+        };
+        let detail = entry
+            .operation
+            .as_deref()
+            .filter(|op| !op.is_empty())
+            .unwrap_or(&entry.summary);
+        let prefix = format!(
+            " v{:<3} {} {} ",
+            count - i,
+            truncate_str(&entry.commit_id, 8),
+            entry.timestamp
+        );
+        let detail_width = (inner.width as usize).saturating_sub(prefix.chars().count() + 5);
+
+        lines.push(Line::from(vec![
+            Span::styled(indicator, Style::default().fg(app.theme.accent).bg(row_bg)),
+            Span::styled(
+                format!("{:<3}", marker),
+                Style::default().fg(marker_color).bg(row_bg),
+            ),
+            Span::styled(prefix, Style::default().fg(text_color).bg(row_bg)),
+            Span::styled(
+                truncate_str(detail, detail_width),
+                Style::default().fg(app.theme.text_muted).bg(row_bg),
+            ),
+        ]));
+    }
+
+    while lines.len() < visible_height {
+        lines.push(Line::from(Span::styled(
+            "",
+            Style::default().bg(app.theme.bg_elevated),
+        )));
+    }
+
+    let para = Paragraph::new(lines).style(Style::default().bg(app.theme.bg_elevated));
+    frame.render_widget(para, inner);
+}
+
 /// Render the help overlay.
 pub fn render_help_overlay(frame: &mut Frame, app: &App) {
     let entries = [
@@ -433,16 +793,22 @@ pub fn render_help_overlay(frame: &mut Frame, app: &App) {
         ("z", "Toggle hunks-only / full file view"),
         ("/", "Open sidebar fuzzy filter"),
         ("T", "Theme selector"),
+        ("W", "Switch git worktree"),
+        ("E", "Compare versions of a jj change (evolog)"),
         ("c / C", "Add or view comments"),
+        ("v", "Select lines for a range comment"),
+        ("X", "Export comments as a markdown report"),
         ("[", "Toggle old pane fullscreen"),
         ("]", "Toggle new pane fullscreen"),
         ("r", "Manual reload of file list/diff"),
         ("y", "Copy current path to clipboard"),
-        ("o", "Open file in $EDITOR"),
+        ("o", "Open file at cursor line in $EDITOR"),
         ("P", "Open PR picker / exit PR mode"),
         ("A", "Approve PR (in PR mode)"),
         ("R", "Request changes (in PR mode)"),
         ("O", "Open PR in browser (in PR mode)"),
+        ("S", "Submit comments as a PR review (in PR mode)"),
+        ("I", "PR checks, description and conversation"),
         ("?", "Close this help overlay"),
         ("q or Ctrl+C", "Quit quickdiff"),
     ];
@@ -541,12 +907,15 @@ pub fn render_help_overlay(frame: &mut Frame, app: &App) {
     frame.render_widget(para, inner);
 }
 
+/// Width of the review decision column in the PR picker.
+const PR_PICKER_REVIEW_WIDTH: usize = 8;
+
 /// Render the PR picker overlay.
 pub fn render_pr_picker_overlay(frame: &mut Frame, app: &mut App) {
     let area = frame.area();
 
     // Center the picker
-    let width = (area.width * 3 / 4).min(80);
+    let width = (area.width * 3 / 4).min(100);
     let height = (area.height * 3 / 4).min(30);
     let x = (area.width - width) / 2;
     let y = (area.height - height) / 2;
@@ -572,19 +941,19 @@ pub fn render_pr_picker_overlay(frame: &mut Frame, app: &mut App) {
         Span::raw(" "),
         styled_filter_tab(
             "All",
-            app.pr.filter == crate::core::PRFilter::All,
+            app.pr.query.filter == crate::core::PRFilter::All,
             &app.theme,
         ),
         Span::raw("  "),
         styled_filter_tab(
             "Mine",
-            app.pr.filter == crate::core::PRFilter::Mine,
+            app.pr.query.filter == crate::core::PRFilter::Mine,
             &app.theme,
         ),
         Span::raw("  "),
         styled_filter_tab(
             "Review Requested",
-            app.pr.filter == crate::core::PRFilter::ReviewRequested,
+            app.pr.query.filter == crate::core::PRFilter::ReviewRequested,
             &app.theme,
         ),
         Span::raw(" "),
@@ -592,15 +961,49 @@ pub fn render_pr_picker_overlay(frame: &mut Frame, app: &mut App) {
     let filter_para = Paragraph::new(filter_line);
     frame.render_widget(filter_para, Rect::new(inner.x, inner.y, inner.width, 1));
 
+    // Query: search being edited, or state, sort and the other filters
+    let query_line = if let Some(input) = &app.pr.search_input {
+        Line::from(vec![
+            Span::styled(" Search: ", Style::default().fg(app.theme.accent)),
+            Span::styled(
+                format!("{}▏", input),
+                Style::default().fg(app.theme.text_normal),
+            ),
+        ])
+    } else {
+        let query = &app.pr.query;
+        let mut text = format!(" {} · {}", query.state.label(), query.sort.label());
+        if let Some(label) = &query.label {
+            text.push_str(&format!(" · label:{}", label));
+        }
+        if let Some(author) = &query.author {
+            text.push_str(&format!(" · author:{}", author));
+        }
+        if !query.search.is_empty() {
+            text.push_str(&format!(" · \"{}\"", query.search));
+        }
+        if app.pr.loading && !app.pr.list.is_empty() {
+            text.push_str(" · loading more…");
+        }
+        Line::from(Span::styled(
+            text,
+            Style::default().fg(app.theme.text_muted),
+        ))
+    };
+    frame.render_widget(
+        Paragraph::new(query_line),
+        Rect::new(inner.x, inner.y + 1, inner.width, 1),
+    );
+
     // Loading or list
     let list_area = Rect::new(
         inner.x,
-        inner.y + 2,
+        inner.y + 3,
         inner.width,
-        inner.height.saturating_sub(4),
+        inner.height.saturating_sub(5),
     );
 
-    if app.pr.loading {
+    if app.pr.list.is_empty() && app.pr.loading {
         let loading = Paragraph::new("Loading...").style(Style::default().fg(app.theme.text_muted));
         frame.render_widget(loading, list_area);
     } else if app.pr.list.is_empty() {
@@ -621,6 +1024,8 @@ pub fn render_pr_picker_overlay(frame: &mut Frame, app: &mut App) {
 
         let start = app.pr.picker_scroll;
         let end = (start + visible_height).min(app.pr.list.len());
+        // Review decision and CI columns sit at the right edge.
+        let left_width = (list_area.width as usize).saturating_sub(PR_PICKER_REVIEW_WIDTH + 4);
 
         for (i, pr) in app.pr.list[start..end].iter().enumerate() {
             let y = list_area.y + i as u16;
@@ -631,20 +1036,51 @@ pub fn render_pr_picker_overlay(frame: &mut Frame, app: &mut App) {
             } else {
                 Style::default().fg(app.theme.text_normal)
             };
+            let column_style = |color| {
+                if is_selected {
+                    style
+                } else {
+                    Style::default().fg(color)
+                }
+            };
 
-            // Format: #123 Title (head → base) +10/-5
-            let pr_line = format!(
-                " #{:<4} {} ({} → {}) +{}/-{}",
-                pr.number,
-                truncate_str(&pr.title, 30),
-                truncate_str(&pr.head_ref_name, 15),
-                truncate_str(&pr.base_ref_name, 15),
-                pr.additions,
-                pr.deletions,
+            // Format: #123 [draft] Title (head → base) +10/-5   review  CI
+            let left = truncate_str(
+                &format!(
+                    " #{:<4} {}{} ({} → {}) +{}/-{}",
+                    pr.number,
+                    if pr.is_draft { "[draft] " } else { "" },
+                    truncate_str(&pr.title, 30),
+                    truncate_str(&pr.head_ref_name, 15),
+                    truncate_str(&pr.base_ref_name, 15),
+                    pr.additions,
+                    pr.deletions,
+                ),
+                left_width,
+            );
+            let pad = left_width.saturating_sub(left.chars().count());
+            let (review, review_color) = pr
+                .review_decision
+                .map(|d| review_decision_label(d, &app.theme))
+                .unwrap_or(("", app.theme.text_muted));
+            let (ci, ci_color) = pr
+                .checks
+                .map(|b| check_bucket_icon(b, &app.theme))
+                .unwrap_or(("", app.theme.text_muted));
+
+            let line = Line::from(vec![
+                Span::styled(left, style),
+                Span::styled(" ".repeat(pad), style),
+                Span::styled(
+                    format!(" {:<width$}", review, width = PR_PICKER_REVIEW_WIDTH),
+                    column_style(review_color),
+                ),
+                Span::styled(format!(" {:<1} ", ci), column_style(ci_color)),
+            ]);
+            frame.render_widget(
+                Paragraph::new(line),
+                Rect::new(list_area.x, y, list_area.width, 1),
             );
-
-            let line = Paragraph::new(pr_line).style(style);
-            frame.render_widget(line, Rect::new(list_area.x, y, list_area.width, 1));
         }
     }
 
@@ -654,6 +1090,12 @@ pub fn render_pr_picker_overlay(frame: &mut Frame, app: &mut App) {
         Span::raw(" navigate  "),
         Span::styled("Tab", Style::default().fg(app.theme.accent)),
         Span::raw(" filter  "),
+        Span::styled("s", Style::default().fg(app.theme.accent)),
+        Span::raw(" state  "),
+        Span::styled("o", Style::default().fg(app.theme.accent)),
+        Span::raw(" sort  "),
+        Span::styled("/", Style::default().fg(app.theme.accent)),
+        Span::raw(" search  "),
         Span::styled("Enter", Style::default().fg(app.theme.accent)),
         Span::raw(" select  "),
         Span::styled("r", Style::default().fg(app.theme.accent)),
@@ -668,6 +1110,166 @@ pub fn render_pr_picker_overlay(frame: &mut Frame, app: &mut App) {
     );
 }
 
+/// Render the PR info overlay: checks, description, labels, reviewers and
+/// the top-level conversation.
+pub fn render_pr_info_overlay(frame: &mut Frame, app: &mut App) {
+    let Some(pr) = app.pr.current.as_ref() else {
+        return;
+    };
+    let area = frame.area();
+    let width = (area.width * 4 / 5).clamp(20.min(area.width), 100);
+    let height = area.height.saturating_sub(4).max(1);
+    let x = (area.width.saturating_sub(width)) / 2;
+    let y = (area.height.saturating_sub(height)) / 2;
+    let overlay_area = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .title(Span::styled(
+            format!(" PR #{} ", pr.number),
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::default().bg(app.theme.bg_elevated));
+    let inner = block.inner(overlay_area);
+    frame.render_widget(block, overlay_area);
+    if inner.height == 0 {
+        return;
+    }
+
+    let muted = Style::default().fg(app.theme.text_muted);
+    let normal = Style::default().fg(app.theme.text_normal);
+    let heading = Style::default()
+        .fg(app.theme.accent)
+        .add_modifier(Modifier::BOLD);
+    let text_lines = |text: &str, indent: &'static str| -> Vec<Line<'static>> {
+        text.lines()
+            .map(|line| Line::from(Span::styled(format!("{}{}", indent, line), normal)))
+            .collect()
+    };
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(Span::styled(
+            pr.title.clone(),
+            Style::default()
+                .fg(app.theme.text_bright)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "{} → {} · @{} · +{}/-{}{}",
+                pr.head_ref_name,
+                pr.base_ref_name,
+                pr.author.login,
+                pr.additions,
+                pr.deletions,
+                if pr.is_draft { " · draft" } else { "" }
+            ),
+            muted,
+        )),
+    ];
+    if let Some(details) = &app.pr.details {
+        if !details.labels.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Labels: ", muted),
+                Span::styled(details.labels.join(", "), normal),
+            ]));
+        }
+        if !details.review_requests.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Review requested: ", muted),
+                Span::styled(details.review_requests.join(", "), normal),
+            ]));
+        }
+    }
+
+    lines.push(Line::default());
+    let mut checks_heading = vec![Span::styled("Checks", heading)];
+    match &app.pr.checks {
+        Some(checks) => {
+            let summary = ChecksSummary::of(checks);
+            if checks.is_empty() {
+                checks_heading.push(Span::styled("  none reported", muted));
+            } else {
+                checks_heading.push(Span::styled(
+                    format!(
+                        "  {} passed, {} failed, {} pending, {} skipped",
+                        summary.passed, summary.failed, summary.pending, summary.skipped
+                    ),
+                    muted,
+                ));
+            }
+            lines.push(Line::from(checks_heading));
+            for check in checks {
+                let (icon, color) = check_bucket_icon(check.bucket, &app.theme);
+                let mut spans = vec![
+                    Span::styled(format!("  {} ", icon), Style::default().fg(color)),
+                    Span::styled(check.name.clone(), normal),
+                ];
+                if !check.workflow.is_empty() {
+                    spans.push(Span::styled(format!("  {}", check.workflow), muted));
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+        None => {
+            checks_heading.push(Span::styled("  loading…", muted));
+            lines.push(Line::from(checks_heading));
+        }
+    }
+    if let Some(err) = &app.pr.checks_error {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", err),
+            Style::default().fg(app.theme.error),
+        )));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled("Description", heading)));
+    match (&app.pr.details, &app.pr.details_error) {
+        (Some(details), _) if !details.body.trim().is_empty() => {
+            lines.extend(text_lines(details.body.trim_end(), "  "));
+        }
+        (Some(_), _) => lines.push(Line::from(Span::styled("  No description", muted))),
+        (None, Some(err)) => lines.push(Line::from(Span::styled(
+            format!("  {}", err),
+            Style::default().fg(app.theme.error),
+        ))),
+        (None, None) => lines.push(Line::from(Span::styled("  loading…", muted))),
+    }
+
+    if let Some(details) = &app.pr.details {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            format!("Conversation ({})", details.comments.len()),
+            heading,
+        )));
+        for comment in &details.comments {
+            let date = comment.created_at.get(..10).unwrap_or(&comment.created_at);
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  @{}", comment.author.as_deref().unwrap_or("ghost")),
+                    Style::default().fg(app.theme.accent),
+                ),
+                Span::styled(format!("  {}", date), muted),
+            ]));
+            lines.extend(text_lines(comment.body.trim_end(), "    "));
+        }
+    }
+
+    let max_scroll = lines.len().saturating_sub(inner.height as usize);
+    app.pr.info_scroll = app.pr.info_scroll.min(max_scroll);
+    let para = Paragraph::new(lines)
+        .style(Style::default().bg(app.theme.bg_elevated))
+        .wrap(Wrap { trim: false })
+        .scroll((app.pr.info_scroll as u16, 0));
+    frame.render_widget(para, inner);
+}
+
 /// Render a styled filter tab.
 pub fn styled_filter_tab<'a>(label: &'a str, active: bool, theme: &Theme) -> Span<'a> {
     if active {
@@ -701,6 +1303,7 @@ pub fn render_pr_action_overlay(frame: &mut Frame, app: &App) {
         Some(PRActionType::Approve) => " Approve PR ",
         Some(PRActionType::Comment) => " Comment on PR ",
         Some(PRActionType::RequestChanges) => " Request Changes ",
+        Some(PRActionType::SubmitReview) => " Submit Review ",
         None => " PR Action ",
     };
 
@@ -724,7 +1327,33 @@ pub fn render_pr_action_overlay(frame: &mut Frame, app: &App) {
         Some(PRActionType::Comment) | Some(PRActionType::RequestChanges)
     );
 
-    if show_input {
+    if app.pr.action_type == Some(PRActionType::SubmitReview) {
+        let verdict = match app.pr.review_event {
+            ReviewEvent::Comment => "comment",
+            ReviewEvent::Approve => "approve",
+            ReviewEvent::RequestChanges => "request changes",
+        };
+        let summary = Line::from(vec![
+            Span::styled(
+                format!(
+                    "{} inline comment(s), verdict: ",
+                    app.pr.review_comment_count
+                ),
+                Style::default().fg(app.theme.text_muted),
+            ),
+            Span::styled(verdict, Style::default().fg(app.theme.accent)),
+        ]);
+        frame.render_widget(
+            Paragraph::new(summary),
+            Rect::new(inner.x, inner.y, inner.width, 1),
+        );
+        let label =
+            Paragraph::new("Summary (optional):").style(Style::default().fg(app.theme.text_muted));
+        frame.render_widget(label, Rect::new(inner.x, inner.y + 1, inner.width, 1));
+        let input_text = format!("{}_", &app.pr.action_text);
+        let input = Paragraph::new(input_text).style(Style::default().fg(app.theme.text_normal));
+        frame.render_widget(input, Rect::new(inner.x, inner.y + 2, inner.width, 2));
+    } else if show_input {
         let label =
             Paragraph::new("Message (required):").style(Style::default().fg(app.theme.text_muted));
         frame.render_widget(label, Rect::new(inner.x, inner.y, inner.width, 1));
@@ -739,12 +1368,18 @@ pub fn render_pr_action_overlay(frame: &mut Frame, app: &App) {
     }
 
     // Help line
-    let help = Line::from(vec![
+    let mut help = Line::from(vec![
         Span::styled("Enter", Style::default().fg(app.theme.accent)),
         Span::raw(" submit  "),
         Span::styled("Esc", Style::default().fg(app.theme.accent)),
         Span::raw(" cancel"),
     ]);
+    if app.pr.action_type == Some(PRActionType::SubmitReview) {
+        help.spans.push(Span::raw("  "));
+        help.spans
+            .push(Span::styled("Tab", Style::default().fg(app.theme.accent)));
+        help.spans.push(Span::raw(" verdict"));
+    }
     let help_para = Paragraph::new(help).style(Style::default().fg(app.theme.text_muted));
     frame.render_widget(
         help_para,
@@ -126,6 +126,7 @@ pub fn render_sidebar(frame: &mut Frame, app: &mut App, area: Rect) {
             FileChangeKind::Deleted => ('D', app.theme.error),
             FileChangeKind::Untracked => ('?', app.theme.text_muted),
             FileChangeKind::Renamed => ('R', app.theme.accent_dim),
+            FileChangeKind::Copied => ('C', app.theme.accent_dim),
         };
 
         let viewed_char = if is_viewed { '✓' } else { '·' };
@@ -135,8 +136,8 @@ pub fn render_sidebar(frame: &mut Frame, app: &mut App, area: Rect) {
             app.theme.text_faint
         };
 
-        // Comment counts only shown in worktree mode
-        let (comment_text, comment_color) = if app.is_worktree_mode() {
+        // Comment counts only shown where comments are available
+        let (comment_text, comment_color) = if app.comments_enabled() {
             let comment_count = app
                 .open_comment_counts
                 .get(&file.path)
@@ -185,6 +186,18 @@ pub fn render_sidebar(frame: &mut Frame, app: &mut App, area: Rect) {
             Span::styled(comment_text, Style::default().fg(comment_color).bg(row_bg)),
             Span::styled(" ", Style::default().bg(row_bg)),
             Span::styled(display_path, Style::default().fg(text_color).bg(row_bg)),
+            Span::styled(
+                file.similarity
+                    .map(|score| format!(" {}%", score))
+                    .unwrap_or_default(),
+                Style::default().fg(app.theme.text_faint).bg(row_bg),
+            ),
+            Span::styled(
+                file.mode_change_tag()
+                    .map(|tag| format!(" {}", tag))
+                    .unwrap_or_default(),
+                Style::default().fg(app.theme.accent_dim).bg(row_bg),
+            ),
         ]));
     }
 
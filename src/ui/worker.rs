@@ -4,8 +4,9 @@ use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
 use std::thread::{self, JoinHandle};
 
 use crate::core::{
-    ChangedFile, DiffResult, DiffSource, PRFilter, PullRequest, RepoRoot, TextBuffer, get_pr_diff,
-    list_prs, load_diff_contents,
+    ChangedFile, DiffResult, DiffSource, PRQuery, PrCheck, PrDetails, PrRevisions, PullRequest,
+    RepoRoot, ReviewThread, TextBuffer, fetch_pr_revisions, get_pr_checks, get_pr_details,
+    get_pr_diff, get_pr_review_threads, list_prs, load_diff_contents,
 };
 
 #[derive(Debug, Clone)]
@@ -157,16 +158,60 @@ fn compute_diff_payload(repo: &RepoRoot, req: DiffLoadRequest) -> DiffLoadRespon
 
 #[derive(Debug, Clone)]
 pub(crate) enum PrRequest {
-    List { id: u64, filter: PRFilter },
+    List { id: u64, query: PRQuery },
     LoadDiff { id: u64, pr_number: u32 },
+    Checks { id: u64, pr_number: u32 },
+    Details { id: u64, pr_number: u32 },
+}
+
+impl PrRequest {
+    /// Response reporting that this request failed.
+    fn failed(&self, message: String) -> PrResponse {
+        match *self {
+            Self::List { id, .. } => PrResponse::ListError { id, message },
+            Self::LoadDiff { id, .. } => PrResponse::DiffError { id, message },
+            Self::Checks { id, .. } => PrResponse::Checks {
+                id,
+                checks: Err(message),
+            },
+            Self::Details { id, .. } => PrResponse::Details {
+                id,
+                details: Err(message),
+            },
+        }
+    }
 }
 
 #[derive(Debug)]
 pub(crate) enum PrResponse {
-    List { id: u64, prs: Vec<PullRequest> },
-    ListError { id: u64, message: String },
-    Diff { id: u64, diff: String },
-    DiffError { id: u64, message: String },
+    List {
+        id: u64,
+        prs: Vec<PullRequest>,
+    },
+    ListError {
+        id: u64,
+        message: String,
+    },
+    Diff {
+        id: u64,
+        diff: String,
+        /// Review threads, or why they could not be fetched.
+        threads: Result<Vec<ReviewThread>, String>,
+        /// Commits to load whole files from; `None` shows the patch alone.
+        revisions: Option<PrRevisions>,
+    },
+    DiffError {
+        id: u64,
+        message: String,
+    },
+    Checks {
+        id: u64,
+        checks: Result<Vec<PrCheck>, String>,
+    },
+    Details {
+        id: u64,
+        details: Result<PrDetails, String>,
+    },
 }
 
 pub(crate) struct PrWorker {
@@ -216,11 +261,7 @@ fn pr_worker_loop(
     response_tx: Sender<PrResponse>,
 ) {
     while let Ok(req) = request_rx.recv() {
-        let (id, is_list) = match &req {
-            PrRequest::List { id, .. } => (*id, true),
-            PrRequest::LoadDiff { id, .. } => (*id, false),
-        };
-
+        let fallback = req.clone();
         let response = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
             compute_pr_response(&repo, req)
         })) {
@@ -233,11 +274,7 @@ fn pr_worker_loop(
                 } else {
                     "worker panic: unknown error".to_string()
                 };
-                if is_list {
-                    PrResponse::ListError { id, message }
-                } else {
-                    PrResponse::DiffError { id, message }
-                }
+                fallback.failed(message)
             }
         };
         if response_tx.send(response).is_err() {
@@ -248,7 +285,7 @@ fn pr_worker_loop(
 
 fn compute_pr_response(repo: &RepoRoot, req: PrRequest) -> PrResponse {
     match req {
-        PrRequest::List { id, filter } => match list_prs(repo.path(), filter) {
+        PrRequest::List { id, query } => match list_prs(repo.path(), &query) {
             Ok(prs) => PrResponse::List { id, prs },
             Err(e) => PrResponse::ListError {
                 id,
@@ -256,12 +293,30 @@ fn compute_pr_response(repo: &RepoRoot, req: PrRequest) -> PrResponse {
             },
         },
         PrRequest::LoadDiff { id, pr_number } => match get_pr_diff(repo.path(), pr_number) {
-            Ok(diff) => PrResponse::Diff { id, diff },
+            Ok(diff) => PrResponse::Diff {
+                id,
+                diff,
+                threads: get_pr_review_threads(repo.path(), pr_number).map_err(|e| e.to_string()),
+                // jj reads its own view of the repository, which the fetch
+                // does not update.
+                revisions: repo
+                    .is_git()
+                    .then(|| fetch_pr_revisions(repo.path(), pr_number).ok())
+                    .flatten(),
+            },
             Err(e) => PrResponse::DiffError {
                 id,
                 message: e.to_string(),
             },
         },
+        PrRequest::Checks { id, pr_number } => PrResponse::Checks {
+            id,
+            checks: get_pr_checks(repo.path(), pr_number).map_err(|e| e.to_string()),
+        },
+        PrRequest::Details { id, pr_number } => PrResponse::Details {
+            id,
+            details: get_pr_details(repo.path(), pr_number).map_err(|e| e.to_string()),
+        },
     }
 }
 
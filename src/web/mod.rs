@@ -9,7 +9,8 @@ use anyhow::{Context, Result};
 use serde::Serialize;
 
 use crate::core::{
-    DiffSource, FileChangeKind, RepoRoot, diff_source_display, get_pr_diff, list_changed_files,
+    CommentContext, CommentKind, CommentStore, DiffSide, DiffSource, FileChangeKind, PathFilter,
+    RelPath, RepoRoot, diff_source_display, get_pr_diff, list_changed_files, open_comment_store,
     parse_unified_diff,
 };
 
@@ -54,9 +55,14 @@ pub struct ReviewComment {
     /// End line number.
     #[serde(rename = "endLine")]
     pub end_line: usize,
-    /// Comment type (bug, warning, suggestion, good).
+    /// Side of the diff the lines refer to (`old` or `new`).
+    pub side: DiffSide,
+    /// Comment type (bug, warning, suggestion, good, or comment when unset).
     #[serde(rename = "type")]
     pub kind: String,
+    /// Who wrote the comment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
     /// Comment text.
     pub text: String,
 }
@@ -80,29 +86,48 @@ pub struct WebInput {
     pub source: DiffSource,
     /// Pre-loaded patch from stdin (if any).
     pub stdin_patch: Option<String>,
-    /// File path filter.
-    pub file_filter: Option<String>,
+    /// Pathspec filter applied to the patch (also applied to stdin patches).
+    pub file_filter: PathFilter,
     /// Display label for the diff.
     pub label: String,
 }
 
 /// Build review data from the given input.
 pub fn build_review_data(repo: &RepoRoot, input: WebInput) -> Result<ReviewData> {
+    let from_stdin = input.stdin_patch.is_some();
     let patch = if let Some(patch) = input.stdin_patch {
-        patch
+        apply_file_filter(patch, &input.file_filter)
     } else {
-        build_patch_from_source(repo, &input.source, input.file_filter.as_deref())?
+        build_patch_from_source(repo, &input.source, &input.file_filter)?
     };
 
+    // Stored comments belong to the repo's own diffs; a piped patch has none.
+    let store = if from_stdin {
+        None
+    } else {
+        open_comment_store(repo).ok()
+    };
+    let context = CommentContext::for_source(&input.source);
+    let mut stats = ReviewStats::default();
+
     let files = parse_unified_diff(&patch);
     let review_files = files
         .iter()
-        .map(|f| ReviewFile {
-            path: f.path.as_str().to_string(),
-            summary: String::new(),
-            additions: f.additions,
-            deletions: f.deletions,
-            comments: Vec::new(),
+        .map(|f| {
+            let comments = store
+                .as_ref()
+                .map(|store| review_comments(store.as_ref(), &f.path, &context))
+                .unwrap_or_default();
+            for comment in &comments {
+                stats.count(&comment.kind);
+            }
+            ReviewFile {
+                path: f.path.as_str().to_string(),
+                summary: String::new(),
+                additions: f.additions,
+                deletions: f.deletions,
+                comments,
+            }
         })
         .collect::<Vec<_>>();
 
@@ -118,14 +143,50 @@ pub fn build_review_data(repo: &RepoRoot, input: WebInput) -> Result<ReviewData>
         summary,
         patch,
         files: review_files,
-        stats: ReviewStats::default(),
+        stats,
     })
 }
 
+impl ReviewStats {
+    fn count(&mut self, kind: &str) {
+        match CommentKind::parse(kind) {
+            Some(CommentKind::Bug) => self.bugs += 1,
+            Some(CommentKind::Warning) => self.warnings += 1,
+            Some(CommentKind::Suggestion) => self.suggestions += 1,
+            Some(CommentKind::Good) => self.good += 1,
+            None => {}
+        }
+    }
+}
+
+/// Open comments on `path` for this diff, placed at the lines they were made on.
+fn review_comments(
+    store: &dyn CommentStore,
+    path: &RelPath,
+    context: &CommentContext,
+) -> Vec<ReviewComment> {
+    store
+        .list_for_path(path, false)
+        .into_iter()
+        .filter(|c| c.context.matches(context))
+        .filter_map(|c| {
+            let (side, start, end) = c.anchor.recorded_span()?;
+            Some(ReviewComment {
+                start_line: start + 1,
+                end_line: end + 1,
+                side,
+                kind: c.kind.map_or("comment", CommentKind::as_str).to_string(),
+                author: c.author.clone(),
+                text: c.message.clone(),
+            })
+        })
+        .collect()
+}
+
 fn build_patch_from_source(
     repo: &RepoRoot,
     source: &DiffSource,
-    file_filter: Option<&str>,
+    file_filter: &PathFilter,
 ) -> Result<String> {
     if repo.is_jj() {
         return build_jj_patch(repo, source, file_filter);
@@ -136,7 +197,7 @@ fn build_patch_from_source(
 fn build_git_patch(
     repo: &RepoRoot,
     source: &DiffSource,
-    file_filter: Option<&str>,
+    file_filter: &PathFilter,
 ) -> Result<String> {
     let base = repo.path();
     let mut patch = match source {
@@ -145,7 +206,9 @@ fn build_git_patch(
             let parent = crate::core::get_parent_revision(repo, commit)?;
             run_git(base, &["diff", "--no-color", &parent, commit])?
         }
-        DiffSource::Range { from, to } => run_git(base, &["diff", "--no-color", from, to])?,
+        DiffSource::Range { from, to } | DiffSource::Evolog { from, to, .. } => {
+            run_git(base, &["diff", "--no-color", from, to])?
+        }
         DiffSource::Base(base_ref) => {
             let merge_base = crate::core::resolve_merge_base(repo, base_ref)?;
             run_git(base, &["diff", "--no-color", &merge_base])?
@@ -207,13 +270,13 @@ fn run_git(repo: &Path, args: &[&str]) -> Result<String> {
 fn build_jj_patch(
     repo: &RepoRoot,
     source: &DiffSource,
-    file_filter: Option<&str>,
+    file_filter: &PathFilter,
 ) -> Result<String> {
     let base = repo.path();
     let args: Vec<&str> = match source {
         DiffSource::WorkingTree => vec!["diff", "--git"],
         DiffSource::Commit(commit) => vec!["diff", "--git", "-r", commit],
-        DiffSource::Range { from, to } => {
+        DiffSource::Range { from, to } | DiffSource::Evolog { from, to, .. } => {
             // jj range syntax
             return run_jj_range(base, from, to, file_filter);
         }
@@ -246,7 +309,7 @@ fn build_jj_patch(
     Ok(apply_file_filter(patch, file_filter))
 }
 
-fn run_jj_range(base: &Path, from: &str, to: &str, file_filter: Option<&str>) -> Result<String> {
+fn run_jj_range(base: &Path, from: &str, to: &str, file_filter: &PathFilter) -> Result<String> {
     let range = format!("{}..{}", from, to);
     let output = Command::new("jj")
         .args(["diff", "--git", "-r", &range])
@@ -263,15 +326,15 @@ fn run_jj_range(base: &Path, from: &str, to: &str, file_filter: Option<&str>) ->
     Ok(apply_file_filter(patch, file_filter))
 }
 
-fn apply_file_filter(patch: String, filter: Option<&str>) -> String {
-    let Some(filter) = filter else {
+fn apply_file_filter(patch: String, filter: &PathFilter) -> String {
+    if filter.is_empty() {
         return patch;
-    };
+    }
 
     let files = parse_unified_diff(&patch);
     let filtered: Vec<_> = files
         .into_iter()
-        .filter(|f| f.path.as_str().contains(filter))
+        .filter(|f| filter.matches(f.path.as_str()))
         .map(|f| f.patch)
         .collect();
 
@@ -309,15 +372,36 @@ diff --git a/tests/test.rs b/tests/test.rs
 -test old
 +test new
 "#;
-        let filtered = apply_file_filter(patch.to_string(), Some("src/"));
+        let filtered = apply_file_filter(patch.to_string(), &PathFilter::new(["src/"]));
         assert!(filtered.contains("src/main.rs"));
         assert!(!filtered.contains("tests/test.rs"));
     }
 
+    #[test]
+    fn apply_file_filter_honors_excludes() {
+        let patch = r#"diff --git a/src/main.rs b/src/main.rs
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1 +1 @@
+-old
++new
+diff --git a/src/snapshots/view.snap b/src/snapshots/view.snap
+--- a/src/snapshots/view.snap
++++ b/src/snapshots/view.snap
+@@ -1 +1 @@
+-old
++new
+"#;
+        let filter = PathFilter::new(["src/**", ":!**/*.snap"]);
+        let filtered = apply_file_filter(patch.to_string(), &filter);
+        assert!(filtered.contains("src/main.rs"));
+        assert!(!filtered.contains("view.snap"));
+    }
+
     #[test]
     fn apply_file_filter_none_returns_all() {
         let patch = "diff --git a/file.rs b/file.rs\n";
-        let result = apply_file_filter(patch.to_string(), None);
+        let result = apply_file_filter(patch.to_string(), &PathFilter::default());
         assert_eq!(result, patch);
     }
 }
@@ -102,3 +102,704 @@ fn test_list_changed_files_untracked() {
     assert_eq!(files[0].path.as_str(), "new.txt");
     assert_eq!(files[0].kind, quickdiff::core::FileChangeKind::Untracked);
 }
+
+#[cfg(unix)]
+#[test]
+fn test_list_changed_files_executable_bit() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = create_test_repo();
+    let path = dir.path();
+
+    let file = path.join("file.txt");
+    let mut perms = std::fs::metadata(&file).unwrap().permissions();
+    perms.set_mode(0o755);
+    std::fs::set_permissions(&file, perms).unwrap();
+
+    let repo =
+        quickdiff::core::RepoRoot::discover(path, quickdiff::core::VcsPreference::Auto).unwrap();
+    let files = quickdiff::core::list_changed_files(&repo).unwrap();
+
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].old_mode, Some(quickdiff::core::FileMode::Regular));
+    assert_eq!(
+        files[0].new_mode,
+        Some(quickdiff::core::FileMode::Executable)
+    );
+    assert_eq!(
+        files[0].mode_change_label().as_deref(),
+        Some("100644 → 100755")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_type_change_diffs_target() {
+    let dir = create_test_repo();
+    let path = dir.path();
+
+    std::fs::remove_file(path.join("file.txt")).unwrap();
+    std::os::unix::fs::symlink("target/elsewhere.txt", path.join("file.txt")).unwrap();
+
+    let repo =
+        quickdiff::core::RepoRoot::discover(path, quickdiff::core::VcsPreference::Auto).unwrap();
+    let files = quickdiff::core::list_changed_files(&repo).unwrap();
+
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].kind, quickdiff::core::FileChangeKind::Modified);
+    assert_eq!(
+        files[0].mode_change_label().as_deref(),
+        Some("file → symlink")
+    );
+
+    let content = quickdiff::core::load_working_content(&repo, &files[0].path).unwrap();
+    assert_eq!(content, b"target/elsewhere.txt");
+}
+
+/// Stage every change in the worktree (including deletions) and commit it.
+fn commit_all(path: &Path, message: &str) -> String {
+    let repo = Repository::open(path).unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.update_all(["*"].iter(), None).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    let sig = Signature::now("Test", "test@test.com").unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+        .unwrap()
+        .to_string()
+}
+
+fn write_lines(path: &Path, name: &str, lines: &[&str]) {
+    std::fs::write(path.join(name), lines.join("\n") + "\n").unwrap();
+}
+
+#[test]
+fn test_commit_rename_with_edits_reports_similarity() {
+    use quickdiff::core::{DiffSource, FileChangeKind, RenameDetection};
+
+    let dir = create_test_repo();
+    let path = dir.path();
+    write_lines(path, "old.rs", &["a", "b", "c", "d"]);
+    commit_all(path, "add old");
+
+    std::fs::remove_file(path.join("old.rs")).unwrap();
+    write_lines(path, "new.rs", &["a", "b", "c", "x"]);
+    let commit = commit_all(path, "rename");
+
+    let repo =
+        quickdiff::core::RepoRoot::discover(path, quickdiff::core::VcsPreference::Auto).unwrap();
+    let files =
+        quickdiff::core::list_commit_files(&repo, &commit, &RenameDetection::default()).unwrap();
+
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].kind, FileChangeKind::Renamed);
+    assert_eq!(files[0].old_path.as_ref().unwrap().as_str(), "old.rs");
+    assert_eq!(files[0].similarity, Some(75));
+
+    let (old, new) = quickdiff::core::load_diff_contents(
+        &repo,
+        &DiffSource::Commit(commit.clone()),
+        &files[0],
+        None,
+    )
+    .unwrap();
+    assert_eq!(old, b"a\nb\nc\nd\n");
+    assert_eq!(new, b"a\nb\nc\nx\n");
+
+    let strict = RenameDetection {
+        threshold: 90,
+        copies: true,
+    };
+    let files = quickdiff::core::list_commit_files(&repo, &commit, &strict).unwrap();
+    let kinds: Vec<_> = files.iter().map(|f| f.kind).collect();
+    assert_eq!(kinds, [FileChangeKind::Added, FileChangeKind::Deleted]);
+}
+
+#[test]
+fn test_range_detects_copies_from_modified_files() {
+    use quickdiff::core::{FileChangeKind, RenameDetection};
+
+    let dir = create_test_repo();
+    let path = dir.path();
+    write_lines(path, "orig.rs", &["one", "two", "three", "four"]);
+    let from = commit_all(path, "add orig");
+
+    write_lines(path, "orig.rs", &["one", "two", "three", "four", "five"]);
+    write_lines(path, "copy.rs", &["one", "two", "three", "four"]);
+    let to = commit_all(path, "copy");
+
+    let repo =
+        quickdiff::core::RepoRoot::discover(path, quickdiff::core::VcsPreference::Auto).unwrap();
+    let files =
+        quickdiff::core::list_changed_files_between(&repo, &from, &to, &RenameDetection::default())
+            .unwrap();
+    assert_eq!(files[0].kind, FileChangeKind::Added);
+
+    let copies = RenameDetection {
+        copies: true,
+        ..RenameDetection::default()
+    };
+    let files = quickdiff::core::list_changed_files_between(&repo, &from, &to, &copies).unwrap();
+
+    assert_eq!(files[0].path.as_str(), "copy.rs");
+    assert_eq!(files[0].kind, FileChangeKind::Copied);
+    assert_eq!(files[0].old_path.as_ref().unwrap().as_str(), "orig.rs");
+    assert_eq!(files[1].kind, FileChangeKind::Modified);
+}
+
+#[test]
+fn test_base_keeps_committed_rename_after_worktree_edit() {
+    use quickdiff::core::{FileChangeKind, RenameDetection};
+
+    let dir = create_test_repo();
+    let path = dir.path();
+    write_lines(path, "old.rs", &["a", "b", "c", "d"]);
+    let base = commit_all(path, "add old");
+
+    std::fs::remove_file(path.join("old.rs")).unwrap();
+    write_lines(path, "new.rs", &["a", "b", "c", "d"]);
+    commit_all(path, "rename");
+    write_lines(path, "new.rs", &["a", "b", "c", "d", "e"]);
+
+    let repo =
+        quickdiff::core::RepoRoot::discover(path, quickdiff::core::VcsPreference::Auto).unwrap();
+    let result = quickdiff::core::list_changed_files_from_base_with_merge_base(
+        &repo,
+        &base,
+        &RenameDetection::default(),
+    )
+    .unwrap();
+
+    assert_eq!(result.files.len(), 1);
+    assert_eq!(result.files[0].kind, FileChangeKind::Renamed);
+    assert_eq!(
+        result.files[0].old_path.as_ref().unwrap().as_str(),
+        "old.rs"
+    );
+}
+
+#[test]
+fn test_base_pairs_unstaged_move_as_rename() {
+    use quickdiff::core::{FileChangeKind, RenameDetection};
+
+    let dir = create_test_repo();
+    let path = dir.path();
+    write_lines(path, "old.rs", &["a", "b", "c", "d"]);
+    let base = commit_all(path, "add old");
+    std::fs::rename(path.join("old.rs"), path.join("new.rs")).unwrap();
+
+    let repo =
+        quickdiff::core::RepoRoot::discover(path, quickdiff::core::VcsPreference::Auto).unwrap();
+    let result = quickdiff::core::list_changed_files_from_base_with_merge_base(
+        &repo,
+        &base,
+        &RenameDetection::default(),
+    )
+    .unwrap();
+
+    assert_eq!(result.files.len(), 1);
+    assert_eq!(result.files[0].path.as_str(), "new.rs");
+    assert_eq!(result.files[0].kind, FileChangeKind::Renamed);
+    assert_eq!(
+        result.files[0].old_path.as_ref().unwrap().as_str(),
+        "old.rs"
+    );
+}
+
+#[test]
+fn test_evolog_requires_jj() {
+    let dir = create_test_repo();
+    let root = quickdiff::core::RepoRoot::discover(dir.path(), quickdiff::core::VcsPreference::Git)
+        .unwrap();
+    let err = quickdiff::core::list_evolog(&root, "@").unwrap_err();
+    assert!(matches!(err, quickdiff::core::RepoError::NotARepo("jj")));
+}
+
+#[test]
+fn test_web_review_data_includes_comment_kinds() {
+    use quickdiff::core::{
+        Anchor, CommentContext, CommentKind, CommentStore, DiffSide, DiffSource, FileCommentStore,
+        LineRangeSelectorV1, NewComment, PathFilter, RelPath, Selector,
+    };
+
+    let dir = create_test_repo();
+    std::fs::write(dir.path().join("file.txt"), "changed content\n").unwrap();
+    let repo = quickdiff::core::RepoRoot::discover(dir.path(), quickdiff::core::VcsPreference::Git)
+        .unwrap();
+
+    let mut store = FileCommentStore::open(&repo).unwrap();
+    let anchor = Anchor {
+        selectors: vec![Selector::LineRangeV1(LineRangeSelectorV1 {
+            side: DiffSide::New,
+            start_line: 0,
+            end_line: 0,
+            fingerprint_hex: String::new(),
+        })],
+    };
+    store
+        .add(NewComment {
+            path: RelPath::new("file.txt"),
+            context: CommentContext::Worktree,
+            author: Some("Reviewer <reviewer@example.com>".to_string()),
+            message: "off by one".to_string(),
+            kind: Some(CommentKind::Bug),
+            suggestion: None,
+            anchor: anchor.clone(),
+        })
+        .unwrap();
+    store
+        .add(NewComment::new(
+            RelPath::new("file.txt"),
+            CommentContext::Worktree,
+            "plain note".to_string(),
+            anchor,
+        ))
+        .unwrap();
+
+    let data = quickdiff::web::build_review_data(
+        &repo,
+        quickdiff::web::WebInput {
+            source: DiffSource::WorkingTree,
+            stdin_patch: None,
+            file_filter: PathFilter::default(),
+            label: String::new(),
+        },
+    )
+    .unwrap();
+
+    let file = data.files.iter().find(|f| f.path == "file.txt").unwrap();
+    assert_eq!(file.comments.len(), 2);
+    assert_eq!(file.comments[0].kind, "bug");
+    assert_eq!(file.comments[0].start_line, 1);
+    assert_eq!(
+        file.comments[0].author.as_deref(),
+        Some("Reviewer <reviewer@example.com>")
+    );
+    assert_eq!(file.comments[1].kind, "comment");
+    assert_eq!(file.comments[1].author, None);
+    assert_eq!(data.stats.bugs, 1);
+    assert_eq!(data.stats.warnings, 0);
+}
+
+#[test]
+fn test_comments_sync_rejects_option_like_remotes() {
+    use quickdiff::core::{
+        CommentBackend, CommentStoreError, RepoRoot, VcsPreference, pull_comments, push_comments,
+    };
+
+    let dir = create_test_repo();
+    let repo = RepoRoot::discover(dir.path(), VcsPreference::Git)
+        .unwrap()
+        .with_comment_backend(CommentBackend::GitRef);
+    let marker = dir.path().join("pwned");
+    let remote = format!("--upload-pack=touch {}", marker.display());
+
+    assert!(matches!(
+        push_comments(&repo, &remote),
+        Err(CommentStoreError::Sync(_))
+    ));
+    assert!(matches!(
+        pull_comments(&repo, &remote),
+        Err(CommentStoreError::Sync(_))
+    ));
+    assert!(!marker.exists());
+}
+
+#[test]
+fn test_comments_sync_through_git_ref_remote() {
+    use quickdiff::core::{
+        Anchor, CommentBackend, CommentContext, CommentStore, CommentStoreError,
+        GitRefCommentStore, NewComment, PullOutcome, RelPath, RepoRoot, VcsPreference,
+        pull_comments, push_comments,
+    };
+
+    let origin = create_test_repo();
+    let remote = TempDir::new().unwrap();
+    Repository::init_bare(remote.path()).unwrap();
+    let url = remote.path().to_str().unwrap();
+
+    // Two reviewers, each with a checkout that has the bare repo as `origin`.
+    let open = |dir: &TempDir| {
+        let repo = Repository::open(dir.path()).unwrap();
+        repo.remote("origin", url).unwrap();
+        RepoRoot::discover(dir.path(), VcsPreference::Git)
+            .unwrap()
+            .with_comment_backend(CommentBackend::GitRef)
+    };
+    let second = TempDir::new().unwrap();
+    Repository::clone(origin.path().to_str().unwrap(), second.path()).unwrap();
+    Repository::open(second.path())
+        .unwrap()
+        .remote_delete("origin")
+        .unwrap();
+    let alice = open(&origin);
+    let bob = open(&second);
+
+    let add = |repo: &RepoRoot, message: &str| {
+        GitRefCommentStore::open(repo)
+            .unwrap()
+            .add(NewComment::new(
+                RelPath::new("file.txt"),
+                CommentContext::Worktree,
+                message.to_string(),
+                Anchor {
+                    selectors: Vec::new(),
+                },
+            ))
+            .unwrap()
+    };
+    let messages = |repo: &RepoRoot| {
+        let store = GitRefCommentStore::open(repo).unwrap();
+        store
+            .list(true)
+            .iter()
+            .map(|c| (c.id, c.message.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        pull_comments(&bob, "origin").unwrap(),
+        PullOutcome::NoRemoteComments
+    );
+    let first = add(&alice, "rename this");
+    push_comments(&alice, "origin").unwrap();
+    assert_eq!(
+        pull_comments(&bob, "origin").unwrap(),
+        PullOutcome::FastForward
+    );
+    assert_eq!(messages(&bob), vec![(first, "rename this".to_string())]);
+
+    // Both comment at the same time; the second push has to pull first.
+    add(&alice, "from alice");
+    let mut store = GitRefCommentStore::open(&bob).unwrap();
+    store
+        .add(NewComment::new(
+            RelPath::new("file.txt"),
+            CommentContext::Worktree,
+            "from bob".to_string(),
+            Anchor {
+                selectors: Vec::new(),
+            },
+        ))
+        .unwrap();
+    store.resolve(first).unwrap();
+    push_comments(&alice, "origin").unwrap();
+    assert!(matches!(
+        push_comments(&bob, "origin"),
+        Err(CommentStoreError::Sync(_))
+    ));
+    assert_eq!(pull_comments(&bob, "origin").unwrap(), PullOutcome::Merged);
+    push_comments(&bob, "origin").unwrap();
+    assert_eq!(
+        pull_comments(&alice, "origin").unwrap(),
+        PullOutcome::FastForward
+    );
+
+    let expected = vec![
+        (1, "rename this".to_string()),
+        (2, "from alice".to_string()),
+        (3, "from bob".to_string()),
+    ];
+    assert_eq!(messages(&alice), expected);
+    assert_eq!(messages(&bob), expected);
+    let store = GitRefCommentStore::open(&alice).unwrap();
+    assert!(store.list(false).iter().all(|c| c.id != first));
+}
+
+#[test]
+fn test_comments_sync_through_remote_path() {
+    use quickdiff::core::{
+        Anchor, CommentBackend, CommentContext, CommentStore, GitRefCommentStore, NewComment,
+        PullOutcome, RelPath, RepoRoot, VcsPreference, pull_comments, push_comments,
+    };
+
+    let origin = create_test_repo();
+    let second = create_test_repo();
+    let remote = TempDir::new().unwrap();
+    Repository::init_bare(remote.path()).unwrap();
+    let path = remote.path().to_str().unwrap();
+    let open = |dir: &TempDir| {
+        RepoRoot::discover(dir.path(), VcsPreference::Git)
+            .unwrap()
+            .with_comment_backend(CommentBackend::GitRef)
+    };
+    let (alice, bob) = (open(&origin), open(&second));
+
+    assert_eq!(
+        pull_comments(&bob, path).unwrap(),
+        PullOutcome::NoRemoteComments
+    );
+    GitRefCommentStore::open(&alice)
+        .unwrap()
+        .add(NewComment::new(
+            RelPath::new("file.txt"),
+            CommentContext::Worktree,
+            "by path",
+            Anchor {
+                selectors: Vec::new(),
+            },
+        ))
+        .unwrap();
+    push_comments(&alice, path).unwrap();
+    assert_eq!(pull_comments(&bob, path).unwrap(), PullOutcome::FastForward);
+    assert_eq!(pull_comments(&bob, path).unwrap(), PullOutcome::UpToDate);
+
+    let store = GitRefCommentStore::open(&bob).unwrap();
+    let messages: Vec<_> = store.list(true).iter().map(|c| c.message.clone()).collect();
+    assert_eq!(messages, vec!["by path".to_string()]);
+}
+
+#[test]
+fn test_import_cargo_diagnostics_keeps_findings_in_diff() {
+    use quickdiff::cli::run_comments_command;
+    use quickdiff::core::{CommentKind, CommentStore, FileCommentStore, RepoRoot, VcsPreference};
+    use std::process::ExitCode;
+
+    let dir = create_test_repo();
+    let lines: Vec<String> = (1..=20).map(|n| format!("line {}", n)).collect();
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    write_lines(dir.path(), "lib.rs", &lines);
+    commit_all(dir.path(), "add lib");
+    let mut edited = lines.clone();
+    edited[2] = "line three";
+    write_lines(dir.path(), "lib.rs", &edited);
+
+    let diagnostic = |file: &str, line: usize| {
+        format!(
+            r#"{{"reason":"compiler-message","message":{{"message":"unused variable","code":{{"code":"unused_variables"}},"level":"warning","spans":[{{"file_name":"{}","line_start":{},"line_end":{},"is_primary":true}}]}}}}"#,
+            file, line, line
+        )
+    };
+    let output = [
+        diagnostic("lib.rs", 3),
+        diagnostic("lib.rs", 18),
+        diagnostic("file.txt", 1),
+    ]
+    .join("\n");
+    let input = dir.path().join("diagnostics.json");
+    std::fs::write(&input, output).unwrap();
+
+    let repo = RepoRoot::discover(dir.path(), VcsPreference::Git).unwrap();
+    let args = vec![
+        "import".to_string(),
+        "--cargo-json".to_string(),
+        input.to_str().unwrap().to_string(),
+    ];
+    assert_eq!(run_comments_command(&repo, &args), ExitCode::SUCCESS);
+
+    let store = FileCommentStore::open(&repo).unwrap();
+    let comments = store.list(true);
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0].path.as_str(), "lib.rs");
+    assert_eq!(comments[0].message, "unused_variables: unused variable");
+    assert_eq!(comments[0].kind, Some(CommentKind::Warning));
+
+    // Importing the same output again only reports duplicates.
+    assert_eq!(run_comments_command(&repo, &args), ExitCode::from(2));
+    assert_eq!(FileCommentStore::open(&repo).unwrap().list(true).len(), 1);
+}
+
+#[test]
+fn test_comments_record_author() {
+    use quickdiff::cli::run_comments_command;
+    use quickdiff::core::{AUTHOR_ENV, CommentStore, FileCommentStore, RepoRoot, VcsPreference};
+    use std::process::ExitCode;
+
+    let dir = create_test_repo();
+    std::fs::write(dir.path().join("file.txt"), "changed content\n").unwrap();
+    let repo = RepoRoot::discover(dir.path(), VcsPreference::Git).unwrap();
+    let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    let add = args(&[
+        "add",
+        "--path",
+        "file.txt",
+        "--hunk",
+        "1",
+        "--message",
+        "from the bot",
+        "--author",
+        "review-bot",
+    ]);
+    assert_eq!(run_comments_command(&repo, &add), ExitCode::SUCCESS);
+    let reply = args(&["reply", "1", "--message", "thanks"]);
+    assert_eq!(run_comments_command(&repo, &reply), ExitCode::SUCCESS);
+
+    let store = FileCommentStore::open(&repo).unwrap();
+    let comment = store.list(true)[0];
+    assert_eq!(comment.author.as_deref(), Some("review-bot"));
+    // Without --author, replies fall back to the git identity.
+    if std::env::var_os(AUTHOR_ENV).is_none() {
+        assert_eq!(
+            comment.replies[0].author.as_deref(),
+            Some("Test <test@test.com>")
+        );
+    }
+
+    let list = args(&["list", "--author", "BOT"]);
+    assert_eq!(run_comments_command(&repo, &list), ExitCode::SUCCESS);
+    let empty = args(&[
+        "add", "--path", "file.txt", "--hunk", "1", "-m", "x", "--author", " ",
+    ]);
+    assert_eq!(run_comments_command(&repo, &empty), ExitCode::from(1));
+}
+
+#[test]
+fn test_comments_add_rejects_lines_outside_hunks() {
+    use quickdiff::cli::run_comments_command;
+    use quickdiff::core::{CommentStore, FileCommentStore, RepoRoot, VcsPreference};
+    use std::process::ExitCode;
+
+    let dir = create_test_repo();
+    let lines: Vec<String> = (1..=20).map(|n| format!("line {n}")).collect();
+    let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+    write_lines(dir.path(), "lib.rs", &refs);
+    commit_all(dir.path(), "add lib");
+    let mut edited = refs.clone();
+    edited[1] = "line two";
+    write_lines(dir.path(), "lib.rs", &edited);
+    let repo = RepoRoot::discover(dir.path(), VcsPreference::Git).unwrap();
+    let add = |span: &str| {
+        let args: Vec<String> = ["add", "--path", "lib.rs", "--new-line", span, "-m", "x"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        run_comments_command(&repo, &args)
+    };
+
+    // Line 18 is unchanged and far from the only hunk.
+    assert_eq!(add("18"), ExitCode::from(1));
+    assert_eq!(add("15-18"), ExitCode::from(1));
+    let store = FileCommentStore::open(&repo).unwrap();
+    assert!(store.list(true).is_empty());
+
+    // A range overlapping the hunk is accepted.
+    assert_eq!(add("2-9"), ExitCode::SUCCESS);
+}
+
+#[test]
+fn test_comments_export_markdown_report() {
+    use quickdiff::cli::run_comments_command;
+    use quickdiff::core::{RepoRoot, VcsPreference};
+    use std::process::ExitCode;
+
+    let dir = create_test_repo();
+    write_lines(
+        dir.path(),
+        "lib.rs",
+        &["fn parse() -> u32 {", "    let n = 1;", "    n", "}"],
+    );
+    commit_all(dir.path(), "add lib");
+    write_lines(
+        dir.path(),
+        "lib.rs",
+        &["fn parse() -> u32 {", "    let n = 2;", "    n", "}"],
+    );
+    let repo = RepoRoot::discover(dir.path(), VcsPreference::Git).unwrap();
+    let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    let add = args(&[
+        "add",
+        "--path",
+        "lib.rs",
+        "--new-line",
+        "2",
+        "-m",
+        "why two?",
+        "--kind",
+        "bug",
+        "--author",
+        "Rev <rev@example.com>",
+    ]);
+    assert_eq!(run_comments_command(&repo, &add), ExitCode::SUCCESS);
+
+    let out = dir.path().join("review.md");
+    let export = args(&["export", "--output", out.to_str().unwrap()]);
+    assert_eq!(run_comments_command(&repo, &export), ExitCode::SUCCESS);
+    let report = std::fs::read_to_string(&out).unwrap();
+    assert!(report.starts_with("# Review: "), "{report}");
+    assert!(report.contains("0 of 1 files viewed (1 unviewed), 1 open comment."));
+    assert!(report.contains("## `lib.rs`"));
+    assert!(
+        report.contains("**#1** [bug] new L2 in `fn parse` · Rev"),
+        "{report}"
+    );
+    assert!(
+        report.contains("-    let n = 1;\n+    let n = 2;"),
+        "{report}"
+    );
+
+    let text = args(&["export", "--format", "text", "-o", out.to_str().unwrap()]);
+    assert_eq!(run_comments_command(&repo, &text), ExitCode::SUCCESS);
+    let report = std::fs::read_to_string(&out).unwrap();
+    assert!(report.contains("\nlib.rs\n  #1 [bug] new L2 in fn parse (Rev)\n"));
+
+    let bad = args(&["export", "--format", "html"]);
+    assert_eq!(run_comments_command(&repo, &bad), ExitCode::from(1));
+}
+
+#[test]
+fn test_serve_answers_requests_and_notifies_on_change() {
+    use quickdiff::cli::serve;
+    use quickdiff::core::{RepoRoot, VcsPreference};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let dir = create_test_repo();
+    std::fs::write(dir.path().join("file.txt"), "changed content\n").unwrap();
+    let repo = RepoRoot::discover(dir.path(), VcsPreference::Git).unwrap();
+
+    let (input, mut client_out) = std::io::pipe().unwrap();
+    let (client_in, output) = std::io::pipe().unwrap();
+    let server = std::thread::spawn(move || serve(&repo, BufReader::new(input), output));
+
+    // Forward each framed message from the server to the test.
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(client_in);
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header).unwrap_or(0) == 0 {
+                break;
+            }
+            let len: usize = header
+                .trim()
+                .strip_prefix("Content-Length: ")
+                .unwrap()
+                .parse()
+                .unwrap();
+            reader.read_line(&mut String::new()).unwrap();
+            let mut body = vec![0; len];
+            reader.read_exact(&mut body).unwrap();
+            let message: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            if tx.send(message).is_err() {
+                break;
+            }
+        }
+    });
+    let mut send = |message: serde_json::Value| {
+        let body = message.to_string();
+        write!(client_out, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        client_out.flush().unwrap();
+    };
+
+    send(serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "files/list"}));
+    let response = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(response["id"], 1);
+    assert_eq!(response["result"][0]["path"], "file.txt");
+
+    std::fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+    let notified = (0..50).any(|_| {
+        rx.recv_timeout(Duration::from_millis(200))
+            .is_ok_and(|m| m["method"] == "quickdiff/didChangeWorktree")
+    });
+    assert!(notified, "expected a worktree change notification");
+
+    send(serde_json::json!({"jsonrpc": "2.0", "method": "exit"}));
+    server.join().unwrap().unwrap();
+}
@@ -1,5 +1,9 @@
 use git2::{IndexAddOption, Repository, Signature};
-use quickdiff::core::{DiffSource, RepoRoot, VcsPreference, ViewPreferences};
+use quickdiff::core::{
+    Anchor, AnchorStatus, CheckBucket, ChecksSummary, CommentContext, CommentKind, CommentStore,
+    DiffSide, DiffSource, FileCommentStore, NewComment, PathFilter, RelPath, RenameDetection,
+    RepoRoot, ReviewDecision, VcsPreference, ViewPreferences,
+};
 use quickdiff::ui::{App, DiffPaneMode, Focus, Mode};
 use std::fs;
 use std::path::Path;
@@ -99,7 +103,14 @@ impl RepoHarness {
             theme: "default".to_string(),
             ..Default::default()
         };
-        App::new(self.repo.clone(), source, None, prefs).unwrap()
+        App::new(
+            self.repo.clone(),
+            source,
+            PathFilter::default(),
+            RenameDetection::default(),
+            prefs,
+        )
+        .unwrap()
     }
 }
 
@@ -160,11 +171,52 @@ impl GhFixture {
         std::fs::write(path, content).unwrap();
     }
 
+    fn write_pr_list_page(&self, limit: usize, value: serde_json::Value) {
+        let path = self.dir.path().join(format!("pr_list_{}.json", limit));
+        std::fs::write(path, value.to_string()).unwrap();
+    }
+
+    /// Arguments of each `gh pr list` call, oldest first.
+    fn pr_list_args(&self) -> Vec<Vec<String>> {
+        let content =
+            std::fs::read_to_string(self.dir.path().join("pr_list_args")).unwrap_or_default();
+        content
+            .lines()
+            .map(|line| {
+                line.split_terminator('|')
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
     fn write_diff(&self, number: u32, patch: &str) {
         let path = self.dir.path().join(format!("diff_{}.patch", number));
         std::fs::write(path, patch).unwrap();
     }
 
+    fn write_review_threads(&self, number: u32, threads: serde_json::Value) {
+        let path = self.dir.path().join(format!("threads_{}.json", number));
+        let value =
+            json!({"data": {"repository": {"pullRequest": {"reviewThreads": {"nodes": threads}}}}});
+        std::fs::write(path, value.to_string()).unwrap();
+    }
+
+    fn write_pr_view(&self, number: u32, value: serde_json::Value) {
+        let path = self.dir.path().join(format!("view_{}.json", number));
+        std::fs::write(path, value.to_string()).unwrap();
+    }
+
+    fn write_pr_details(&self, number: u32, value: serde_json::Value) {
+        let path = self.dir.path().join(format!("details_{}.json", number));
+        std::fs::write(path, value.to_string()).unwrap();
+    }
+
+    fn write_checks(&self, number: u32, value: serde_json::Value) {
+        let path = self.dir.path().join(format!("checks_{}.json", number));
+        std::fs::write(path, value.to_string()).unwrap();
+    }
+
     fn log_lines(&self) -> Vec<String> {
         let content = std::fs::read_to_string(&self.log_path).unwrap_or_default();
         content
@@ -214,7 +266,15 @@ case "$CMD" in
     shift || true
     case "$ACTION" in
       list)
-        cat "$BASE/pr_list.json"
+        printf '%s|' "$@" >> "$BASE/pr_list_args"
+        echo >> "$BASE/pr_list_args"
+        # Pages beyond the first come from pr_list_<limit>.json when present.
+        LIMIT=$(printf '%s\n' "$@" | sed -n '/^--limit$/{n;p;}')
+        if [[ -f "$BASE/pr_list_${LIMIT}.json" ]]; then
+          cat "$BASE/pr_list_${LIMIT}.json"
+        else
+          cat "$BASE/pr_list.json"
+        fi
         exit 0
         ;;
       diff)
@@ -222,6 +282,16 @@ case "$CMD" in
         cat "$BASE/diff_${PRNUM}.patch"
         exit 0
         ;;
+      checks)
+        PRNUM="${1:-}"
+        if [[ -f "$BASE/checks_${PRNUM}.json" ]]; then
+          cat "$BASE/checks_${PRNUM}.json"
+          # Like gh, exit non-zero while checks fail or are pending.
+          exit 8
+        fi
+        echo "no checks reported on the 'feature' branch" >&2
+        exit 1
+        ;;
       review)
         PRNUM="${1:-}"
         shift || true
@@ -230,11 +300,38 @@ case "$CMD" in
         ;;
       view)
         PRNUM="${1:-}"
+        if [[ " $* " == *" --json "* ]]; then
+          # Commit lookups ask for headRefOid; everything else is the PR details.
+          FILE="$BASE/details_${PRNUM}.json"
+          if [[ " $* " == *headRefOid* ]]; then
+            FILE="$BASE/view_${PRNUM}.json"
+          fi
+          if [[ -f "$FILE" ]]; then
+            cat "$FILE"
+            exit 0
+          fi
+          echo "no PR data available" >&2
+          exit 1
+        fi
         echo "view ${PRNUM}" >> "$LOG"
         exit 0
         ;;
     esac
     ;;
+  api)
+    if [[ "${1:-}" == "graphql" ]]; then
+      PRNUM=$(printf '%s\n' "$@" | sed -n 's/^number=//p')
+      if [[ -f "$BASE/threads_${PRNUM}.json" ]]; then
+        cat "$BASE/threads_${PRNUM}.json"
+      else
+        echo '{"data":{"repository":{"pullRequest":{"reviewThreads":{"nodes":[]}}}}}'
+      fi
+      exit 0
+    fi
+    echo "api $*" >> "$LOG"
+    cat > "$BASE/api_input.json"
+    exit 0
+    ;;
 esac
 
 echo "unexpected gh invocation: cmd=${CMD} args=$*" >> "$LOG"
@@ -308,6 +405,8 @@ fn wait_for_pr_loaded(app: &mut App) {
     let start = Instant::now();
     while start.elapsed() < Duration::from_secs(5) {
         app.poll_pr_worker();
+        // Whole PR files load through the diff worker.
+        app.poll_worker();
         if !app.pr.loading && app.pr.active && !app.files.is_empty() && app.diff.is_some() {
             return;
         }
@@ -316,6 +415,19 @@ fn wait_for_pr_loaded(app: &mut App) {
     panic!("pr diff timed out");
 }
 
+#[cfg(unix)]
+fn wait_for_pr_info(app: &mut App) {
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(5) {
+        app.poll_pr_worker();
+        if app.pr.checks.is_some() && app.pr.details.is_some() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    panic!("pr info timed out");
+}
+
 fn select_file(app: &mut App, path: &str) {
     let idx = app
         .files
@@ -366,6 +478,26 @@ fn sidebar_filter_limits_visible_files() {
     assert!(app.sidebar.filtered_indices.is_empty());
 }
 
+#[test]
+fn pathspec_filter_limits_file_list() {
+    let harness = RepoHarness::new();
+    let prefs = ViewPreferences {
+        theme: "default".to_string(),
+        ..Default::default()
+    };
+    let filter = PathFilter::new(["**/*.rs", "docs", ":!src/lib.rs"]);
+    let app = App::new(
+        harness.repo.clone(),
+        DiffSource::WorkingTree,
+        filter,
+        RenameDetection::default(),
+        prefs,
+    )
+    .unwrap();
+    let paths: Vec<_> = app.files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(paths, vec![FILE_NOTES]);
+}
+
 #[test]
 fn apply_filter_reselects_first_match() {
     let harness = RepoHarness::new();
@@ -408,6 +540,171 @@ fn comment_flow_updates_counts() {
     assert!(!app.open_comment_counts.contains_key(&path));
 }
 
+#[test]
+fn reply_threads_under_comment() {
+    let harness = RepoHarness::new();
+    let mut app = harness.app();
+    select_file(&mut app, FILE_RUST);
+    app.start_add_comment();
+    app.comments.draft = "why 42?".into();
+    app.save_comment();
+
+    app.show_comments();
+    app.comments_start_reply();
+    assert_eq!(app.ui.mode, Mode::AddComment);
+    app.comments.draft = "it's the answer".into();
+    app.save_comment();
+    assert_eq!(app.ui.mode, Mode::ViewComments);
+    assert!(app.comments.reply_to.is_none());
+    assert_eq!(app.comments.viewing[0].replies.len(), 1);
+    assert_eq!(
+        app.comments.viewing[0].replies[0].message,
+        "it's the answer"
+    );
+
+    app.comments_resolve_selected();
+    let path = app.selected_file().unwrap().path.clone();
+    assert!(!app.open_comment_counts.contains_key(&path));
+}
+
+#[test]
+fn edit_reopen_and_delete_from_overlay() {
+    let harness = RepoHarness::new();
+    let mut app = harness.app();
+    select_file(&mut app, FILE_RUST);
+    app.start_add_comment();
+    app.comments.draft = "tpyo".into();
+    app.save_comment();
+    let path = app.selected_file().unwrap().path.clone();
+
+    app.show_comments();
+    app.comments_start_edit();
+    assert_eq!(app.comments.draft, "tpyo");
+    app.comments.draft = "typo".into();
+    app.save_comment();
+    assert_eq!(app.ui.mode, Mode::ViewComments);
+    assert_eq!(app.comments.viewing[0].message, "typo");
+    assert!(app.comments.viewing[0].edited);
+
+    app.comments_toggle_resolved_selected();
+    assert!(!app.open_comment_counts.contains_key(&path));
+    app.comments_toggle_include_resolved();
+    app.comments_toggle_resolved_selected();
+    assert_eq!(app.open_comment_counts.get(&path).copied(), Some(1));
+
+    app.comments_delete_selected();
+    assert_eq!(app.comments.viewing.len(), 1);
+    app.comments_delete_selected();
+    assert!(app.comments.viewing.is_empty());
+    assert!(!app.open_comment_counts.contains_key(&path));
+}
+
+#[test]
+fn comment_kind_is_cycled_in_editor_and_saved() {
+    let harness = RepoHarness::new();
+    let mut app = harness.app();
+    select_file(&mut app, FILE_RUST);
+    app.start_add_comment();
+    app.comment_cycle_kind();
+    app.comment_cycle_kind();
+    assert_eq!(app.comments.draft_kind, Some(CommentKind::Warning));
+    app.comments.draft = "careful".into();
+    app.save_comment();
+
+    let hunk = app.comment_index.by_hunk.keys().next().copied();
+    assert_eq!(
+        app.comment_index.severity_at(hunk, DiffSide::New, None),
+        Some(CommentKind::Warning)
+    );
+
+    app.show_comments();
+    assert_eq!(app.comments.viewing[0].kind, Some(CommentKind::Warning));
+    app.comments_start_edit();
+    app.comment_cycle_kind();
+    app.save_comment();
+    assert_eq!(app.comments.viewing[0].kind, Some(CommentKind::Suggestion));
+}
+
+#[test]
+fn suggestion_is_shown_and_applied() {
+    let harness = RepoHarness::new();
+    let mut app = harness.app();
+    select_file(&mut app, FILE_RUST);
+    app.start_add_comment();
+    app.comment_insert_suggestion();
+    assert_eq!(
+        app.comments.draft,
+        "```suggestion\npub fn meaning() -> i32 {\n    42\n}\n```"
+    );
+    // The cursor lands at the end of the last prefilled line, ready for edits.
+    app.comment_insert_char(';');
+    app.comments.draft = app.comments.draft.replace("42", "43");
+    app.save_comment();
+
+    app.show_comments();
+    let item = &app.comments.viewing[0];
+    assert_eq!(item.message, "Suggested change");
+    let (original, replacement) = item.suggestion.clone().unwrap();
+    assert_eq!(original[1], "    42");
+    assert_eq!(replacement, "pub fn meaning() -> i32 {\n    43\n};");
+
+    app.comments_apply_suggestion_selected();
+    assert_eq!(app.ui.mode, Mode::Normal);
+    let content = fs::read_to_string(harness.repo.path().join(FILE_RUST)).unwrap();
+    assert_eq!(content, "pub fn meaning() -> i32 {\n    43\n};\n");
+    let path = app.selected_file().unwrap().path.clone();
+    assert!(!app.open_comment_counts.contains_key(&path));
+}
+
+#[test]
+fn line_selection_creates_range_comment() {
+    let harness = RepoHarness::new();
+    let mut app = harness.app();
+    select_file(&mut app, FILE_NOTES);
+    app.set_focus(Focus::Diff);
+    app.toggle_line_selection();
+    app.scroll_diff(1, 0);
+    let (first, last) = app.selected_rows().unwrap();
+    assert_eq!(last, first + 1);
+    assert!(app.selected_line_range().is_some());
+
+    app.start_add_comment();
+    assert_eq!(app.ui.mode, Mode::AddComment);
+    app.comments.draft = "these two lines".into();
+    app.save_comment();
+    assert!(app.viewer.selection_anchor.is_none());
+    assert_eq!(app.comment_index.line_ranges.len(), 1);
+
+    app.show_comments();
+    let item = &app.comments.viewing[0];
+    assert!(item.anchor_summary.starts_with("new L"));
+    assert_eq!(item.anchor_status, AnchorStatus::Anchored);
+}
+
+#[test]
+fn comments_follow_code_that_moved() {
+    let harness = RepoHarness::new();
+    let mut app = harness.app();
+    select_file(&mut app, FILE_RUST);
+    app.start_add_comment();
+    app.comments.draft = "check the constant".into();
+    app.save_comment();
+
+    fs::write(
+        harness.repo.path().join(FILE_RUST),
+        "// header\n\npub fn meaning() -> i32 {\n    42\n}\n",
+    )
+    .unwrap();
+    app.diff = None;
+    select_file(&mut app, FILE_RUST);
+
+    app.show_comments();
+    let item = &app.comments.viewing[0];
+    assert_eq!(item.anchor_status, AnchorStatus::Relocated);
+    assert!(item.confidence.is_some());
+    assert!(item.hunk_start_row.is_some());
+}
+
 #[test]
 fn comment_draft_editor_preserves_utf8_and_newlines() {
     let harness = RepoHarness::new();
@@ -521,6 +818,76 @@ fn manual_reload_sets_status_for_commit_mode() {
     assert_eq!(app.ui.status.as_deref(), Some("Reloaded current diff"));
 }
 
+#[test]
+fn comments_written_elsewhere_show_up_live() {
+    let harness = RepoHarness::new();
+    let mut app = harness.app();
+    wait_for_diff(&mut app);
+    select_file(&mut app, FILE_RUST);
+    let path = app.selected_file().unwrap().path.clone();
+
+    // Another process (an agent running `comments import`, say) adds a comment.
+    let mut store = FileCommentStore::open(&harness.repo).unwrap();
+    store
+        .add(NewComment::new(
+            path.clone(),
+            CommentContext::Worktree,
+            "from outside".to_string(),
+            Anchor {
+                selectors: Vec::new(),
+            },
+        ))
+        .unwrap();
+
+    let start = Instant::now();
+    while !app.open_comment_counts.contains_key(&path) {
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "comment file change not picked up"
+        );
+        app.poll_comments_watcher();
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    assert_eq!(app.open_comment_counts.get(&path).copied(), Some(1));
+
+    // Comments saved from the TUI keep the externally added one.
+    app.start_add_comment();
+    app.comments.draft = "from the tui".into();
+    app.save_comment();
+    assert_eq!(app.open_comment_counts.get(&path).copied(), Some(2));
+}
+
+#[test]
+fn comments_work_in_commit_mode_and_stay_scoped() {
+    let harness = RepoHarness::new();
+    let repo = Repository::open(harness.repo.path()).unwrap();
+    let head = repo.head().unwrap().target().unwrap().to_string();
+    let mut app = harness.app_with_source(DiffSource::Commit(head.clone()));
+    wait_for_diff(&mut app);
+    assert!(app.comments_enabled());
+    select_file(&mut app, FILE_RUST);
+    app.start_add_comment();
+    assert_eq!(app.ui.mode, Mode::AddComment);
+    app.comments.draft = "why 41?".into();
+    app.save_comment();
+
+    let path = app.selected_file().unwrap().path.clone();
+    assert_eq!(app.open_comment_counts.get(&path).copied(), Some(1));
+    assert!(!app.commented_hunks.is_empty());
+    app.show_comments();
+    assert_eq!(app.comments.viewing.len(), 1);
+
+    let store = FileCommentStore::open(&harness.repo).unwrap();
+    assert_eq!(
+        store.list(false)[0].context,
+        CommentContext::Commit { commit: head }
+    );
+
+    // The worktree view does not pick up comments made on the commit.
+    let worktree = harness.app();
+    assert!(worktree.open_comment_counts.is_empty());
+}
+
 #[cfg(unix)]
 #[test]
 fn pr_picker_loads_pr_and_diff() {
@@ -569,6 +936,108 @@ fn pr_picker_loads_pr_and_diff() {
     assert_eq!(app.files.len(), 1);
     assert_eq!(app.files[0].path.as_str(), "src/lib.rs");
     assert!(app.diff.is_some());
+
+    assert_eq!(
+        app.comment_context,
+        CommentContext::PullRequest { number: 7 }
+    );
+    app.start_add_comment();
+    app.comments.draft = "looks right".into();
+    app.save_comment();
+    let path = app.files[0].path.clone();
+    assert_eq!(app.open_comment_counts.get(&path).copied(), Some(1));
+    assert!(!app.commented_hunks.is_empty());
+
+    app.exit_pr_mode();
+    assert_eq!(app.comment_context, CommentContext::Worktree);
+    assert!(!app.open_comment_counts.contains_key(&path));
+}
+
+#[cfg(unix)]
+fn pr_list_entries(numbers: std::ops::RangeInclusive<u32>) -> serde_json::Value {
+    numbers
+        .map(|n| {
+            json!({
+                "number": n,
+                "title": format!("Change {}", n),
+                "headRefName": format!("feature/{}", n),
+                "baseRefName": "main",
+                "author": {"login": "alice"},
+                "additions": 1,
+                "deletions": 0,
+                "changedFiles": 1,
+                "isDraft": false
+            })
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn has_arg_pair(args: &[String], flag: &str, value: &str) -> bool {
+    args.windows(2).any(|w| w[0] == flag && w[1] == value)
+}
+
+#[cfg(unix)]
+#[test]
+fn pr_picker_filters_searches_and_pages() {
+    let harness = RepoHarness::new();
+    let gh = GhFixture::new();
+    let mut first_page = pr_list_entries(1..=30);
+    first_page[0]["isDraft"] = json!(true);
+    first_page[0]["reviewDecision"] = json!("CHANGES_REQUESTED");
+    first_page[0]["statusCheckRollup"] = json!([
+        {"__typename": "CheckRun", "status": "COMPLETED", "conclusion": "SUCCESS"}
+    ]);
+    gh.write_pr_list(first_page);
+    gh.write_pr_list_page(60, pr_list_entries(1..=31));
+
+    let mut app = harness.app();
+    app.open_pr_picker();
+    wait_for_pr_list(&mut app);
+    assert_eq!(app.pr.list.len(), 30);
+    assert!(app.pr.has_more);
+    // Drafts are listed, with their review decision and CI state.
+    assert!(app.pr.list[0].is_draft);
+    assert_eq!(
+        app.pr.list[0].review_decision,
+        Some(ReviewDecision::ChangesRequested)
+    );
+    assert_eq!(app.pr.list[0].checks, Some(CheckBucket::Pass));
+    let args = gh.pr_list_args();
+    assert!(has_arg_pair(&args[0], "--state", "open"));
+    assert!(has_arg_pair(&args[0], "--limit", "30"));
+
+    // Reaching the last PR fetches the next page and keeps the selection.
+    for _ in 0..29 {
+        app.pr_picker_next();
+    }
+    wait_for_pr_list(&mut app);
+    assert_eq!(app.pr.list.len(), 31);
+    assert!(!app.pr.has_more);
+    assert_eq!(app.pr.picker_selected, 29);
+    assert!(has_arg_pair(&gh.pr_list_args()[1], "--limit", "60"));
+
+    // Label and author words become filters; the rest is searched.
+    app.pr_picker_start_search();
+    app.pr.search_input = Some("label:bug author:bob parser".to_string());
+    app.pr_picker_apply_search();
+    wait_for_pr_list(&mut app);
+    assert_eq!(app.pr.picker_selected, 0);
+    let args = gh.pr_list_args();
+    let search = args.last().unwrap();
+    assert!(has_arg_pair(search, "--limit", "30"));
+    assert!(has_arg_pair(search, "--label", "bug"));
+    assert!(has_arg_pair(search, "--author", "bob"));
+    assert!(has_arg_pair(search, "--search", "parser sort:created-desc"));
+
+    app.pr_picker_next_state();
+    app.pr_picker_next_state();
+    app.pr_picker_next_sort();
+    wait_for_pr_list(&mut app);
+    let args = gh.pr_list_args();
+    let merged = args.last().unwrap();
+    assert!(has_arg_pair(merged, "--state", "merged"));
+    assert!(has_arg_pair(merged, "--search", "parser sort:created-asc"));
 }
 
 #[cfg(unix)]
@@ -635,6 +1104,381 @@ fn pr_actions_issue_commands() {
     );
 }
 
+#[cfg(unix)]
+#[test]
+fn pr_submit_review_posts_local_comments_inline() {
+    let harness = RepoHarness::new();
+    let gh = GhFixture::new();
+    gh.write_pr_list(json!([
+        {
+            "number": 11,
+            "title": "Inline review",
+            "headRefName": "feature/inline",
+            "baseRefName": "main",
+            "author": {"login": "carol"},
+            "additions": 1,
+            "deletions": 1,
+            "changedFiles": 1,
+            "isDraft": false
+        }
+    ]));
+    gh.write_diff(
+        11,
+        r#"diff --git a/docs/notes.md b/docs/notes.md
+--- a/docs/notes.md
++++ b/docs/notes.md
+@@ -4,3 +4,3 @@ intro
+ # Notes
+-Original
++Updated body
+ end
+"#,
+    );
+
+    let mut app = harness.app();
+    app.open_pr_picker();
+    wait_for_pr_list(&mut app);
+    app.pr_picker_select();
+    wait_for_pr_loaded(&mut app);
+
+    app.start_add_comment();
+    app.comments.draft = "tighten wording".into();
+    app.save_comment();
+    // A comment on a file outside the PR diff cannot be placed inline.
+    let mut store = FileCommentStore::open(&harness.repo).unwrap();
+    store
+        .add(NewComment::new(
+            RelPath::new("README.md"),
+            CommentContext::PullRequest { number: 11 },
+            "elsewhere".to_string(),
+            Anchor {
+                selectors: Vec::new(),
+            },
+        ))
+        .unwrap();
+
+    app.start_pr_submit_review();
+    assert_eq!(app.ui.mode, Mode::PRAction);
+    assert_eq!(app.pr.review_comment_count, 2);
+    app.cycle_pr_review_event();
+    app.pr.action_text = "overall fine".into();
+    app.submit_pr_action();
+
+    assert_eq!(app.ui.mode, Mode::Normal, "{:?}", app.ui.error);
+    let status = app.ui.status.clone().unwrap_or_default();
+    assert!(status.contains("with 1 inline comment(s)"), "{status}");
+    assert!(
+        status.contains("#2 README.md (file is not part of the PR)"),
+        "{status}"
+    );
+    assert_eq!(
+        gh.log_lines(),
+        vec!["api --method POST repos/{owner}/{repo}/pulls/11/reviews --input -"]
+    );
+
+    let sent: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(gh.dir.path().join("api_input.json")).unwrap())
+            .unwrap();
+    assert_eq!(sent["event"], "APPROVE");
+    assert_eq!(sent["body"], "overall fine");
+    let comments = sent["comments"].as_array().unwrap();
+    assert_eq!(comments.len(), 1);
+    assert_eq!(comments[0]["path"], "docs/notes.md");
+    assert_eq!(comments[0]["body"], "tighten wording");
+    assert_eq!(comments[0]["side"], "RIGHT");
+    // A hunk comment spans the hunk's lines in the file.
+    assert_eq!(comments[0]["start_line"], 4);
+    assert_eq!(comments[0]["line"], 6);
+}
+
+#[cfg(unix)]
+#[test]
+fn pr_review_threads_show_inline_and_read_only() {
+    let harness = RepoHarness::new();
+    let gh = GhFixture::new();
+    gh.write_pr_list(json!([
+        {
+            "number": 12,
+            "title": "Threads",
+            "headRefName": "feature/threads",
+            "baseRefName": "main",
+            "author": {"login": "dave"},
+            "additions": 1,
+            "deletions": 1,
+            "changedFiles": 1,
+            "isDraft": false
+        }
+    ]));
+    gh.write_diff(
+        12,
+        r#"diff --git a/docs/notes.md b/docs/notes.md
+--- a/docs/notes.md
++++ b/docs/notes.md
+@@ -4,3 +4,3 @@ intro
+ # Notes
+-Original
++Updated body
+ end
+"#,
+    );
+    let thread = |line: Option<u32>, resolved: bool, outdated: bool, body: &str| {
+        json!({
+            "path": "docs/notes.md", "line": line, "startLine": null, "diffSide": "RIGHT",
+            "isResolved": resolved, "isOutdated": outdated,
+            "comments": {"nodes": [
+                {"author": {"login": "erin"}, "body": body},
+                {"author": {"login": "dave"}, "body": "done"}
+            ]}
+        })
+    };
+    gh.write_review_threads(
+        12,
+        json!([
+            thread(Some(5), false, false, "reword this"),
+            thread(Some(5), true, false, "typo"),
+            thread(None, false, true, "old remark"),
+        ]),
+    );
+
+    let mut app = harness.app();
+    app.open_pr_picker();
+    wait_for_pr_list(&mut app);
+    app.pr_picker_select();
+    wait_for_pr_loaded(&mut app);
+    assert_eq!(app.pr.threads.len(), 3);
+
+    // Without the PR's commits the view is the patch: a separator for lines
+    // 1-3, then the hunk with its real line numbers.
+    assert!(app.pr.revisions.is_none());
+    assert_eq!(app.gutter_line_number(DiffSide::New, 0), None);
+    assert_eq!(app.gutter_line_number(DiffSide::New, 2), Some(5));
+    assert!(app.comment_index.has_review_thread(DiffSide::New, 2));
+    assert!(!app.comment_index.has_review_thread(DiffSide::New, 1));
+
+    app.show_comments();
+    assert_eq!(app.ui.mode, Mode::ViewComments);
+    let items: Vec<_> = app
+        .comments
+        .viewing
+        .iter()
+        .map(|c| {
+            (
+                c.message.as_str(),
+                c.remote,
+                c.outdated,
+                c.hunk_start_row.is_some(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            ("reword this", true, false, true),
+            ("old remark", true, true, false)
+        ]
+    );
+    assert_eq!(app.comments.viewing[0].author.as_deref(), Some("erin"));
+    assert_eq!(app.comments.viewing[0].replies[0].message, "done");
+
+    app.comments_start_reply();
+    assert_eq!(app.ui.mode, Mode::ViewComments);
+    assert_eq!(
+        app.ui.status.as_deref(),
+        Some("GitHub review threads are read-only")
+    );
+
+    app.comments_toggle_include_resolved();
+    assert_eq!(app.comments.viewing.len(), 3);
+    app.comments_toggle_include_resolved();
+
+    app.comments_jump_to_selected();
+    assert_eq!(app.ui.mode, Mode::Normal);
+    assert_eq!(app.cursor_line(), Some((DiffSide::New, 2)));
+}
+
+#[cfg(unix)]
+#[test]
+fn pr_mode_shows_whole_files_from_pr_commits() {
+    let harness = RepoHarness::new();
+    let root = harness.repo.path();
+    let git = Repository::open(root).unwrap();
+    let sig = Signature::now("Test", "test@example.com").unwrap();
+    let commit_worktree = |message: &str, parent: git2::Oid| {
+        let mut index = git.index().unwrap();
+        index.add_all(["."], IndexAddOption::DEFAULT, None).unwrap();
+        let tree = git.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = git.find_commit(parent).unwrap();
+        git.commit(None, &sig, &sig, message, &tree, &[&parent])
+            .unwrap()
+    };
+    let long_file = |changed: &str| {
+        (1..=20)
+            .map(|n| {
+                if n == 15 {
+                    changed.to_string()
+                } else {
+                    format!("line {}", n)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n"
+    };
+    fs::write(root.join("docs/long.md"), long_file("line 15")).unwrap();
+    let base = commit_worktree("base", git.head().unwrap().target().unwrap());
+    fs::write(root.join("docs/long.md"), long_file("line fifteen")).unwrap();
+    let head = commit_worktree("head", base);
+
+    let gh = GhFixture::new();
+    gh.write_pr_list(json!([
+        {
+            "number": 14,
+            "title": "Long file",
+            "headRefName": "feature/long",
+            "baseRefName": "main",
+            "author": {"login": "frank"},
+            "additions": 1,
+            "deletions": 1,
+            "changedFiles": 1,
+            "isDraft": false
+        }
+    ]));
+    gh.write_diff(
+        14,
+        r#"diff --git a/docs/long.md b/docs/long.md
+--- a/docs/long.md
++++ b/docs/long.md
+@@ -12,7 +12,7 @@ line 11
+ line 12
+ line 13
+ line 14
+-line 15
++line fifteen
+ line 16
+ line 17
+ line 18
+"#,
+    );
+    gh.write_pr_view(
+        14,
+        json!({
+            "headRefOid": head.to_string(),
+            "baseRefOid": base.to_string(),
+            "baseRefName": "main",
+            "url": "https://github.com/acme/widgets/pull/14"
+        }),
+    );
+    gh.write_review_threads(
+        14,
+        json!([{
+            "path": "docs/long.md", "line": 15, "startLine": null, "diffSide": "RIGHT",
+            "isResolved": false, "isOutdated": false,
+            "comments": {"nodes": [{"author": {"login": "erin"}, "body": "nice"}]}
+        }]),
+    );
+
+    let mut app = harness.app();
+    app.open_pr_picker();
+    wait_for_pr_list(&mut app);
+    app.pr_picker_select();
+    wait_for_pr_loaded(&mut app);
+
+    let revisions = app.pr.revisions.clone().expect("PR commits are local");
+    assert_eq!(revisions.head, head.to_string());
+    assert_eq!(revisions.base, base.to_string());
+    assert!(app.pr.patch_lines.is_none());
+    assert_eq!(app.new_buffer.as_ref().unwrap().line_count(), 20);
+    assert_eq!(app.gutter_line_number(DiffSide::New, 14), Some(15));
+    assert!(app.comment_index.has_review_thread(DiffSide::New, 14));
+    assert!(gh.log_lines().is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn pr_info_overlay_loads_checks_and_conversation() {
+    let harness = RepoHarness::new();
+    let gh = GhFixture::new();
+    gh.write_pr_list(json!([
+        {
+            "number": 15,
+            "title": "Info",
+            "headRefName": "feature/info",
+            "baseRefName": "main",
+            "author": {"login": "gina"},
+            "additions": 1,
+            "deletions": 1,
+            "changedFiles": 1,
+            "isDraft": false
+        }
+    ]));
+    gh.write_diff(
+        15,
+        r#"diff --git a/docs/notes.md b/docs/notes.md
+--- a/docs/notes.md
++++ b/docs/notes.md
+@@ -1,3 +1,3 @@
+ # Notes
+ 
+-Original
++Updated body
+"#,
+    );
+    gh.write_checks(
+        15,
+        json!([
+            {"name": "build", "workflow": "CI", "bucket": "pass", "link": ""},
+            {"name": "lint", "workflow": "CI", "bucket": "fail", "link": ""}
+        ]),
+    );
+    gh.write_pr_details(
+        15,
+        json!({
+            "body": "Rewrites the notes.",
+            "labels": [{"name": "docs"}],
+            "reviewRequests": [{"login": "hank"}],
+            "comments": [
+                {"author": {"login": "hank"}, "body": "Will look", "createdAt": "2025-05-01T10:00:00Z"}
+            ]
+        }),
+    );
+
+    let mut app = harness.app();
+    app.open_pr_picker();
+    wait_for_pr_list(&mut app);
+    app.pr_picker_select();
+    wait_for_pr_loaded(&mut app);
+    wait_for_pr_info(&mut app);
+
+    let checks = app.pr.checks.as_deref().unwrap();
+    assert_eq!(
+        ChecksSummary::of(checks),
+        ChecksSummary {
+            passed: 1,
+            failed: 1,
+            pending: 0,
+            skipped: 0
+        }
+    );
+    let details = app.pr.details.as_ref().unwrap();
+    assert_eq!(details.body, "Rewrites the notes.");
+    assert_eq!(details.labels, ["docs"]);
+    assert_eq!(details.review_requests, ["hank"]);
+    assert_eq!(details.comments[0].author.as_deref(), Some("hank"));
+
+    app.open_pr_info();
+    assert_eq!(app.ui.mode, Mode::PRInfo);
+    app.scroll_pr_info(3);
+    assert_eq!(app.pr.info_scroll, 3);
+    app.close_pr_info();
+    assert_eq!(app.ui.mode, Mode::Normal);
+    // Reads only; nothing is posted.
+    assert!(gh.log_lines().is_empty());
+
+    app.exit_pr_mode();
+    assert!(app.pr.checks.is_none());
+    assert!(app.pr.details.is_none());
+}
+
 #[cfg(unix)]
 #[test]
 fn gh_fixture_handles_list_and_diff() {
@@ -718,3 +1562,93 @@ fn gh_fixture_unknown_command_fails_and_logs() {
     assert_eq!(logs.len(), 1);
     assert!(logs[0].starts_with("unexpected gh invocation"));
 }
+
+#[test]
+fn worktree_picker_switches_checkout() {
+    let harness = RepoHarness::new();
+    let linked_dir = TempDir::new().unwrap();
+    let linked = linked_dir.path().join("feature");
+    let git = Repository::open(harness.repo.path()).unwrap();
+    git.worktree("feature", &linked, None).unwrap();
+    fs::write(linked.join("feature.txt"), "feature\n").unwrap();
+
+    let mut app = harness.app();
+    assert!(!app.files.iter().any(|f| f.path.as_str() == "feature.txt"));
+
+    app.open_worktree_picker();
+    assert_eq!(app.ui.mode, Mode::WorktreePicker);
+    assert_eq!(app.worktree.list.len(), 2);
+    assert!(app.worktree.list[app.worktree.selected].is_current);
+
+    app.worktree_select_next();
+    app.worktree_apply();
+    assert_eq!(app.ui.mode, Mode::Normal);
+    assert!(app.repo.is_linked_worktree());
+    assert_eq!(app.files.len(), 1);
+    assert_eq!(app.files[0].path.as_str(), "feature.txt");
+}
+
+#[cfg(unix)]
+#[test]
+fn control_commands_open_files_and_switch_source() {
+    use quickdiff::core::ControlCommand;
+
+    let harness = RepoHarness::new();
+    let mut app = harness.app();
+    wait_for_diff(&mut app);
+
+    app.apply_control_command(ControlCommand::Open {
+        path: FILE_NOTES.to_string(),
+        line: Some(3),
+    })
+    .unwrap();
+    wait_for_diff(&mut app);
+    assert_eq!(app.selected_file().unwrap().path.as_str(), FILE_NOTES);
+    assert_eq!(app.focus, Focus::Diff);
+    assert!(app.viewer.pending_line.is_none());
+
+    let err = app
+        .apply_control_command(ControlCommand::Open {
+            path: "missing.rs".to_string(),
+            line: None,
+        })
+        .unwrap_err();
+    assert!(err.contains("not in the current diff"));
+
+    let repo = Repository::open(harness.repo.path()).unwrap();
+    let head = repo.head().unwrap().target().unwrap().to_string();
+    app.apply_control_command(ControlCommand::Commit {
+        commit: head.clone(),
+    })
+    .unwrap();
+    assert!(matches!(&app.source, DiffSource::Commit(c) if *c == head));
+    wait_for_diff(&mut app);
+
+    app.apply_control_command(ControlCommand::Quit).unwrap();
+    assert!(app.should_quit);
+}
+
+#[test]
+fn open_in_editor_asks_before_opening_deleted_lines() {
+    let harness = RepoHarness::new();
+    fs::write(harness.repo.path().join(FILE_ALPHA), "alpha line one\n").unwrap();
+    let mut app = harness.app();
+    wait_for_diff(&mut app);
+    select_file(&mut app, FILE_ALPHA);
+
+    app.viewer.scroll_y = 0;
+    assert_eq!(app.cursor_line(), Some((DiffSide::New, 0)));
+    while app.cursor_line() != Some((DiffSide::Old, 1)) {
+        app.viewer.scroll_y += 1;
+        assert!(app.viewer.scroll_y < 10, "deleted line not found");
+    }
+
+    app.open_selected_in_editor();
+    assert!(
+        app.ui
+            .status
+            .as_deref()
+            .is_some_and(|s| s.contains("press o again"))
+    );
+    assert!(app.viewer.confirm_open_old.is_some());
+}